@@ -0,0 +1,180 @@
+// gl-api/src/handlers/drafts.rs
+
+//! Review of entries before posting; see `gl_core`'s workflow module.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use gl_core::{DraftEntry, DraftId, JournalEntry};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::change_books;
+use crate::{Actor, ApiError, AppState, ErrorBody};
+
+/// Comment of a reviewer; required to reject a draft.
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default)]
+pub struct Review {
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/companies/{company}/drafts",
+    params(("company" = String, Path, description = "Company id")),
+    responses((status = 200, body = [DraftEntry]), (status = 404, body = ErrorBody))
+)]
+pub async fn list_drafts(
+    State(state): State<AppState>,
+    _: Actor,
+    Path(company): Path<String>,
+) -> Result<Json<Vec<DraftEntry>>, ApiError> {
+    let workspace = state.workspace.read().expect("workspace lock");
+    Ok(Json(workspace.books(&company)?.ledger().drafts().to_vec()))
+}
+
+/// Saves a draft; it may be unbalanced until it is submitted.
+#[utoipa::path(
+    post,
+    path = "/companies/{company}/drafts",
+    params(("company" = String, Path, description = "Company id")),
+    request_body = JournalEntry,
+    responses((status = 201, body = DraftEntry), (status = 403, body = ErrorBody), (status = 404, body = ErrorBody))
+)]
+pub async fn save_draft(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(company): Path<String>,
+    Json(entry): Json<JournalEntry>,
+) -> Result<(StatusCode, Json<DraftEntry>), ApiError> {
+    let draft = change_books(&state, &company, actor, |books| {
+        let ledger = books.ledger_mut();
+        let id = ledger.save_draft(entry)?;
+        Ok(ledger.draft(id)?.clone())
+    })?;
+    Ok((StatusCode::CREATED, Json(draft)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/companies/{company}/drafts/{id}",
+    params(("company" = String, Path, description = "Company id"), ("id" = u64, Path, description = "Draft id")),
+    request_body = JournalEntry,
+    responses(
+        (status = 200, body = DraftEntry),
+        (status = 403, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+        (status = 422, body = ErrorBody)
+    )
+)]
+pub async fn update_draft(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path((company, id)): Path<(String, DraftId)>,
+    Json(entry): Json<JournalEntry>,
+) -> Result<Json<DraftEntry>, ApiError> {
+    change_books(&state, &company, actor, |books| {
+        let ledger = books.ledger_mut();
+        ledger.update_draft(id, entry)?;
+        Ok(Json(ledger.draft(id)?.clone()))
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/companies/{company}/drafts/{id}/submit",
+    params(("company" = String, Path, description = "Company id"), ("id" = u64, Path, description = "Draft id")),
+    responses(
+        (status = 200, body = DraftEntry),
+        (status = 403, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+        (status = 422, body = ErrorBody)
+    )
+)]
+pub async fn submit_draft(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path((company, id)): Path<(String, DraftId)>,
+) -> Result<Json<DraftEntry>, ApiError> {
+    change_books(&state, &company, actor, |books| {
+        let ledger = books.ledger_mut();
+        ledger.submit_draft(id)?;
+        Ok(Json(ledger.draft(id)?.clone()))
+    })
+}
+
+/// Approves a submitted draft; the approver cannot be who prepared it.
+#[utoipa::path(
+    post,
+    path = "/companies/{company}/drafts/{id}/approve",
+    params(("company" = String, Path, description = "Company id"), ("id" = u64, Path, description = "Draft id")),
+    request_body = Review,
+    responses(
+        (status = 200, body = DraftEntry),
+        (status = 403, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+        (status = 422, body = ErrorBody)
+    )
+)]
+pub async fn approve_draft(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path((company, id)): Path<(String, DraftId)>,
+    Json(review): Json<Review>,
+) -> Result<Json<DraftEntry>, ApiError> {
+    change_books(&state, &company, actor, |books| {
+        let ledger = books.ledger_mut();
+        ledger.approve_draft(id, review.comment.as_deref())?;
+        Ok(Json(ledger.draft(id)?.clone()))
+    })
+}
+
+/// Sends a draft back to its preparer with a comment.
+#[utoipa::path(
+    post,
+    path = "/companies/{company}/drafts/{id}/reject",
+    params(("company" = String, Path, description = "Company id"), ("id" = u64, Path, description = "Draft id")),
+    request_body = Review,
+    responses(
+        (status = 200, body = DraftEntry),
+        (status = 403, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+        (status = 422, body = ErrorBody)
+    )
+)]
+pub async fn reject_draft(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path((company, id)): Path<(String, DraftId)>,
+    Json(review): Json<Review>,
+) -> Result<Json<DraftEntry>, ApiError> {
+    change_books(&state, &company, actor, |books| {
+        let ledger = books.ledger_mut();
+        ledger.reject_draft(id, review.comment.as_deref().unwrap_or_default())?;
+        Ok(Json(ledger.draft(id)?.clone()))
+    })
+}
+
+/// Posts an approved draft to the company's books.
+#[utoipa::path(
+    post,
+    path = "/companies/{company}/drafts/{id}/post",
+    params(("company" = String, Path, description = "Company id"), ("id" = u64, Path, description = "Draft id")),
+    responses(
+        (status = 200, body = DraftEntry),
+        (status = 403, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+        (status = 422, body = ErrorBody)
+    )
+)]
+pub async fn post_draft(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path((company, id)): Path<(String, DraftId)>,
+) -> Result<Json<DraftEntry>, ApiError> {
+    change_books(&state, &company, actor, |books| {
+        books.post_draft(id)?;
+        Ok(Json(books.ledger().draft(id)?.clone()))
+    })
+}
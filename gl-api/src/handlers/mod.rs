@@ -0,0 +1,259 @@
+// gl-api/src/handlers/mod.rs
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use chrono::NaiveDate;
+use gl_core::{
+    balance_sheet as b01, income_statement as b02, Account, BalanceSheet, Company, CompanyBooks, IncomeStatement,
+    JournalEntry, Period, PostedEntry, TrialBalance,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{Actor, ApiError, AppState, ErrorBody};
+
+pub mod drafts;
+
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
+pub struct Validation {
+    pub valid: bool,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct AsOf {
+    /// Transaction date to report at; every entry when absent.
+    pub as_of: Option<NaiveDate>,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct DateRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+/// Runs `change` on the books of company `id` as the signed-in user, then
+/// saves them, so what it recorded (a refusal too) outlives the server.
+pub(crate) fn change_books<T>(
+    state: &AppState,
+    id: &str,
+    actor: Actor,
+    change: impl FnOnce(&mut CompanyBooks) -> Result<T, ApiError>,
+) -> Result<T, ApiError> {
+    let mut workspace = state.workspace.write().expect("workspace lock");
+    let books = workspace.books_mut(id)?;
+    if let Actor(Some(username)) = actor {
+        books.ledger_mut().set_actor(&username);
+    }
+    let result = change(books);
+    if let Some(store) = &state.store {
+        store.save(books).map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+    result
+}
+
+#[utoipa::path(get, path = "/companies", responses((status = 200, body = [Company])))]
+pub async fn list_companies(State(state): State<AppState>, _: Actor) -> Json<Vec<Company>> {
+    let workspace = state.workspace.read().expect("workspace lock");
+    Json(workspace.companies().cloned().collect())
+}
+
+#[utoipa::path(
+    get,
+    path = "/companies/{company}/accounts",
+    params(("company" = String, Path, description = "Company id")),
+    responses((status = 200, body = [Account]), (status = 404, body = ErrorBody))
+)]
+pub async fn list_accounts(
+    State(state): State<AppState>,
+    _: Actor,
+    Path(company): Path<String>,
+) -> Result<Json<Vec<Account>>, ApiError> {
+    let workspace = state.workspace.read().expect("workspace lock");
+    Ok(Json(workspace.books(&company)?.coa().accounts.clone()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/companies/{company}/periods",
+    params(("company" = String, Path, description = "Company id")),
+    responses((status = 200, body = [Period]), (status = 404, body = ErrorBody))
+)]
+pub async fn list_periods(
+    State(state): State<AppState>,
+    _: Actor,
+    Path(company): Path<String>,
+) -> Result<Json<Vec<Period>>, ApiError> {
+    let workspace = state.workspace.read().expect("workspace lock");
+    Ok(Json(workspace.books(&company)?.periods().periods().to_vec()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/companies/{company}/periods",
+    params(("company" = String, Path, description = "Company id")),
+    request_body = Period,
+    responses(
+        (status = 201, body = Period),
+        (status = 403, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+        (status = 422, body = ErrorBody)
+    )
+)]
+pub async fn open_period(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(company): Path<String>,
+    Json(period): Json<Period>,
+) -> Result<(StatusCode, Json<Period>), ApiError> {
+    let period = Period::new(period.start_date, period.end_date);
+    change_books(&state, &company, actor, |books| Ok(books.open_period(period.clone())?))?;
+    Ok((StatusCode::CREATED, Json(period)))
+}
+
+/// Closes the most recently opened period and returns it.
+#[utoipa::path(
+    post,
+    path = "/companies/{company}/periods/close",
+    params(("company" = String, Path, description = "Company id")),
+    responses(
+        (status = 200, body = Period),
+        (status = 403, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+        (status = 422, body = ErrorBody)
+    )
+)]
+pub async fn close_period(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(company): Path<String>,
+) -> Result<Json<Period>, ApiError> {
+    change_books(&state, &company, actor, |books| {
+        books.close_current_period()?;
+        let closed = books.periods().get_current_period().cloned();
+        closed
+            .map(Json)
+            .ok_or_else(|| ApiError(StatusCode::UNPROCESSABLE_ENTITY, "no period is open".to_string()))
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/companies/{company}/entries",
+    params(("company" = String, Path, description = "Company id")),
+    responses((status = 200, body = [PostedEntry]), (status = 404, body = ErrorBody))
+)]
+pub async fn list_entries(
+    State(state): State<AppState>,
+    _: Actor,
+    Path(company): Path<String>,
+) -> Result<Json<Vec<PostedEntry>>, ApiError> {
+    let workspace = state.workspace.read().expect("workspace lock");
+    Ok(Json(workspace.books(&company)?.ledger().entries().to_vec()))
+}
+
+/// Posts an entry after checking it against the company's chart of
+/// accounts, open periods and voucher numbering.
+#[utoipa::path(
+    post,
+    path = "/companies/{company}/entries",
+    params(("company" = String, Path, description = "Company id")),
+    request_body = JournalEntry,
+    responses(
+        (status = 201, body = PostedEntry),
+        (status = 403, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+        (status = 422, body = ErrorBody)
+    )
+)]
+pub async fn post_entry(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(company): Path<String>,
+    Json(entry): Json<JournalEntry>,
+) -> Result<(StatusCode, Json<PostedEntry>), ApiError> {
+    let posted = change_books(&state, &company, actor, |books| {
+        let id = books.post_entry(entry)?;
+        Ok(books.ledger().get_entry(id).cloned().ok_or(gl_core::LedgerError::EntryNotFound(id))?)
+    })?;
+    Ok((StatusCode::CREATED, Json(posted)))
+}
+
+/// Checks an entry as `POST /entries` would, without posting it.
+#[utoipa::path(
+    post,
+    path = "/companies/{company}/entries/validate",
+    params(("company" = String, Path, description = "Company id")),
+    request_body = JournalEntry,
+    responses((status = 200, body = Validation), (status = 404, body = ErrorBody), (status = 422, body = ErrorBody))
+)]
+pub async fn validate_entry(
+    State(state): State<AppState>,
+    _: Actor,
+    Path(company): Path<String>,
+    Json(entry): Json<JournalEntry>,
+) -> Result<Json<Validation>, ApiError> {
+    let workspace = state.workspace.read().expect("workspace lock");
+    let books = workspace.books(&company)?;
+    books.ledger().validate_entry(&entry, books.coa(), books.periods())?;
+    Ok(Json(Validation { valid: true }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/companies/{company}/trial-balance",
+    params(("company" = String, Path, description = "Company id"), AsOf),
+    responses((status = 200, body = TrialBalance), (status = 404, body = ErrorBody))
+)]
+pub async fn trial_balance(
+    State(state): State<AppState>,
+    _: Actor,
+    Path(company): Path<String>,
+    Query(query): Query<AsOf>,
+) -> Result<Json<TrialBalance>, ApiError> {
+    let workspace = state.workspace.read().expect("workspace lock");
+    let ledger = workspace.books(&company)?.ledger();
+    Ok(Json(match query.as_of {
+        Some(date) => ledger.as_of_date(date),
+        None => ledger.trial_balance(),
+    }))
+}
+
+/// B01-DNN, the balance sheet (Bảng cân đối kế toán).
+#[utoipa::path(
+    get,
+    path = "/companies/{company}/reports/b01",
+    params(("company" = String, Path, description = "Company id"), ("as_of" = NaiveDate, Query, description = "Report date")),
+    responses((status = 200, body = BalanceSheet), (status = 404, body = ErrorBody))
+)]
+pub async fn balance_sheet(
+    State(state): State<AppState>,
+    _: Actor,
+    Path(company): Path<String>,
+    Query(query): Query<ReportDate>,
+) -> Result<Json<BalanceSheet>, ApiError> {
+    let workspace = state.workspace.read().expect("workspace lock");
+    Ok(Json(b01(workspace.books(&company)?.ledger(), query.as_of)))
+}
+
+#[derive(Deserialize)]
+pub struct ReportDate {
+    pub as_of: NaiveDate,
+}
+
+/// B02-DNN, the income statement (Kết quả hoạt động kinh doanh).
+#[utoipa::path(
+    get,
+    path = "/companies/{company}/reports/b02",
+    params(("company" = String, Path, description = "Company id"), DateRange),
+    responses((status = 200, body = IncomeStatement), (status = 404, body = ErrorBody))
+)]
+pub async fn income_statement(
+    State(state): State<AppState>,
+    _: Actor,
+    Path(company): Path<String>,
+    Query(range): Query<DateRange>,
+) -> Result<Json<IncomeStatement>, ApiError> {
+    let workspace = state.workspace.read().expect("workspace lock");
+    Ok(Json(b02(workspace.books(&company)?.ledger(), range.from, range.to)))
+}
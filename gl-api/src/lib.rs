@@ -0,0 +1,182 @@
+// gl-api/src/lib.rs
+
+//! REST API over the books of the companies in a `Workspace`. Every route
+//! below `/companies/{company}` works on that company's books only.
+//!
+//! With a user directory, requests sign in with `Authorization: Bearer
+//! <token>` and act as that user, whose roles the ledger enforces.
+//!
+//! With a `BookStore`, the books are read from their files at start and
+//! every request that changes them writes them back.
+
+use std::sync::{Arc, RwLock};
+
+use axum::extract::FromRequestParts;
+use axum::http::header::AUTHORIZATION;
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+use gl_core::{LedgerError, UserDirectory, Workspace};
+use serde::{Deserialize, Serialize};
+use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+use utoipa::{Modify, OpenApi, ToSchema};
+
+mod handlers;
+mod store;
+
+use handlers::drafts;
+pub use store::BookStore;
+
+/// Shared by every request; the workspace is locked for the duration of a
+/// handler, so postings to one company are serialized.
+#[derive(Clone)]
+pub struct AppState {
+    workspace: Arc<RwLock<Workspace>>,
+    /// Anyone may call the API when empty.
+    users: Arc<UserDirectory>,
+    /// Where changed books are saved; they live in memory only without one.
+    store: Option<Arc<BookStore>>,
+}
+
+impl AppState {
+    pub fn new(workspace: Workspace) -> Self {
+        AppState { workspace: Arc::new(RwLock::new(workspace)), users: Arc::new(UserDirectory::new()), store: None }
+    }
+
+    /// Requires every request to sign in as one of `users`. The ledgers of
+    /// the workspace should be given the same directory (`Ledger::with_users`).
+    pub fn with_users(mut self, users: UserDirectory) -> Self {
+        self.users = Arc::new(users);
+        self
+    }
+
+    /// Saves the books to `store` after every request that changes them;
+    /// the workspace should have been opened from the same store.
+    pub fn with_store(mut self, store: BookStore) -> Self {
+        self.store = Some(Arc::new(store));
+        self
+    }
+}
+
+/// The user a request signed in as; `None` when the API has no users.
+pub struct Actor(pub Option<String>);
+
+impl FromRequestParts<AppState> for Actor {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        if state.users.is_empty() {
+            return Ok(Actor(None));
+        }
+        let token = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        match token.and_then(|t| state.users.authenticate(t)) {
+            Some(user) => Ok(Actor(Some(user.username.clone()))),
+            None => Err(ApiError(StatusCode::UNAUTHORIZED, "a valid bearer token is required".to_string())),
+        }
+    }
+}
+
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/openapi.json", get(openapi_json))
+        .route("/companies", get(handlers::list_companies))
+        .route("/companies/{company}/accounts", get(handlers::list_accounts))
+        .route("/companies/{company}/periods", get(handlers::list_periods).post(handlers::open_period))
+        .route("/companies/{company}/periods/close", post(handlers::close_period))
+        .route("/companies/{company}/entries", get(handlers::list_entries).post(handlers::post_entry))
+        .route("/companies/{company}/entries/validate", post(handlers::validate_entry))
+        .route("/companies/{company}/drafts", get(drafts::list_drafts).post(drafts::save_draft))
+        .route("/companies/{company}/drafts/{id}", put(drafts::update_draft))
+        .route("/companies/{company}/drafts/{id}/submit", post(drafts::submit_draft))
+        .route("/companies/{company}/drafts/{id}/approve", post(drafts::approve_draft))
+        .route("/companies/{company}/drafts/{id}/reject", post(drafts::reject_draft))
+        .route("/companies/{company}/drafts/{id}/post", post(drafts::post_draft))
+        .route("/companies/{company}/trial-balance", get(handlers::trial_balance))
+        .route("/companies/{company}/reports/b01", get(handlers::balance_sheet))
+        .route("/companies/{company}/reports/b02", get(handlers::income_statement))
+        .with_state(state)
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "gl-api", description = "General ledger for Vietnamese SMEs (Circular 133)"),
+    paths(
+        handlers::list_companies,
+        handlers::list_accounts,
+        handlers::list_periods,
+        handlers::open_period,
+        handlers::close_period,
+        handlers::list_entries,
+        handlers::post_entry,
+        handlers::validate_entry,
+        drafts::list_drafts,
+        drafts::save_draft,
+        drafts::update_draft,
+        drafts::submit_draft,
+        drafts::approve_draft,
+        drafts::reject_draft,
+        drafts::post_draft,
+        handlers::trial_balance,
+        handlers::balance_sheet,
+        handlers::income_statement,
+    ),
+    modifiers(&BearerAuth),
+    security(("bearer" = []))
+)]
+pub struct ApiDoc;
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme("bearer", SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)));
+    }
+}
+
+/// The OpenAPI document of the API, as served at `/openapi.json`.
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(openapi())
+}
+
+/// Body of every error response.
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
+pub struct ErrorBody {
+    pub error: String,
+}
+
+/// A `LedgerError` as an HTTP response: unknown companies and entries are
+/// 404, actions the user's roles do not allow 403, anything else the ledger
+/// refuses 422.
+pub struct ApiError(StatusCode, String);
+
+impl From<LedgerError> for ApiError {
+    fn from(error: LedgerError) -> Self {
+        let status = match error {
+            LedgerError::UnknownCompany(_) | LedgerError::EntryNotFound(_) | LedgerError::DraftNotFound(_) => {
+                StatusCode::NOT_FOUND
+            }
+            LedgerError::PermissionDenied(..) | LedgerError::UnknownUser(_) | LedgerError::SelfApproval(..) => {
+                StatusCode::FORBIDDEN
+            }
+            _ => StatusCode::UNPROCESSABLE_ENTITY,
+        };
+        ApiError(status, error.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(ErrorBody { error: self.1 })).into_response()
+    }
+}
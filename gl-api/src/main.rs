@@ -0,0 +1,123 @@
+use std::process;
+
+use clap::{Arg, Command};
+use gl_api::{openapi, router, AppState, BookStore};
+use gl_core::{CompanyRegistry, UserDirectory, Workspace};
+use tracing::info;
+
+fn setup_logging() {
+    tracing_subscriber::fmt()
+        .compact()
+        .with_max_level(tracing::Level::INFO)
+        .init();
+}
+
+#[tokio::main]
+async fn main() {
+    setup_logging();
+
+    let matches = Command::new("gl-api")
+        .arg(
+            Arg::new("COMPANIES")
+                .long("companies")
+                .value_name("FILE")
+                .default_value("data/companies.yaml")
+                .help("Registry of the companies served (YAML)"),
+        )
+        .arg(
+            Arg::new("DATA")
+                .long("data")
+                .value_name("DIR")
+                .default_value("data")
+                .help("Directory of the books; each company's are in companies/<ID>/ under it"),
+        )
+        .arg(
+            Arg::new("USERS")
+                .long("users")
+                .value_name("FILE")
+                .help("Users and roles (YAML); requests then need a bearer token"),
+        )
+        .arg(
+            Arg::new("BIND")
+                .long("bind")
+                .value_name("ADDR")
+                .default_value("127.0.0.1:8080")
+                .help("Address to listen on"),
+        )
+        .arg(
+            Arg::new("OPENAPI")
+                .long("openapi")
+                .help("Print the OpenAPI document and exit")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .get_matches();
+
+    if matches.get_flag("OPENAPI") {
+        match openapi().to_pretty_json() {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("❌ Failed to write OpenAPI document: {}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let path = matches.get_one::<String>("COMPANIES").unwrap();
+    let registry = std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| CompanyRegistry::from_yaml(&contents).map_err(|e| e.to_string()));
+    let registry = match registry {
+        Ok(registry) => registry,
+        Err(e) => {
+            eprintln!("❌ Failed to read companies {}: {}", path, e);
+            process::exit(1);
+        }
+    };
+
+    let users = match matches.get_one::<String>("USERS") {
+        Some(path) => {
+            let users = std::fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|contents| UserDirectory::from_yaml(&contents).map_err(|e| e.to_string()));
+            match users {
+                Ok(users) => users,
+                Err(e) => {
+                    eprintln!("❌ Failed to read users {}: {}", path, e);
+                    process::exit(1);
+                }
+            }
+        }
+        None => UserDirectory::new(),
+    };
+
+    let store = BookStore::new(matches.get_one::<String>("DATA").unwrap());
+    let mut workspace = Workspace::new();
+    for company in registry.companies {
+        let books = company
+            .load_coa()
+            .and_then(|coa| store.open(company.clone(), coa, &users))
+            .map_err(|e| e.to_string());
+        match books.and_then(|books| workspace.insert(books).map(|_| ()).map_err(|e| e.to_string())) {
+            Ok(()) => info!("Serving the books of {} ({})", company.name, company.id),
+            Err(e) => {
+                eprintln!("❌ Failed to open the books of {}: {}", company.id, e);
+                process::exit(1);
+            }
+        }
+    }
+
+    let bind = matches.get_one::<String>("BIND").unwrap();
+    let listener = match tokio::net::TcpListener::bind(bind).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("❌ Cannot listen on {}: {}", bind, e);
+            process::exit(1);
+        }
+    };
+    info!("gl-api listening on {}", bind);
+    if let Err(e) = axum::serve(listener, router(AppState::new(workspace).with_users(users).with_store(store))).await {
+        eprintln!("❌ Server error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,106 @@
+// gl-api/src/store.rs
+
+//! The files the books are kept in between runs: each company's ledger in
+//! `<dir>/companies/<id>/ledger.json` with its audit trail in
+//! `audit_log.jsonl` beside it, as `gl --company <id>` keeps them.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use gl_core::{AuditLog, CoaConfig, Company, CompanyBooks, Ledger, UserDirectory};
+
+/// How much of a company's books is already in its files.
+#[derive(Clone, Copy)]
+struct Saved {
+    sequence: Option<u64>,
+    audit_written: usize,
+}
+
+pub struct BookStore {
+    dir: PathBuf,
+    saved: Mutex<BTreeMap<String, Saved>>,
+}
+
+impl BookStore {
+    /// Keeps the books under `dir`, e.g. `data`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        BookStore { dir: dir.into(), saved: Mutex::new(BTreeMap::new()) }
+    }
+
+    pub fn ledger_path(&self, id: &str) -> PathBuf {
+        self.dir.join("companies").join(id).join("ledger.json")
+    }
+
+    pub fn audit_path(&self, id: &str) -> PathBuf {
+        self.dir.join("companies").join(id).join("audit_log.jsonl")
+    }
+
+    /// Opens the books of a company from its files, or empty books when it
+    /// has none yet. Books without users are given `users`; books that have
+    /// their own must be served with the same.
+    pub fn open(&self, company: Company, coa: CoaConfig, users: &UserDirectory) -> Result<CompanyBooks, Box<dyn Error>> {
+        let ledger_path = self.ledger_path(&company.id);
+        let audit_path = self.audit_path(&company.id);
+        let (mut ledger, sequence) = if ledger_path.exists() {
+            let contents = std::fs::read_to_string(&ledger_path)
+                .map_err(|e| format!("Failed to read ledger {}: {}", ledger_path.display(), e))?;
+            let ledger: Ledger = serde_json::from_str(&contents)
+                .map_err(|e| format!("Failed to read ledger {}: {}", ledger_path.display(), e))?;
+            let sequence = ledger.sequence();
+            (ledger, Some(sequence))
+        } else {
+            (Ledger::new(), None)
+        };
+        if ledger.users().is_empty() {
+            ledger = ledger.with_users(users.clone());
+        } else if ledger.users() != users {
+            return Err(format!("{} has users of its own; serve it with the same --users", ledger_path.display()).into());
+        }
+        let audit = AuditLog::load(&audit_path)
+            .map_err(|e| format!("Failed to read audit trail {}: {}", audit_path.display(), e))?;
+        let saved = Saved { sequence, audit_written: audit.events().len() };
+        let ledger = ledger.with_audit_log(audit).map_err(|e| {
+            format!("Audit trail {} does not match ledger {}: {}", audit_path.display(), ledger_path.display(), e)
+        })?;
+        let books = CompanyBooks::with_ledger(company, coa, ledger)?;
+        self.saved.lock().expect("store lock").insert(books.company().id.clone(), saved);
+        Ok(books)
+    }
+
+    /// Appends the new audit events (refusals included), then writes the
+    /// ledger, which records where the trail ends, if anything was recorded
+    /// since it was last saved.
+    pub fn save(&self, books: &CompanyBooks) -> Result<(), Box<dyn Error>> {
+        let id = &books.company().id;
+        let ledger = books.ledger();
+        let mut saved = self.saved.lock().expect("store lock");
+        let last = saved.get(id).copied().unwrap_or(Saved { sequence: None, audit_written: 0 });
+        let audited = ledger.audit_log().events().len() > last.audit_written;
+        let audit_path = self.audit_path(id);
+        ledger
+            .audit_log()
+            .append_to(&audit_path, last.audit_written)
+            .map_err(|e| format!("Failed to write audit trail {}: {}", audit_path.display(), e))?;
+        if audited || (last.sequence != Some(ledger.sequence()) && ledger.sequence() > 0) {
+            write_ledger(&self.ledger_path(id), ledger)?;
+        }
+        saved.insert(
+            id.clone(),
+            Saved { sequence: Some(ledger.sequence()), audit_written: ledger.audit_log().events().len() },
+        );
+        Ok(())
+    }
+}
+
+/// Writes aside and renames, so a failed write leaves the old file.
+fn write_ledger(path: &Path, ledger: &Ledger) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string(ledger)?)?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("Failed to write ledger {}: {}", path.display(), e))?;
+    Ok(())
+}
@@ -0,0 +1,268 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use chrono::NaiveDate;
+use gl_api::{router, AppState, BookStore};
+use gl_core::{
+    hash_token, Account, AccountCode, AccountingMethod, CoaConfig, CoaRegime, Company, CompanyBooks, JournalEntry,
+    JournalLine, Ledger, Period, Role, User, UserDirectory, Workspace,
+};
+use http_body_util::BodyExt;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+fn company(id: &str, tax_code: &str) -> Company {
+    Company {
+        id: id.to_string(),
+        name: format!("Công ty {}", id),
+        tax_code: tax_code.to_string(),
+        address: String::new(),
+        fiscal_year_start: 1,
+        coa_regime: CoaRegime::Circular133,
+        accounting_method: AccountingMethod::Perpetual,
+        coa_file: None,
+    }
+}
+
+fn coa() -> CoaConfig {
+    let account = |code: &str, classification: &str| Account {
+        code: code.to_string(),
+        name: String::new(),
+        classification: classification.to_string(),
+        default_balance: String::new(),
+        description: String::new(),
+    };
+    CoaConfig { accounts: vec![account("1111", "Asset"), account("5111", "Revenue")] }
+}
+
+fn app() -> Router {
+    let mut workspace = Workspace::new();
+    let mut books = CompanyBooks::new(company("phuthanh", "0312345678"), coa()).unwrap();
+    books.open_fiscal_year(2025).unwrap();
+    workspace.insert(books).unwrap();
+    workspace.insert(CompanyBooks::new(company("anphat", "0109876543"), coa()).unwrap()).unwrap();
+    router(AppState::new(workspace))
+}
+
+fn sale(day: u32, amount: i64) -> Value {
+    let line = |account: &str, debit: i64, credit: i64| {
+        JournalLine::new(AccountCode::new(account).unwrap(), Decimal::from(debit), Decimal::from(credit)).unwrap()
+    };
+    let entry = JournalEntry::new(
+        NaiveDate::from_ymd_opt(2025, 1, day).unwrap(),
+        vec![line("1111", amount, 0), line("5111", 0, amount)],
+        "Bán hàng".to_string(),
+    )
+    .unwrap();
+    serde_json::to_value(entry).unwrap()
+}
+
+fn secured_app() -> Router {
+    let user = |username: &str, role: Role| User {
+        username: username.to_string(),
+        name: String::new(),
+        roles: vec![role],
+        token_sha256: Some(hash_token(&format!("{}-token", username))),
+    };
+    let users = UserDirectory {
+        users: vec![
+            user("ketoanvien", Role::Clerk),
+            user("ketoantruong", Role::ChiefAccountant),
+            user("kiemtoan", Role::Auditor),
+        ],
+    };
+    let ledger = Ledger::new().with_users(users.clone());
+    let mut books = CompanyBooks::with_ledger(company("phuthanh", "0312345678"), coa(), ledger).unwrap();
+    books.ledger_mut().set_actor("ketoantruong");
+    books.open_fiscal_year(2025).unwrap();
+    let mut workspace = Workspace::new();
+    workspace.insert(books).unwrap();
+    router(AppState::new(workspace).with_users(users))
+}
+
+/// The books of phuthanh kept under `dir`, served as `gl-api --data <dir>` does.
+fn stored_app(dir: &std::path::Path) -> Router {
+    let store = BookStore::new(dir);
+    let books = store.open(company("phuthanh", "0312345678"), coa(), &UserDirectory::new()).unwrap();
+    let mut workspace = Workspace::new();
+    workspace.insert(books).unwrap();
+    router(AppState::new(workspace).with_store(store))
+}
+
+async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    send_as(app, None, method, uri, body).await
+}
+
+async fn send_as(app: &Router, token: Option<&str>, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let mut request = Request::builder().method(method).uri(uri).header("content-type", "application/json");
+    if let Some(token) = token {
+        request = request.header("authorization", format!("Bearer {}", token));
+    }
+    let request = request.body(body.map_or_else(Body::empty, |b| Body::from(b.to_string()))).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let value = if bytes.is_empty() { Value::Null } else { serde_json::from_slice(&bytes).unwrap() };
+    (status, value)
+}
+
+// --- API Tests ---
+#[tokio::test]
+async fn test_post_entry_and_trial_balance() {
+    let app = app();
+    let (status, posted) = send(&app, "POST", "/companies/phuthanh/entries", Some(sale(10, 1_000_000))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(posted["entry"]["lines"].as_array().unwrap().len(), 2);
+
+    let (status, entries) = send(&app, "GET", "/companies/phuthanh/entries", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(entries.as_array().unwrap().len(), 1);
+
+    let (status, trial) = send(&app, "GET", "/companies/phuthanh/trial-balance?as_of=2025-01-31", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(trial["total_debits"], trial["total_credits"]);
+
+    let (status, _) = send(&app, "GET", "/companies/phuthanh/reports/b02?from=2025-01-01&to=2025-01-31", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The other company's books are untouched.
+    let (_, entries) = send(&app, "GET", "/companies/anphat/entries", None).await;
+    assert!(entries.as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_validate_entry() {
+    let app = app();
+    let (status, body) = send(&app, "POST", "/companies/phuthanh/entries/validate", Some(sale(10, 500_000))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "valid": true }));
+
+    // anphat has no open period yet
+    let (status, body) = send(&app, "POST", "/companies/anphat/entries/validate", Some(sale(10, 500_000))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["error"].is_string());
+
+    let (_, entries) = send(&app, "GET", "/companies/phuthanh/entries", None).await;
+    assert!(entries.as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_periods() {
+    let app = app();
+    let january = Period::new(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2025, 1, 31).unwrap());
+    let (status, _) = send(&app, "POST", "/companies/anphat/periods", Some(serde_json::to_value(&january).unwrap())).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = send(&app, "POST", "/companies/anphat/entries", Some(sale(10, 200_000))).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, closed) = send(&app, "POST", "/companies/anphat/periods/close", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(closed["status"], "Closed");
+
+    let (status, _) = send(&app, "POST", "/companies/anphat/entries", Some(sale(11, 200_000))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn test_unknown_company_and_accounts() {
+    let app = app();
+    let (status, body) = send(&app, "GET", "/companies/khongco/accounts", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body["error"].as_str().unwrap().contains("khongco"));
+
+    let (status, accounts) = send(&app, "GET", "/companies/phuthanh/accounts", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(accounts.as_array().unwrap().len(), 2);
+
+    let (_, companies) = send(&app, "GET", "/companies", None).await;
+    assert_eq!(companies.as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_openapi_document() {
+    let (status, spec) = send(&app(), "GET", "/openapi.json", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(spec["paths"]["/companies/{company}/entries"]["post"].is_object());
+    assert!(spec["components"]["schemas"]["JournalEntry"].is_object());
+}
+
+#[tokio::test]
+async fn test_sign_in_and_roles() {
+    let app = secured_app();
+    let (status, _) = send(&app, "GET", "/companies/phuthanh/entries", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send_as(&app, Some("wrong"), "GET", "/companies/phuthanh/entries", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = send_as(&app, Some("kiemtoan-token"), "POST", "/companies/phuthanh/entries", Some(sale(10, 100))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send_as(&app, Some("ketoanvien-token"), "POST", "/companies/phuthanh/entries", Some(sale(10, 100))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send_as(&app, Some("ketoantruong-token"), "POST", "/companies/phuthanh/entries", Some(sale(10, 100))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send_as(&app, Some("ketoanvien-token"), "POST", "/companies/phuthanh/periods/close", None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, entries) = send_as(&app, Some("kiemtoan-token"), "GET", "/companies/phuthanh/entries", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(entries.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_draft_review() {
+    let app = secured_app();
+    let clerk = Some("ketoanvien-token");
+    let chief = Some("ketoantruong-token");
+    let (status, draft) = send_as(&app, clerk, "POST", "/companies/phuthanh/drafts", Some(sale(10, 300))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let id = draft["id"].as_u64().unwrap();
+
+    let (status, _) = send_as(&app, clerk, "POST", &format!("/companies/phuthanh/drafts/{}/submit", id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let approve = format!("/companies/phuthanh/drafts/{}/approve", id);
+    let (status, _) = send_as(&app, clerk, "POST", &approve, Some(json!({}))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let reject = format!("/companies/phuthanh/drafts/{}/reject", id);
+    let (status, draft) = send_as(&app, chief, "POST", &reject, Some(json!({ "comment": "Sai ngày" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(draft["status"], "Draft");
+
+    send_as(&app, clerk, "POST", &format!("/companies/phuthanh/drafts/{}/submit", id), None).await;
+    let (status, _) = send_as(&app, chief, "POST", &approve, Some(json!({}))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, entries) = send_as(&app, chief, "GET", "/companies/phuthanh/entries", None).await;
+    assert!(entries.as_array().unwrap().is_empty());
+
+    let (status, draft) = send_as(&app, clerk, "POST", &format!("/companies/phuthanh/drafts/{}/post", id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(draft["status"], "Posted");
+    let (_, entries) = send_as(&app, chief, "GET", "/companies/phuthanh/entries", None).await;
+    assert_eq!(entries.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_books_are_saved_between_runs() {
+    let dir = std::env::temp_dir().join(format!("gl-api-store-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let app = stored_app(&dir);
+    let january = Period::new(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2025, 1, 31).unwrap());
+    let (status, _) = send(&app, "POST", "/companies/phuthanh/periods", Some(serde_json::to_value(&january).unwrap())).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "POST", "/companies/phuthanh/entries", Some(sale(10, 1_000_000))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(dir.join("companies/phuthanh/ledger.json").exists());
+    drop(app);
+
+    // A restarted server finds the entry, the open period and the audit trail
+    let app = stored_app(&dir);
+    let (_, entries) = send(&app, "GET", "/companies/phuthanh/entries", None).await;
+    assert_eq!(entries.as_array().unwrap().len(), 1);
+    let (status, _) = send(&app, "POST", "/companies/phuthanh/entries", Some(sale(11, 500_000))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let audit = std::fs::read_to_string(dir.join("companies/phuthanh/audit_log.jsonl")).unwrap();
+    assert_eq!(audit.lines().filter(|l| l.contains("EntryPosted")).count(), 2);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
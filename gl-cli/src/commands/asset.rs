@@ -0,0 +1,231 @@
+// gl-cli/src/commands/asset.rs
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use clap::Subcommand;
+use gl_core::{asset_movements, depreciation_schedule, AccountCode, FixedAsset, ASSET_CLASSES};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::info;
+
+use super::{parse_month, range};
+use crate::context::Context;
+use crate::output::{self, amount, Table};
+
+#[derive(Subcommand, Debug)]
+pub enum AssetCommand {
+    /// Add asset cards from a YAML file (see data/assets.yaml)
+    Add { file: PathBuf },
+    /// List the asset register with cost, depreciation and net book value
+    List,
+    /// Asset classes of Circular 45/2013 and their useful life ranges
+    Classes,
+    /// Month by month depreciation schedule of an asset
+    Schedule { code: String },
+    /// Post the depreciation of a month (Nợ 642/627, Có 214)
+    Depreciate {
+        #[arg(value_name = "YYYY-MM", value_parser = parse_month)]
+        month: NaiveDate,
+    },
+    /// Dispose of an asset: proceeds to 711, net book value to 811
+    Dispose {
+        code: String,
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        date: NaiveDate,
+        /// Sale proceeds, before VAT
+        #[arg(long, default_value = "0")]
+        proceeds: Decimal,
+        /// Account receiving the proceeds
+        #[arg(long, value_name = "ACCOUNT", default_value = "1121")]
+        account: String,
+    },
+    /// Báo cáo tăng giảm TSCĐ: cost and depreciation movements over a period
+    Movements {
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        from: Option<NaiveDate>,
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        to: Option<NaiveDate>,
+    },
+}
+
+#[derive(Deserialize)]
+struct AssetCards {
+    assets: Vec<FixedAsset>,
+}
+
+pub fn run(ctx: &mut Context, command: AssetCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        AssetCommand::Add { file } => {
+            let contents = std::fs::read_to_string(&file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+            let cards: AssetCards =
+                serde_yaml::from_str(&contents).map_err(|e| format!("Failed to parse {}: {}", file.display(), e))?;
+            let mut added = vec![];
+            let mut failed = 0;
+            for asset in cards.assets {
+                let code = asset.code.clone();
+                match ctx.ledger.register_asset(asset) {
+                    Ok(()) => added.push(code),
+                    Err(e) => {
+                        failed += 1;
+                        eprintln!("❌ Asset {}: {}", code, e);
+                    }
+                }
+            }
+            info!("Registered {} asset(s) from {}", added.len(), file.display());
+            output::done(ctx.format, &format!("{} asset(s) registered, {} failed", added.len(), failed), &added)?;
+            match failed {
+                0 => Ok(()),
+                n => Err(format!("{} asset(s) of {} not registered", n, file.display()).into()),
+            }
+        }
+        AssetCommand::List => {
+            let mut table = Table::new("🏭 Sổ tài sản cố định:")
+                .column("Mã")
+                .column("Tên")
+                .column("Nhóm")
+                .column("Bộ phận")
+                .column("Ngày tăng")
+                .column("Số năm")
+                .amount("Nguyên giá")
+                .amount("Hao mòn lũy kế")
+                .amount("Giá trị còn lại")
+                .column("Tình trạng");
+            let (mut cost, mut depreciation) = (Decimal::ZERO, Decimal::ZERO);
+            for record in ctx.ledger.assets() {
+                let asset = &record.asset;
+                let status = match record.disposal() {
+                    Some(disposal) => format!("Thanh lý {}", disposal.date),
+                    None => record.depreciated_through().map(|d| format!("KH đến {}", d)).unwrap_or_default(),
+                };
+                table.row(vec![
+                    asset.code.clone(),
+                    asset.name.clone(),
+                    asset.class.clone(),
+                    asset.cost_center.clone(),
+                    asset.acquired.to_string(),
+                    asset.useful_life_years.to_string(),
+                    amount(asset.cost),
+                    amount(record.accumulated_depreciation()),
+                    amount(record.net_book_value()),
+                    status,
+                ]);
+                if record.disposal.is_none() {
+                    cost += asset.cost;
+                    depreciation += record.accumulated_depreciation();
+                }
+            }
+            table.total(vec![
+                "Đang sử dụng".to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                amount(cost),
+                amount(depreciation),
+                amount(cost - depreciation),
+                String::new(),
+            ]);
+            output::print(ctx.format, ctx.ledger.assets(), &table)
+        }
+        AssetCommand::Classes => {
+            let mut table = Table::new("📘 Khung thời gian trích khấu hao (Thông tư 45/2013):")
+                .column("Nhóm")
+                .column("Tên")
+                .column("Tối thiểu (năm)")
+                .column("Tối đa (năm)");
+            for class in ASSET_CLASSES {
+                table.row(vec![
+                    class.code.to_string(),
+                    class.name.to_string(),
+                    class.min_years.to_string(),
+                    class.max_years.to_string(),
+                ]);
+            }
+            output::print(ctx.format, ASSET_CLASSES, &table)
+        }
+        AssetCommand::Schedule { code } => {
+            let record = ctx.ledger.asset(&code)?;
+            let schedule = depreciation_schedule(&record.asset, ctx.ledger.precision());
+            let mut table = Table::new(format!("📉 Lịch khấu hao {} - {}:", record.asset.code, record.asset.name))
+                .column("Tháng")
+                .amount("Khấu hao")
+                .amount("Lũy kế")
+                .amount("Giá trị còn lại")
+                .column("Đã trích");
+            for line in &schedule {
+                let posted = record.depreciated_through().is_some_and(|d| d >= line.month);
+                table.row(vec![
+                    line.month.format("%m/%Y").to_string(),
+                    amount(line.charge),
+                    amount(line.accumulated),
+                    amount(line.net_book_value),
+                    if posted { "✓".to_string() } else { String::new() },
+                ]);
+            }
+            output::print(ctx.format, &schedule, &table)
+        }
+        AssetCommand::Depreciate { month } => {
+            match ctx.ledger.post_depreciation(&ctx.coa, &ctx.periods, month)? {
+                Some(id) => {
+                    let entry = ctx.ledger.get_entry(id);
+                    output::done(ctx.format, &format!("Depreciation of {} posted as entry #{}", month.format("%m/%Y"), id), &entry)
+                }
+                None => output::done(
+                    ctx.format,
+                    &format!("No depreciation due for {}", month.format("%m/%Y")),
+                    &json!({ "entry_id": null }),
+                ),
+            }
+        }
+        AssetCommand::Dispose { code, date, proceeds, account } => {
+            let account = AccountCode::new(&account)?;
+            let id = ctx.ledger.dispose_asset(&code, date, proceeds, &account, &ctx.coa, &ctx.periods)?;
+            let disposal = ctx.ledger.asset(&code)?.disposal();
+            output::done(ctx.format, &format!("Asset {} disposed of as entry #{}", code, id), &disposal)
+        }
+        AssetCommand::Movements { from, to } => {
+            let (from, to) = range(&ctx.ledger, from, to);
+            let movements = asset_movements(&ctx.ledger, from, to);
+            let mut table = Table::new(format!("🏭 Tình hình tăng giảm TSCĐ từ {} đến {}:", from, to))
+                .column("Mã")
+                .column("Tên")
+                .amount("NG đầu kỳ")
+                .amount("Tăng")
+                .amount("Giảm")
+                .amount("NG cuối kỳ")
+                .amount("HM đầu kỳ")
+                .amount("Khấu hao")
+                .amount("HM giảm")
+                .amount("HM cuối kỳ")
+                .amount("GTCL cuối kỳ");
+            let mut totals = [Decimal::ZERO; 9];
+            for m in &movements {
+                let values = [
+                    m.opening_cost,
+                    m.additions,
+                    m.disposals,
+                    m.closing_cost,
+                    m.opening_depreciation,
+                    m.depreciation,
+                    m.depreciation_disposed,
+                    m.closing_depreciation,
+                    m.net_book_value,
+                ];
+                for (total, value) in totals.iter_mut().zip(values) {
+                    *total += value;
+                }
+                let mut row = vec![m.code.clone(), m.name.clone()];
+                row.extend(values.map(amount));
+                table.row(row);
+            }
+            let mut total = vec!["Tổng cộng".to_string(), String::new()];
+            total.extend(totals.map(amount));
+            table.total(total);
+            output::print(ctx.format, &movements, &table)
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// gl-cli/src/commands/audit.rs
+
+use std::error::Error;
+
+use clap::Subcommand;
+use serde_json::json;
+use tracing::error;
+
+use crate::context::{Context, GlobalArgs};
+use crate::output;
+
+#[derive(Subcommand, Debug)]
+pub enum AuditCommand {
+    /// Verify the hash chain of the audit trail and that it ends where the ledger file records
+    Verify,
+}
+
+pub fn run(command: AuditCommand, global: &GlobalArgs) -> Result<(), Box<dyn Error>> {
+    match command {
+        AuditCommand::Verify => {
+            let (path, ledger) =
+                Context::audit_trail(global).inspect_err(|e| error!("Audit trail verification failed: {}", e))?;
+            let head = ledger.audit_log().head_hash().to_string();
+            let events = ledger.audit_log().events().len();
+            output::done(
+                global.format,
+                &format!("Audit trail {} intact: {} events\nHead hash: {}", path.display(), events, head),
+                &json!({ "path": path, "events": events, "head": head }),
+            )
+        }
+    }
+}
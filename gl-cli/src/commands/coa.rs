@@ -0,0 +1,65 @@
+// gl-cli/src/commands/coa.rs
+
+use std::error::Error;
+
+use clap::Subcommand;
+use gl_core::LedgerError;
+use rust_decimal::Decimal;
+use serde_json::json;
+
+use crate::context::Context;
+use crate::output::{self, amount, Table};
+
+#[derive(Subcommand, Debug)]
+pub enum CoaCommand {
+    /// List the accounts of the chart
+    List,
+    /// Show an account and its balance, sub-accounts included
+    Show { code: String },
+}
+
+pub fn run(ctx: &Context, command: CoaCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        CoaCommand::List => {
+            let mut table = Table::new("📚 Hệ thống tài khoản:")
+                .column("Tài khoản")
+                .column("Tên")
+                .column("Loại")
+                .column("Số dư");
+            for account in &ctx.coa.accounts {
+                table.row(vec![
+                    account.code.clone(),
+                    account.name.clone(),
+                    account.classification.clone(),
+                    account.default_balance.clone(),
+                ]);
+            }
+            output::print(ctx.format, &ctx.coa.accounts, &table)
+        }
+        CoaCommand::Show { code } => {
+            let account = ctx
+                .coa
+                .accounts
+                .iter()
+                .find(|a| a.code == code)
+                .ok_or_else(|| LedgerError::UnknownAccount(code.clone()))?;
+            let (debit, credit) = ctx
+                .ledger
+                .trial_balance()
+                .balances
+                .iter()
+                .filter(|b| b.account.as_str().starts_with(&code))
+                .fold((Decimal::ZERO, Decimal::ZERO), |(d, c), b| (d + b.debit_balance, c + b.credit_balance));
+
+            let mut table = Table::new(format!("📚 Tài khoản {}:", code)).column("Mục").column("Giá trị");
+            table.row(vec!["Tên".to_string(), account.name.clone()]);
+            table.row(vec!["Loại".to_string(), account.classification.clone()]);
+            table.row(vec!["Số dư thông thường".to_string(), account.default_balance.clone()]);
+            table.row(vec!["Mô tả".to_string(), account.description.clone()]);
+            table.row(vec!["Phát sinh Nợ".to_string(), amount(debit)]);
+            table.row(vec!["Phát sinh Có".to_string(), amount(credit)]);
+            table.row(vec!["Số dư (Nợ - Có)".to_string(), amount(debit - credit)]);
+            output::print(ctx.format, &json!({ "account": account, "debit": debit, "credit": credit }), &table)
+        }
+    }
+}
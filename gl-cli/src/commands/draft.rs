@@ -0,0 +1,97 @@
+// gl-cli/src/commands/draft.rs
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Subcommand;
+use gl_core::{DraftId, JournalEntry};
+use tracing::info;
+
+use super::read_json;
+use crate::context::Context;
+use crate::output::{self, amount, Table};
+
+#[derive(Subcommand, Debug)]
+pub enum DraftCommand {
+    /// Save a journal entry from a JSON file as a draft; it may be unbalanced
+    Save { file: PathBuf },
+    /// Replace the entry of a draft that is not yet submitted
+    Update { id: DraftId, file: PathBuf },
+    /// Send a balanced draft for review
+    Submit { id: DraftId },
+    /// Approve a submitted draft prepared by someone else
+    Approve {
+        id: DraftId,
+        #[arg(long)]
+        comment: Option<String>,
+    },
+    /// Send a draft back to its preparer
+    Reject {
+        id: DraftId,
+        /// What the preparer must correct
+        #[arg(long)]
+        comment: String,
+    },
+    /// Post an approved draft to the books
+    Post { id: DraftId },
+    /// List the drafts and where they are in review
+    List,
+}
+
+pub fn run(ctx: &mut Context, command: DraftCommand) -> Result<(), Box<dyn Error>> {
+    let (id, message) = match command {
+        DraftCommand::Save { file } => {
+            let entry: JournalEntry = read_json(&file)?;
+            let id = ctx.ledger.save_draft(entry)?;
+            info!("Saved draft #{} from {}", id, file.display());
+            (id, format!("Draft #{} saved", id))
+        }
+        DraftCommand::Update { id, file } => {
+            let entry: JournalEntry = read_json(&file)?;
+            ctx.ledger.update_draft(id, entry)?;
+            (id, format!("Draft #{} updated", id))
+        }
+        DraftCommand::Submit { id } => {
+            ctx.ledger.submit_draft(id)?;
+            (id, format!("Draft #{} submitted for review", id))
+        }
+        DraftCommand::Approve { id, comment } => {
+            ctx.ledger.approve_draft(id, comment.as_deref())?;
+            (id, format!("Draft #{} approved", id))
+        }
+        DraftCommand::Reject { id, comment } => {
+            ctx.ledger.reject_draft(id, &comment)?;
+            (id, format!("Draft #{} sent back", id))
+        }
+        DraftCommand::Post { id } => {
+            let entry_id = ctx.ledger.post_draft(id, &ctx.coa, &ctx.periods)?;
+            info!("Posted draft #{} as entry #{}", id, entry_id);
+            (id, format!("Draft #{} posted as entry #{}", id, entry_id))
+        }
+        DraftCommand::List => {
+            let drafts = ctx.ledger.drafts();
+            let mut table = Table::new("📝 Chứng từ chờ duyệt:")
+                .column("#")
+                .column("Ngày")
+                .column("Diễn giải")
+                .amount("Số tiền")
+                .column("Trạng thái")
+                .column("Người lập")
+                .column("Người duyệt");
+            for draft in drafts {
+                let total = draft.entry.lines.iter().map(|l| l.debit).sum();
+                table.row(vec![
+                    draft.id.to_string(),
+                    draft.entry.date.to_string(),
+                    draft.entry.description.clone(),
+                    amount(total),
+                    format!("{:?}", draft.status),
+                    draft.preparer.clone(),
+                    draft.approver.clone().unwrap_or_default(),
+                ]);
+            }
+            return output::print(ctx.format, drafts, &table);
+        }
+    };
+    output::done(ctx.format, &message, ctx.ledger.draft(id)?)
+}
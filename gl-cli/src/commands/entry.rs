@@ -0,0 +1,228 @@
+// gl-cli/src/commands/entry.rs
+
+use std::error::Error;
+use std::fs::File;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use clap::Subcommand;
+use gl_core::{
+    import_csv, import_xlsx, parse_entry_batch, BatchError, ColumnMapping, EInvoiceImporter, EInvoiceMapping, EntryId,
+    ImportMode, JournalEntry, PostedEntry,
+};
+use serde_json::json;
+use tracing::{error, info};
+
+use super::{check_open, read_json, report::trial_balance_table};
+use crate::context::Context;
+use crate::output::{self, amount, Table};
+
+#[derive(Subcommand, Debug)]
+pub enum EntryCommand {
+    /// Post a journal entry from a JSON file
+    Add { file: PathBuf },
+    /// Import journal entries from a CSV or XLSX file (rows grouped by voucher)
+    Import {
+        file: PathBuf,
+        /// CSV field delimiter
+        #[arg(long, default_value_t = ',')]
+        delimiter: char,
+        /// Reject the whole file if any voucher is invalid
+        #[arg(long)]
+        all_or_nothing: bool,
+        /// Require voucher numbers like PT2501-0001, gapless per type and month
+        #[arg(long)]
+        voucher_numbers: bool,
+    },
+    /// Post a purchase or sale e-invoice (XML, Decree 123) not yet in the books
+    Einvoice {
+        file: PathBuf,
+        /// MST of the company keeping the books [default: the selected company's]
+        #[arg(long)]
+        mst: Option<String>,
+        /// Account mapping (YAML); the Circular 133 accounts when absent
+        #[arg(long, value_name = "FILE")]
+        mapping: Option<PathBuf>,
+    },
+    /// Post a batch of journal entries (JSON array or JSONL) atomically
+    Batch {
+        file: PathBuf,
+        /// Validate the batch and print its trial balance delta without posting
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List posted entries
+    List {
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        from: Option<NaiveDate>,
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        to: Option<NaiveDate>,
+    },
+    /// Post the reversal of an entry
+    Reverse {
+        id: EntryId,
+        /// Why the entry is reversed; recorded in the reversal's description
+        #[arg(long)]
+        reason: String,
+        /// Date of the reversal [default: today]
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        date: Option<NaiveDate>,
+    },
+}
+
+pub fn run(ctx: &mut Context, command: EntryCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        EntryCommand::Add { file } => {
+            let entry: JournalEntry = read_json(&file)?;
+            ctx.ledger.validate_entry(&entry, &ctx.coa, &ctx.periods)?;
+            let id = ctx.ledger.post_entry(entry)?;
+            info!("Posted entry #{} from {}", id, file.display());
+            output::done(ctx.format, &format!("Entry #{} posted", id), &ctx.ledger.get_entry(id))
+        }
+        EntryCommand::Import { file, delimiter, all_or_nothing, voucher_numbers } => {
+            let reader = File::open(&file).map_err(|e| format!("Failed to open {}: {}", file.display(), e))?;
+            let delimiter = u8::try_from(delimiter).map_err(|_| format!("Delimiter {:?} is not a single byte", delimiter))?;
+            let mode = if all_or_nothing { ImportMode::AllOrNothing } else { ImportMode::BestEffort };
+            let mut mapping = ColumnMapping::default();
+            if voucher_numbers {
+                mapping.voucher_format = Some(ctx.ledger.voucher_numbering().format.clone());
+            }
+            let is_xlsx = file.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("xlsx"));
+            let imported = if is_xlsx {
+                import_xlsx(reader, None, &mapping, &mode)
+            } else {
+                import_csv(reader, delimiter, &mapping, &mode)
+            }
+            .map_err(|e| format!("Failed to import {}: {}", file.display(), e))?;
+
+            let mut errors: Vec<String> = imported.errors.iter().map(|e| e.to_string()).collect();
+            let mut posted = vec![];
+            if all_or_nothing && errors.is_empty() {
+                let vouchers: Vec<String> = imported.vouchers.iter().map(|v| v.voucher.clone()).collect();
+                let entries = imported.vouchers.into_iter().map(|v| v.entry).collect();
+                let first = ctx.ledger.entries().len();
+                match ctx.ledger.post_batch(entries, &ctx.coa, &ctx.periods) {
+                    Ok(_) => posted.extend(ctx.ledger.entries()[first..].iter().map(|e| e.id)),
+                    Err(failed) => {
+                        errors.extend(failed.iter().map(|f| format!("voucher {}: {}", vouchers[f.index], f.error)))
+                    }
+                }
+            } else if !all_or_nothing {
+                for voucher in imported.vouchers {
+                    let result = ctx
+                        .ledger
+                        .validate_entry(&voucher.entry, &ctx.coa, &ctx.periods)
+                        .and_then(|_| ctx.ledger.post_entry(voucher.entry));
+                    match result {
+                        Ok(id) => posted.push(id),
+                        Err(e) => errors.push(format!("line {} (voucher {}): {}", voucher.line, voucher.voucher, e)),
+                    }
+                }
+            }
+            for e in &errors {
+                eprintln!("❌ {}", e);
+            }
+            info!("Imported {} voucher(s) from {}", posted.len(), file.display());
+            output::done(
+                ctx.format,
+                &format!("{} voucher(s) posted, {} error(s)", posted.len(), errors.len()),
+                &json!({ "posted": posted, "errors": errors }),
+            )?;
+            match errors.len() {
+                0 => Ok(()),
+                n => {
+                    // The vouchers that were posted stay posted
+                    ctx.save()?;
+                    Err(format!("{} voucher(s) of {} not posted", n, file.display()).into())
+                }
+            }
+        }
+        EntryCommand::Einvoice { file, mst, mapping } => {
+            let mapping = match mapping {
+                Some(path) => {
+                    let contents = std::fs::read_to_string(&path)
+                        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                    EInvoiceMapping::from_yaml(&contents)
+                        .map_err(|e| format!("Failed to parse mapping {}: {}", path.display(), e))?
+                }
+                None => {
+                    let mst = mst
+                        .or_else(|| ctx.company.as_ref().map(|c| c.tax_code.clone()))
+                        .ok_or("Pass --mst, --mapping or --company to tell purchases from sales")?;
+                    EInvoiceMapping::new(&mst)
+                }
+            };
+            let xml = std::fs::read_to_string(&file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+            let entry = EInvoiceImporter::new(mapping).import_xml(&ctx.ledger, &xml)?;
+            ctx.ledger.validate_entry(&entry, &ctx.coa, &ctx.periods)?;
+            let id = ctx.ledger.post_entry(entry)?;
+            info!("Posted e-invoice {} as entry #{}", file.display(), id);
+            output::done(ctx.format, &format!("Entry #{} posted", id), &ctx.ledger.get_entry(id))
+        }
+        EntryCommand::Batch { file, dry_run } => {
+            let contents = std::fs::read_to_string(&file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+            let entries = parse_entry_batch(&contents).map_err(|e| format!("Failed to parse batch {}: {}", file.display(), e))?;
+            let count = entries.len();
+            if dry_run {
+                let delta = ctx.ledger.validate_batch(&entries, &ctx.coa, &ctx.periods).map_err(rejected)?;
+                let title = format!("🔍 Dry run: {} entries valid, trial balance delta:", count);
+                return output::print(ctx.format, &delta, &trial_balance_table(&title, &delta, ctx));
+            }
+            let first = ctx.ledger.entries().len();
+            ctx.ledger.post_batch(entries, &ctx.coa, &ctx.periods).map_err(rejected)?;
+            info!("Posted batch {} ({} entries)", file.display(), count);
+            output::done(ctx.format, &format!("{} entries posted", count), &ctx.ledger.entries()[first..])
+        }
+        EntryCommand::List { from, to } => {
+            let entries: Vec<&PostedEntry> = ctx
+                .ledger
+                .entries()
+                .iter()
+                .filter(|e| from.is_none_or(|from| e.entry.date >= from) && to.is_none_or(|to| e.entry.date <= to))
+                .collect();
+            let mut table = Table::new("📒 Bút toán:")
+                .column("#")
+                .column("Ngày")
+                .column("Chứng từ")
+                .column("Diễn giải")
+                .amount("Số tiền")
+                .column("Ghi chú");
+            for posted in &entries {
+                let total: rust_decimal::Decimal = posted.entry.lines.iter().map(|l| l.debit).sum();
+                let link = match (posted.reverses, posted.reversed_by) {
+                    (Some(id), _) => format!("đảo #{}", id),
+                    (_, Some(id)) => format!("đã đảo bởi #{}", id),
+                    _ => String::new(),
+                };
+                table.row(vec![
+                    posted.id.to_string(),
+                    posted.entry.date.to_string(),
+                    posted.entry.voucher.as_ref().map(|v| v.number.clone()).unwrap_or_default(),
+                    posted.entry.description.clone(),
+                    amount(total),
+                    link,
+                ]);
+            }
+            output::print(ctx.format, &entries, &table)
+        }
+        EntryCommand::Reverse { id, reason, date } => {
+            let date = date.unwrap_or_else(super::today);
+            check_open(ctx, date)?;
+            let reversal = ctx.ledger.reverse_entry(id, date, &reason)?;
+            output::done(
+                ctx.format,
+                &format!("Entry #{} reversed by entry #{}", id, reversal),
+                &ctx.ledger.get_entry(reversal),
+            )
+        }
+    }
+}
+
+/// Reports every failing entry of a rejected batch.
+fn rejected(errors: Vec<BatchError>) -> Box<dyn Error> {
+    for err in &errors {
+        eprintln!("❌ Entry #{}: {}", err.index, err.error);
+    }
+    error!("Batch rejected: {} invalid entries", errors.len());
+    "Batch rejected, no entries posted".into()
+}
@@ -0,0 +1,81 @@
+// gl-cli/src/commands/fx.rs
+
+use std::error::Error;
+
+use chrono::NaiveDate;
+use clap::Subcommand;
+use gl_core::{currency_balances, revaluation};
+use serde_json::json;
+use tracing::error;
+
+use super::{check_open, last_date};
+use crate::context::Context;
+use crate::output::{self, amount, Table};
+
+#[derive(Subcommand, Debug)]
+pub enum FxCommand {
+    /// Foreign-currency balances per account alongside VND
+    Balances {
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        as_of: Option<NaiveDate>,
+    },
+    /// Revalue 1122/131/331 foreign-currency balances at the rates of a period end
+    Revalue {
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        as_of: NaiveDate,
+    },
+}
+
+pub fn run(ctx: &mut Context, command: FxCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        FxCommand::Balances { as_of } => {
+            let as_of = as_of.unwrap_or_else(|| last_date(&ctx.ledger));
+            let balances = currency_balances(&ctx.ledger, as_of);
+            let mut table = Table::new(format!("💱 Số dư ngoại tệ tại {}:", as_of))
+                .column("Tài khoản")
+                .column("Loại tiền")
+                .amount("Nguyên tệ")
+                .amount("VND");
+            for b in &balances {
+                table.row(vec![
+                    b.account.as_str().to_string(),
+                    b.currency.clone(),
+                    amount(b.foreign_balance),
+                    amount(b.balance),
+                ]);
+            }
+            output::print(ctx.format, &balances, &table)
+        }
+        FxCommand::Revalue { as_of } => {
+            check_open(ctx, as_of)?;
+            let lines = revaluation(&ctx.ledger, as_of)?;
+            let entry = ctx.ledger.revalue_currencies(&ctx.periods, as_of).inspect_err(|e| error!("FX revaluation failed: {}", e))?;
+            let mut table = Table::new(format!("💱 Đánh giá lại ngoại tệ tại {}:", as_of))
+                .column("Tài khoản")
+                .column("Đối tượng")
+                .column("Loại tiền")
+                .amount("Nguyên tệ")
+                .amount("Tỷ giá")
+                .amount("Đánh giá lại")
+                .amount("Sổ sách")
+                .amount("Chênh lệch");
+            for r in &lines {
+                table.row(vec![
+                    r.account.as_str().to_string(),
+                    r.party.clone().unwrap_or_default(),
+                    r.currency.clone(),
+                    amount(r.foreign_balance),
+                    r.rate.to_string(),
+                    amount(r.revalued),
+                    amount(r.booked),
+                    amount(r.difference),
+                ]);
+            }
+            match entry {
+                Some(id) => table.note(format!("✅ Revaluation posted as entry #{}, reversed when the next period starts", id)),
+                None => table.note("✅ Nothing to revalue"),
+            }
+            output::print(ctx.format, &json!({ "entry_id": entry, "lines": lines }), &table)
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// gl-cli/src/commands/ledger.rs
+
+use std::error::Error;
+
+use chrono::{Datelike, NaiveDate};
+use clap::Subcommand;
+use gl_core::{LedgerError, Period};
+use serde_json::json;
+
+use crate::context::Context;
+use crate::output;
+
+#[derive(Subcommand, Debug)]
+pub enum LedgerCommand {
+    /// Create the ledger file and open the months of a fiscal year
+    Init {
+        /// Fiscal year to open, by its starting calendar year [default: this year]
+        #[arg(long)]
+        year: Option<i32>,
+    },
+    /// Check that the balances match a replay of the recorded events
+    Verify,
+}
+
+pub fn run(ctx: &mut Context, command: LedgerCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        LedgerCommand::Init { year } => {
+            let year = year.unwrap_or_else(|| super::today().year());
+            let fiscal_year = match &ctx.company {
+                Some(company) => company.fiscal_year(year)?,
+                None => {
+                    let start = NaiveDate::from_ymd_opt(year, 1, 1).ok_or(LedgerError::InvalidDate)?;
+                    let end = NaiveDate::from_ymd_opt(year, 12, 31).ok_or(LedgerError::InvalidDate)?;
+                    Period::new(start, end)
+                }
+            };
+            for month in fiscal_year.months() {
+                ctx.ledger.open_period(&mut ctx.periods, month)?;
+            }
+            output::done(
+                ctx.format,
+                &format!(
+                    "Ledger {} initialized with {} accounts; periods {} → {} open",
+                    ctx.ledger_path().display(),
+                    ctx.coa.accounts.len(),
+                    fiscal_year.start_date,
+                    fiscal_year.end_date
+                ),
+                ctx.periods.periods(),
+            )
+        }
+        LedgerCommand::Verify => {
+            ctx.ledger.verify_projection()?;
+            output::done(
+                ctx.format,
+                &format!("Balances match the {} recorded events", ctx.ledger.events().len()),
+                &json!({ "events": ctx.ledger.events().len(), "sequence": ctx.ledger.sequence() }),
+            )
+        }
+    }
+}
@@ -0,0 +1,145 @@
+// gl-cli/src/commands/mod.rs
+
+//! The `gl` subcommands, one module per noun.
+
+pub mod asset;
+pub mod audit;
+pub mod coa;
+pub mod draft;
+pub mod entry;
+pub mod fx;
+pub mod ledger;
+pub mod period;
+pub mod recurring;
+pub mod report;
+pub mod subledger;
+
+use std::error::Error;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use clap::Subcommand;
+use gl_core::{Ledger, LedgerError};
+
+use crate::context::{Context, GlobalArgs};
+
+#[derive(Subcommand, Debug)]
+pub enum Commands {
+    /// Create or check the ledger file
+    #[command(subcommand)]
+    Ledger(ledger::LedgerCommand),
+    /// Post, import, list and reverse journal entries
+    #[command(subcommand)]
+    Entry(entry::EntryCommand),
+    /// Prepare entries as drafts and take them through review to the books
+    #[command(subcommand)]
+    Draft(draft::DraftCommand),
+    /// Open, close and list accounting periods
+    #[command(subcommand)]
+    Period(period::PeriodCommand),
+    /// Trial balance, financial statements and ledger books
+    #[command(subcommand)]
+    Report(report::ReportCommand),
+    /// Browse the chart of accounts
+    #[command(subcommand)]
+    Coa(coa::CoaCommand),
+    /// Accounts receivable (131)
+    #[command(subcommand)]
+    Ar(subledger::ArCommand),
+    /// Accounts payable (331)
+    #[command(subcommand)]
+    Ap(subledger::ApCommand),
+    /// Open items of 131 and 331
+    #[command(subcommand)]
+    Items(subledger::ItemsCommand),
+    /// Foreign-currency balances and revaluation
+    #[command(subcommand)]
+    Fx(fx::FxCommand),
+    /// Fixed asset register, depreciation and disposals
+    #[command(subcommand)]
+    Asset(asset::AssetCommand),
+    /// Recurring entries generated from templates
+    #[command(subcommand)]
+    Recurring(recurring::RecurringCommand),
+    /// Audit trail commands
+    #[command(subcommand)]
+    Audit(audit::AuditCommand),
+    /// Enter vouchers interactively, with account completion and a trial balance view
+    Tui,
+}
+
+/// Runs a command against the selected books and writes back what it
+/// changed. A failed command writes back none of its changes, only the
+/// audit records of what it refused.
+pub fn run(command: Commands, global: &GlobalArgs) -> Result<(), Box<dyn Error>> {
+    let create = match command {
+        Commands::Audit(command) => return audit::run(command, global),
+        Commands::Ledger(ledger::LedgerCommand::Init { .. }) => true,
+        _ => false,
+    };
+    let mut ctx = Context::open(global, create)?;
+    let result = match command {
+        Commands::Ledger(command) => ledger::run(&mut ctx, command),
+        Commands::Entry(command) => entry::run(&mut ctx, command),
+        Commands::Draft(command) => draft::run(&mut ctx, command),
+        Commands::Period(command) => period::run(&mut ctx, command),
+        Commands::Report(command) => report::run(&ctx, command),
+        Commands::Coa(command) => coa::run(&ctx, command),
+        Commands::Ar(command) => subledger::run_ar(&ctx, command),
+        Commands::Ap(command) => subledger::run_ap(&mut ctx, command),
+        Commands::Items(command) => subledger::run_items(&mut ctx, command),
+        Commands::Fx(command) => fx::run(&mut ctx, command),
+        Commands::Asset(command) => asset::run(&mut ctx, command),
+        Commands::Recurring(command) => recurring::run(&mut ctx, command),
+        Commands::Tui => crate::tui::run(&mut ctx),
+        Commands::Audit(_) => unreachable!("handled above"),
+    };
+    match result {
+        Ok(()) => ctx.save(),
+        Err(e) => {
+            if !ctx.has_new_events() {
+                ctx.save()?;
+            }
+            Err(e)
+        }
+    }
+}
+
+/// `from`/`to` of a report, defaulting to the first and last entry dates.
+pub fn range(ledger: &Ledger, from: Option<NaiveDate>, to: Option<NaiveDate>) -> (NaiveDate, NaiveDate) {
+    (from.unwrap_or_else(|| first_date(ledger)), to.unwrap_or_else(|| last_date(ledger)))
+}
+
+/// Date of the earliest entry, or today.
+pub fn first_date(ledger: &Ledger) -> NaiveDate {
+    ledger.entries().iter().map(|e| e.entry.date).min().unwrap_or_else(today)
+}
+
+/// Date of the latest entry, or today.
+pub fn last_date(ledger: &Ledger) -> NaiveDate {
+    ledger.entries().iter().map(|e| e.entry.date).max().unwrap_or_else(today)
+}
+
+/// First day of a `YYYY-MM` month.
+pub fn parse_month(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{}-01", value), "%Y-%m-%d").map_err(|_| format!("expected YYYY-MM, got {}", value))
+}
+
+pub fn today() -> NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+pub fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, Box<dyn Error>> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse {}: {}", path.display(), e).into())
+}
+
+/// Refuses dates outside the open periods of the books.
+pub fn check_open(ctx: &Context, date: NaiveDate) -> Result<(), LedgerError> {
+    if ctx.periods.is_date_in_open_period(date) {
+        Ok(())
+    } else {
+        Err(LedgerError::DateNotInOpenPeriod(date))
+    }
+}
@@ -0,0 +1,74 @@
+// gl-cli/src/commands/period.rs
+
+use std::error::Error;
+
+use chrono::NaiveDate;
+use clap::Subcommand;
+use gl_core::{LedgerError, Period};
+use tracing::info;
+
+use super::parse_month;
+use crate::context::Context;
+use crate::output::{self, Table};
+
+#[derive(Subcommand, Debug)]
+pub enum PeriodCommand {
+    /// Open the accounting period of a month
+    Open {
+        #[arg(value_name = "YYYY-MM", value_parser = parse_month)]
+        month: NaiveDate,
+    },
+    /// Close the period of a month [default: the earliest open one]
+    Close {
+        #[arg(value_name = "YYYY-MM", value_parser = parse_month)]
+        month: Option<NaiveDate>,
+    },
+    /// List the periods and whether they are open
+    List,
+}
+
+pub fn run(ctx: &mut Context, command: PeriodCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        PeriodCommand::Open { month } => {
+            let period = Period::month(month).ok_or(LedgerError::InvalidDate)?;
+            let known = ctx
+                .periods
+                .periods()
+                .iter()
+                .any(|p| p.start_date == period.start_date && p.end_date == period.end_date);
+            if known {
+                return Err(format!("Period {} → {} was already opened", period.start_date, period.end_date).into());
+            }
+            ctx.ledger.open_period(&mut ctx.periods, period.clone())?;
+            info!("Opened period {} → {}", period.start_date, period.end_date);
+            output::done(ctx.format, &format!("Period opened: {}", month.format("%Y-%m")), &period)
+        }
+        PeriodCommand::Close { month } => {
+            let date = match month {
+                Some(month) => month,
+                None => ctx
+                    .periods
+                    .periods()
+                    .iter()
+                    .filter(|p| p.is_open())
+                    .map(|p| p.start_date)
+                    .min()
+                    .ok_or("No open period to close")?,
+            };
+            let period = ctx.ledger.close_period(&mut ctx.periods, date)?;
+            info!("Closed period {} → {}", period.start_date, period.end_date);
+            output::done(ctx.format, &format!("Period closed: {} → {}", period.start_date, period.end_date), &period)
+        }
+        PeriodCommand::List => {
+            let mut table = Table::new("📅 Kỳ kế toán:").column("Từ ngày").column("Đến ngày").column("Trạng thái");
+            for period in ctx.periods.periods() {
+                table.row(vec![
+                    period.start_date.to_string(),
+                    period.end_date.to_string(),
+                    format!("{:?}", period.status),
+                ]);
+            }
+            output::print(ctx.format, ctx.periods.periods(), &table)
+        }
+    }
+}
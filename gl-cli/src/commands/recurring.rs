@@ -0,0 +1,97 @@
+// gl-cli/src/commands/recurring.rs
+
+use std::error::Error;
+
+use chrono::NaiveDate;
+use clap::Subcommand;
+use gl_core::SkipReason;
+use rust_decimal::Decimal;
+use serde_json::json;
+use tracing::info;
+
+use crate::context::Context;
+use crate::output::{self, amount, Table};
+
+#[derive(Subcommand, Debug)]
+pub enum RecurringCommand {
+    /// List the templates (load them with --recurring) and what they generated
+    List,
+    /// Post every occurrence due up to a date whose period is open
+    Run {
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        until: NaiveDate,
+    },
+}
+
+pub fn run(ctx: &mut Context, command: RecurringCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        RecurringCommand::List => {
+            let generated = ctx.ledger.generated_entries();
+            let mut table = Table::new("🔁 Bút toán định kỳ:")
+                .column("Mã")
+                .column("Diễn giải")
+                .column("Lịch")
+                .column("Từ")
+                .column("Đến")
+                .column("Đã lập")
+                .column("Lần cuối");
+            for template in &ctx.ledger.recurring().templates {
+                let done: Vec<_> = generated.iter().filter(|g| g.template == template.code).collect();
+                table.row(vec![
+                    template.code.clone(),
+                    template.description.clone(),
+                    template.schedule.to_string(),
+                    template.start.to_string(),
+                    template.end.map(|d| d.to_string()).unwrap_or_default(),
+                    done.len().to_string(),
+                    done.iter().map(|g| g.date).max().map(|d| d.to_string()).unwrap_or_default(),
+                ]);
+            }
+            if ctx.ledger.recurring().is_empty() {
+                table.note("No templates; load them with --recurring data/recurring.yaml");
+            }
+            output::print(ctx.format, &json!({ "templates": ctx.ledger.recurring().templates, "generated": generated }), &table)
+        }
+        RecurringCommand::Run { until } => {
+            let run = ctx.ledger.run_recurring(&ctx.coa, &ctx.periods, until)?;
+            let mut table = Table::new(format!("🔁 Bút toán định kỳ đến {}:", until))
+                .column("Mã")
+                .column("Ngày")
+                .column("Bút toán")
+                .amount("Số tiền");
+            for generated in &run.posted {
+                let total: Decimal = ctx
+                    .ledger
+                    .get_entry(generated.entry_id)
+                    .map(|e| e.entry.lines.iter().map(|l| l.debit).sum())
+                    .unwrap_or_default();
+                table.row(vec![
+                    generated.template.clone(),
+                    generated.date.to_string(),
+                    format!("#{}", generated.entry_id),
+                    amount(total),
+                ]);
+            }
+            for (occurrence, reason) in &run.skipped {
+                let why = match reason {
+                    SkipReason::PeriodNotOpen => "its period is not open",
+                    SkipReason::NothingToPost => "every amount is zero",
+                };
+                table.note(format!("⏭️  {} on {} skipped: {}", occurrence.template, occurrence.date, why));
+            }
+            let errors: Vec<String> =
+                run.failed.iter().map(|(o, e)| format!("{} on {}: {}", o.template, o.date, e)).collect();
+            for e in &errors {
+                eprintln!("❌ {}", e);
+            }
+            info!("Generated {} recurring entries up to {}", run.posted.len(), until);
+            table.note(format!("✅ {} entries posted, {} skipped, {} error(s)", run.posted.len(), run.skipped.len(), errors.len()));
+            let skipped: Vec<_> = run.skipped.iter().map(|(o, reason)| json!({ "occurrence": o, "reason": reason })).collect();
+            output::print(ctx.format, &json!({ "posted": run.posted, "skipped": skipped, "errors": errors }), &table)?;
+            match errors.len() {
+                0 => Ok(()),
+                n => Err(format!("{} recurring entries not posted", n).into()),
+            }
+        }
+    }
+}
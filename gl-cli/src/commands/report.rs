@@ -0,0 +1,218 @@
+// gl-cli/src/commands/report.rs
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use clap::Subcommand;
+use gl_core::{
+    balance_sheet, income_statement, ledger_book, payables_aging, receivables_aging, AccountCode, AgingBucket,
+    AgingReport, ReportLine, ReportWorkbook, TrialBalance,
+};
+use tracing::error;
+
+use super::{last_date, range};
+use crate::context::Context;
+use crate::output::{self, amount, Format, Table};
+
+#[derive(Subcommand, Debug)]
+pub enum ReportCommand {
+    /// Trial balance
+    Tb {
+        /// Balances as of this date [default: every entry]
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        as_of: Option<NaiveDate>,
+    },
+    /// Balance sheet (B01-DNN)
+    B01 {
+        /// Report date [default: the last entry date]
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        as_of: Option<NaiveDate>,
+    },
+    /// Income statement (B02-DNN)
+    B02 {
+        /// Start date [default: the first entry date]
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        from: Option<NaiveDate>,
+        /// End date [default: the last entry date]
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        to: Option<NaiveDate>,
+    },
+    /// Sổ cái (general ledger book) of an account
+    Ledger {
+        account: String,
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        from: Option<NaiveDate>,
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        to: Option<NaiveDate>,
+    },
+    /// Aging of receivables (131) or payables (331), with provisions for 131
+    Aging {
+        #[arg(value_parser = ["131", "331"])]
+        account: String,
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        as_of: Option<NaiveDate>,
+    },
+    /// Write the trial balance, B01, B02 and Sổ cái of some accounts to an Excel workbook
+    Xlsx {
+        file: PathBuf,
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        from: Option<NaiveDate>,
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        to: Option<NaiveDate>,
+        /// Account whose Sổ cái to include; may be repeated
+        #[arg(long = "account", value_name = "ACCOUNT")]
+        accounts: Vec<String>,
+    },
+}
+
+pub fn run(ctx: &Context, command: ReportCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        ReportCommand::Tb { as_of } => {
+            let tb = match as_of {
+                Some(date) => ctx.ledger.as_of_date(date),
+                None => ctx.ledger.trial_balance(),
+            };
+            if tb.total_debit != tb.total_credit {
+                error!("Trial balance is unbalanced!");
+            }
+            let title = match as_of {
+                Some(date) => format!("📊 Bảng cân đối số phát sinh tại {}:", date),
+                None => "📊 Bảng cân đối số phát sinh:".to_string(),
+            };
+            output::print(ctx.format, &tb, &trial_balance_table(&title, &tb, ctx))
+        }
+        ReportCommand::B01 { as_of } => {
+            let to = as_of.unwrap_or_else(|| last_date(&ctx.ledger));
+            let bs = balance_sheet(&ctx.ledger, to);
+            let mut table = report_table(&format!("📊 B01-DNN Bảng cân đối kế toán tại {}:", to), &bs.lines);
+            if bs.total_assets != bs.total_sources {
+                error!("Balance sheet is unbalanced!");
+                table.note(format!("⚠️ Tổng tài sản {} ≠ tổng nguồn vốn {}", amount(bs.total_assets), amount(bs.total_sources)));
+            }
+            output::print(ctx.format, &bs, &table)
+        }
+        ReportCommand::B02 { from, to } => {
+            let (from, to) = range(&ctx.ledger, from, to);
+            let is = income_statement(&ctx.ledger, from, to);
+            let table = report_table(&format!("📊 B02-DNN Kết quả hoạt động kinh doanh ({} → {}):", from, to), &is.lines);
+            output::print(ctx.format, &is, &table)
+        }
+        ReportCommand::Ledger { account, from, to } => {
+            let (from, to) = range(&ctx.ledger, from, to);
+            let book = ledger_book(&ctx.ledger, &AccountCode::new(&account)?, from, to);
+            let mut table = Table::new(format!("📒 Sổ cái {} ({} → {}):", account, from, to))
+                .column("#")
+                .column("Ngày")
+                .column("Diễn giải")
+                .column("TK đối ứng")
+                .amount("Nợ")
+                .amount("Có")
+                .amount("Số dư");
+            let blank = String::new;
+            table.row(vec![blank(), blank(), "Số dư đầu kỳ".to_string(), blank(), blank(), blank(), amount(book.opening_balance)]);
+            for line in &book.lines {
+                let counter: Vec<&str> = line.counter_accounts.iter().map(|a| a.as_str()).collect();
+                let link = match (line.reverses, line.reversed_by) {
+                    (Some(id), _) => format!(" (đảo #{})", id),
+                    (_, Some(id)) => format!(" (đã đảo bởi #{})", id),
+                    _ => String::new(),
+                };
+                table.row(vec![
+                    line.entry_id.to_string(),
+                    line.date.to_string(),
+                    format!("{}{}", line.description, link),
+                    counter.join(","),
+                    amount(line.debit),
+                    amount(line.credit),
+                    amount(line.balance),
+                ]);
+            }
+            table.total(vec![
+                blank(),
+                blank(),
+                "Cộng phát sinh".to_string(),
+                blank(),
+                amount(book.total_debit),
+                amount(book.total_credit),
+                blank(),
+            ]);
+            table.total(vec![blank(), blank(), "Số dư cuối kỳ".to_string(), blank(), blank(), blank(), amount(book.closing_balance)]);
+            output::print(ctx.format, &book, &table)
+        }
+        ReportCommand::Aging { account, as_of } => {
+            let as_of = as_of.unwrap_or_else(|| last_date(&ctx.ledger));
+            let report = if account == "131" {
+                receivables_aging(&ctx.ledger, as_of)
+            } else {
+                payables_aging(&ctx.ledger, as_of)
+            };
+            match ctx.format {
+                Format::Table => print!("{}", aging_table(&report)),
+                Format::Json => println!("{}", report.to_json()?),
+                Format::Csv => print!("{}", report.to_csv()?),
+            }
+            Ok(())
+        }
+        ReportCommand::Xlsx { file, from, to, accounts } => {
+            let (from, to) = range(&ctx.ledger, from, to);
+            let mut workbook = ReportWorkbook::new();
+            workbook.add_trial_balance(&ctx.ledger.as_of_date(to))?;
+            workbook.add_balance_sheet(&balance_sheet(&ctx.ledger, to))?;
+            workbook.add_income_statement(&income_statement(&ctx.ledger, from, to))?;
+            for account in &accounts {
+                workbook.add_ledger_book(&ledger_book(&ctx.ledger, &AccountCode::new(account)?, from, to))?;
+            }
+            workbook.save(&file.to_string_lossy())?;
+            output::done(ctx.format, &format!("Reports exported to {}", file.display()), &file)
+        }
+    }
+}
+
+/// A trial balance with the account names of the chart.
+pub fn trial_balance_table(title: &str, tb: &TrialBalance, ctx: &Context) -> Table {
+    let mut table = Table::new(title).column("Tài khoản").column("Tên").amount("Nợ").amount("Có");
+    for bal in &tb.balances {
+        let name = ctx.coa.find(bal.account.as_str()).map(|a| a.name.clone()).unwrap_or_default();
+        table.row(vec![bal.account.as_str().to_string(), name, amount(bal.debit_balance), amount(bal.credit_balance)]);
+    }
+    table.total(vec!["Tổng cộng".to_string(), String::new(), amount(tb.total_debit), amount(tb.total_credit)]);
+    table
+}
+
+fn report_table(title: &str, lines: &[ReportLine]) -> Table {
+    let mut table = Table::new(title).column("Mã số").column("Chỉ tiêu").amount("Số tiền");
+    for line in lines {
+        let name = if line.is_total { line.name.to_uppercase() } else { line.name.clone() };
+        table.row(vec![line.code.clone(), name, amount(line.amount)]);
+    }
+    table
+}
+
+fn aging_table(report: &AgingReport) -> String {
+    let mut out = format!("📊 Tuổi nợ TK {} tại {}:\n", report.account, report.as_of);
+    let labels: Vec<String> = AgingBucket::ALL.iter().map(|b| format!("{:>14}", b.label())).collect();
+    out.push_str(&format!("{:<10} {:<30} {} {:>14}\n", "", "", labels.join(" "), "total"));
+    for line in report.lines.iter().chain(std::iter::once(&report.totals)) {
+        let amounts: Vec<String> = AgingBucket::ALL.iter().map(|b| format!("{:>14.2}", line.bucket(*b))).collect();
+        let party = if line.party.is_empty() { "Tổng cộng" } else { &line.party };
+        out.push_str(&format!("{:<10} {:<30} {} {:>14.2}\n", party, line.name, amounts.join(" "), line.total));
+    }
+    if !report.provisions.is_empty() {
+        out.push_str("Dự phòng phải thu khó đòi (Thông tư 48/2019):\n");
+        for p in &report.provisions {
+            out.push_str(&format!(
+                "{} #{} hạn {} quá hạn {} ngày: {:.2} × {} = {:.2}\n",
+                p.party,
+                p.entry_id,
+                p.due_date,
+                p.days_overdue,
+                p.open_amount,
+                p.rate,
+                p.provision
+            ));
+        }
+        out.push_str(&format!("Tổng dự phòng: {:.2}\n", report.total_provision));
+    }
+    out
+}
@@ -0,0 +1,277 @@
+// gl-cli/src/commands/subledger.rs
+
+//! Receivables (131), payables (331) and their open items.
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use clap::Subcommand;
+use gl_core::{
+    customer_balances, customer_statement, payments_due, reconcile_payables, reconcile_receivables, vendor_balances,
+    InvoiceAllocation, ItemKind, JournalEntry, PartyBalance, Reconciliation,
+};
+use serde::Deserialize;
+use serde_json::json;
+use tracing::error;
+
+use super::{check_open, last_date, range, read_json};
+use crate::context::Context;
+use crate::output::{self, amount, Table};
+
+#[derive(Subcommand, Debug)]
+pub enum ArCommand {
+    /// Customer balances, reconciled to 131
+    Balances {
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        as_of: Option<NaiveDate>,
+    },
+    /// Statement of account of a customer
+    Statement {
+        customer: String,
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        from: Option<NaiveDate>,
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        to: Option<NaiveDate>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ApCommand {
+    /// Record vendor invoices from a JSON array of {vendor, number, due_date?, entry}
+    Invoices { file: PathBuf },
+    /// Record vendor payments from a JSON array of {vendor, entry, allocations?}
+    Payments { file: PathBuf },
+    /// Vendor balances, reconciled to 331
+    Balances {
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        as_of: Option<NaiveDate>,
+    },
+    /// Unpaid vendor invoices due on or before a date
+    Due {
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        until: NaiveDate,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ItemsCommand {
+    /// Match payments and credit notes to invoices by reference, then by amount
+    Match {
+        #[arg(value_parser = ["131", "331"])]
+        account: String,
+    },
+    /// List the unmatched and partly matched items
+    Open {
+        #[arg(value_parser = ["131", "331"])]
+        account: String,
+    },
+}
+
+#[derive(Deserialize)]
+struct VendorInvoiceInput {
+    vendor: String,
+    number: String,
+    #[serde(default)]
+    due_date: Option<NaiveDate>,
+    entry: JournalEntry,
+}
+
+#[derive(Deserialize)]
+struct VendorPaymentInput {
+    vendor: String,
+    entry: JournalEntry,
+    /// Oldest invoices are paid first when empty.
+    #[serde(default)]
+    allocations: Vec<InvoiceAllocation>,
+}
+
+pub fn run_ar(ctx: &Context, command: ArCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        ArCommand::Balances { as_of } => {
+            let as_of = as_of.unwrap_or_else(|| last_date(&ctx.ledger));
+            let balances = customer_balances(&ctx.ledger, as_of);
+            let reconciliation = reconcile_receivables(&ctx.ledger, as_of);
+            print_balances(ctx, &format!("📒 Công nợ phải thu tại {}:", as_of), &balances, reconciliation, "AR", "131")
+        }
+        ArCommand::Statement { customer, from, to } => {
+            let (from, to) = range(&ctx.ledger, from, to);
+            let st = customer_statement(&ctx.ledger, &customer, from, to)?;
+            let mut table = Table::new(format!("📄 Đối chiếu công nợ {} - {} ({} → {}):", st.party, st.name, from, to))
+                .column("#")
+                .column("Ngày")
+                .column("Chứng từ")
+                .column("Diễn giải")
+                .amount("Nợ")
+                .amount("Có")
+                .amount("Số dư");
+            let blank = String::new;
+            table.row(vec![blank(), blank(), blank(), "Số dư đầu kỳ".to_string(), blank(), blank(), amount(st.opening_balance)]);
+            for line in &st.lines {
+                table.row(vec![
+                    line.entry_id.to_string(),
+                    line.date.to_string(),
+                    line.voucher.clone().unwrap_or_default(),
+                    line.description.clone(),
+                    amount(line.debit),
+                    amount(line.credit),
+                    amount(line.balance),
+                ]);
+            }
+            table.total(vec![blank(), blank(), blank(), "Số dư cuối kỳ".to_string(), blank(), blank(), amount(st.closing_balance)]);
+            output::print(ctx.format, &st, &table)
+        }
+    }
+}
+
+pub fn run_ap(ctx: &mut Context, command: ApCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        ApCommand::Invoices { file } => {
+            let invoices: Vec<VendorInvoiceInput> = read_json(&file)?;
+            let mut recorded = vec![];
+            let mut failed = 0;
+            for input in invoices {
+                let result = check_open(ctx, input.entry.date)
+                    .and_then(|_| ctx.ledger.record_vendor_invoice(&input.vendor, &input.number, input.entry, input.due_date));
+                match result {
+                    Ok(id) => recorded.push(json!({ "vendor": input.vendor, "invoice": input.number, "entry_id": id })),
+                    Err(e) => {
+                        failed += 1;
+                        eprintln!("❌ Invoice {} of {}: {}", input.number, input.vendor, e);
+                    }
+                }
+            }
+            output::done(ctx.format, &format!("{} invoice(s) recorded, {} failed", recorded.len(), failed), &recorded)?;
+            match failed {
+                0 => Ok(()),
+                n => Err(format!("{} invoice(s) of {} not recorded", n, file.display()).into()),
+            }
+        }
+        ApCommand::Payments { file } => {
+            let payments: Vec<VendorPaymentInput> = read_json(&file)?;
+            let mut recorded = vec![];
+            let mut failed = 0;
+            for input in payments {
+                let result = check_open(ctx, input.entry.date)
+                    .and_then(|_| ctx.ledger.pay_vendor(&input.vendor, input.entry, input.allocations));
+                match result {
+                    Ok(id) => recorded.push(json!({ "vendor": input.vendor, "entry_id": id })),
+                    Err(e) => {
+                        failed += 1;
+                        eprintln!("❌ Payment to {}: {}", input.vendor, e);
+                    }
+                }
+            }
+            output::done(ctx.format, &format!("{} payment(s) recorded, {} failed", recorded.len(), failed), &recorded)?;
+            match failed {
+                0 => Ok(()),
+                n => Err(format!("{} payment(s) of {} not recorded", n, file.display()).into()),
+            }
+        }
+        ApCommand::Balances { as_of } => {
+            let as_of = as_of.unwrap_or_else(|| last_date(&ctx.ledger));
+            let balances = vendor_balances(&ctx.ledger, as_of);
+            let reconciliation = reconcile_payables(&ctx.ledger, as_of);
+            print_balances(ctx, &format!("📒 Công nợ phải trả tại {}:", as_of), &balances, reconciliation, "AP", "331")
+        }
+        ApCommand::Due { until } => {
+            let schedule = payments_due(&ctx.ledger, until);
+            let mut table = Table::new(format!("📅 Hóa đơn đến hạn thanh toán đến {}:", until))
+                .column("Hạn")
+                .column("Nhà cung cấp")
+                .column("Tên")
+                .column("Hóa đơn")
+                .column("Ngày HĐ")
+                .amount("Còn phải trả")
+                .amount("Quá hạn (ngày)");
+            for line in &schedule.lines {
+                table.row(vec![
+                    line.due_date.to_string(),
+                    line.vendor.clone(),
+                    line.vendor_name.clone(),
+                    line.invoice.clone(),
+                    line.invoice_date.to_string(),
+                    amount(line.outstanding),
+                    line.days_overdue.max(0).to_string(),
+                ]);
+            }
+            let blank = String::new;
+            table.total(vec!["Tổng cộng".to_string(), blank(), blank(), blank(), blank(), amount(schedule.total), blank()]);
+            output::print(ctx.format, &schedule, &table)
+        }
+    }
+}
+
+pub fn run_items(ctx: &mut Context, command: ItemsCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        ItemsCommand::Match { account } => {
+            let made = ctx.ledger.auto_match(&account)?;
+            let mut table = Table::new(format!("🔗 Đối trừ TK {}:", account))
+                .column("Đối tượng")
+                .column("Hóa đơn #")
+                .column("Thanh toán #")
+                .amount("Số tiền");
+            for m in &made {
+                table.row(vec![m.party.clone(), m.charge.to_string(), m.settlement.to_string(), amount(m.amount)]);
+            }
+            table.note(format!("✅ {} match(es) on {}", made.len(), account));
+            output::print(ctx.format, &made, &table)
+        }
+        ItemsCommand::Open { account } => {
+            let items = ctx.ledger.unmatched_items(&account)?;
+            let mut table = Table::new(format!("📋 Khoản mục chưa đối trừ TK {}:", account))
+                .column("Đối tượng")
+                .column("#")
+                .column("Loại")
+                .column("Ngày")
+                .column("Tham chiếu")
+                .column("Diễn giải")
+                .amount("Số tiền")
+                .amount("Đã đối trừ")
+                .amount("Còn lại");
+            for item in &items {
+                let kind = match item.kind {
+                    ItemKind::Charge => "HĐ",
+                    ItemKind::Settlement => "TT",
+                };
+                table.row(vec![
+                    item.party.clone(),
+                    item.entry_id.to_string(),
+                    kind.to_string(),
+                    item.date.to_string(),
+                    item.reference.clone().unwrap_or_default(),
+                    item.description.clone(),
+                    amount(item.amount),
+                    amount(item.matched),
+                    amount(item.open_amount()),
+                ]);
+            }
+            output::print(ctx.format, &items, &table)
+        }
+    }
+}
+
+/// Party balances with the check of the subledger against its control
+/// account; a difference fails the command.
+fn print_balances(
+    ctx: &Context,
+    title: &str,
+    balances: &[PartyBalance],
+    reconciliation: Result<Reconciliation, gl_core::LedgerError>,
+    subledger: &str,
+    control: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut table = Table::new(title).column("Mã").column("Tên").amount("Số dư");
+    for b in balances {
+        table.row(vec![b.party.clone(), b.name.clone(), amount(b.balance)]);
+    }
+    if let Ok(r) = &reconciliation {
+        table.total(vec!["Tổng cộng".to_string(), String::new(), amount(r.subledger_total)]);
+        table.note(format!("✅ {} subledger reconciles to {}: {}", subledger, control, amount(r.ledger_balance)));
+    }
+    output::print(ctx.format, balances, &table)?;
+    reconciliation.map(|_| ()).map_err(|e| {
+        error!("{} reconciliation failed: {}", subledger, e);
+        e.into()
+    })
+}
@@ -0,0 +1,292 @@
+// gl-cli/src/context.rs
+
+//! The books a command works on: the ledger file, its audit trail, chart of
+//! accounts and master files, loaded from the global options.
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use gl_core::{
+    import_rates_csv, load_vn_coa, AuditLog, CoaConfig, Company, CompanyRegistry, CustomerMaster, Ledger, PeriodManager,
+    PrecisionPolicy, RateTable, RecurringTemplates, UserDirectory, VendorMaster, VoucherFormat,
+};
+use tracing::info;
+
+use crate::output::Format;
+
+#[derive(Args, Debug)]
+pub struct GlobalArgs {
+    /// Output format of listings, reports and results
+    #[arg(long, global = true, value_enum, default_value_t = Format::Table)]
+    pub format: Format,
+    /// Company whose books to work on (see --companies)
+    #[arg(long, global = true, value_name = "ID")]
+    pub company: Option<String>,
+    /// Registry of the companies served (YAML)
+    #[arg(long, global = true, value_name = "FILE", default_value = "data/companies.yaml")]
+    pub companies: PathBuf,
+    /// Ledger file [default: data/ledger.json, or data/companies/<ID>/ledger.json]
+    #[arg(long, global = true, value_name = "FILE")]
+    pub ledger: Option<PathBuf>,
+    /// Append-only audit trail (JSONL) [default: next to the ledger file]
+    #[arg(long, global = true, value_name = "FILE")]
+    pub audit_log: Option<PathBuf>,
+    /// User recorded in the audit trail (defaults to $USER)
+    #[arg(long, global = true, value_name = "USER")]
+    pub actor: Option<String>,
+    /// Users and roles (YAML), stored with the ledger; the actor then signs in with --token
+    #[arg(long, global = true, value_name = "FILE")]
+    pub users: Option<PathBuf>,
+    /// API token of the user acting
+    #[arg(long, global = true, value_name = "TOKEN", env = "GL_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+    /// YAML customer master; lines on 131 must then name a customer
+    #[arg(long, global = true, value_name = "FILE")]
+    pub customers: Option<PathBuf>,
+    /// YAML vendor master; lines on 331 must then name a vendor
+    #[arg(long, global = true, value_name = "FILE")]
+    pub vendors: Option<PathBuf>,
+    /// Amount precision and rounding policy (YAML); VND is whole đồng by default
+    #[arg(long, global = true, value_name = "FILE")]
+    pub precision: Option<PathBuf>,
+    /// Exchange rates from CSV (date,currency,buying,selling)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub fx_rates: Option<PathBuf>,
+    /// YAML voucher prefixes and digits
+    #[arg(long, global = true, value_name = "FILE")]
+    pub voucher_format: Option<PathBuf>,
+    /// YAML templates of recurring entries (rent, depreciation, ...)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub recurring: Option<PathBuf>,
+}
+
+/// An opened set of books; `save` writes back what the command changed.
+pub struct Context {
+    pub ledger: Ledger,
+    pub periods: PeriodManager,
+    pub coa: CoaConfig,
+    pub company: Option<Company>,
+    pub format: Format,
+    ledger_path: PathBuf,
+    audit_path: PathBuf,
+    audit_written: usize,
+    loaded_sequence: Option<u64>,
+    masters_changed: bool,
+}
+
+impl Context {
+    /// Opens the ledger file, or starts a new ledger when `create` is set.
+    pub fn open(args: &GlobalArgs, create: bool) -> Result<Self, Box<dyn Error>> {
+        let (company, ledger_path, audit_path) = locate(args)?;
+        let (mut ledger, loaded_sequence) = match (ledger_path.exists(), create) {
+            (true, true) => return Err(format!("{} already exists", ledger_path.display()).into()),
+            (true, false) => {
+                let contents = read(&ledger_path, "ledger")?;
+                let ledger: Ledger = serde_json::from_str(&contents)
+                    .map_err(|e| format!("Failed to read ledger {}: {}", ledger_path.display(), e))?;
+                let sequence = ledger.sequence();
+                (ledger, Some(sequence))
+            }
+            (false, true) => (Ledger::new(), None),
+            (false, false) => {
+                return Err(format!("No ledger at {}; create one with `gl ledger init`", ledger_path.display()).into())
+            }
+        };
+        let audit = AuditLog::load(&audit_path)
+            .map_err(|e| format!("Failed to read audit trail {}: {}", audit_path.display(), e))?;
+        let audit_written = audit.events().len();
+        ledger = attach_trail(ledger, audit, &ledger_path, &audit_path)?;
+        if let Some(company) = &company {
+            info!("Working on the books of {} ({})", company.name, company.tax_code);
+            ledger = ledger.for_company(&company.id)?;
+        }
+
+        let mut masters_changed = false;
+        if let Some(path) = &args.customers {
+            let customers = CustomerMaster::from_yaml(&read(path, "customers")?)
+                .map_err(|e| format!("Failed to read customers {}: {}", path.display(), e))?;
+            ledger = ledger.with_customers(customers);
+            masters_changed = true;
+        }
+        if let Some(path) = &args.vendors {
+            let vendors = VendorMaster::from_yaml(&read(path, "vendors")?)
+                .map_err(|e| format!("Failed to read vendors {}: {}", path.display(), e))?;
+            ledger = ledger.with_vendors(vendors);
+            masters_changed = true;
+        }
+        if let Some(path) = &args.precision {
+            let precision = PrecisionPolicy::from_yaml(&read(path, "precision policy")?)
+                .map_err(|e| format!("Failed to read precision policy {}: {}", path.display(), e))?;
+            ledger = ledger.with_precision(precision);
+            masters_changed = true;
+        }
+        if let Some(path) = &args.fx_rates {
+            let rates = std::fs::File::open(path)
+                .map_err(|e| e.to_string())
+                .and_then(|file| import_rates_csv(file, b',').map_err(|e| e.to_string()))
+                .map_err(|e| format!("Failed to read exchange rates {}: {}", path.display(), e))?;
+            ledger = ledger.with_rates(RateTable { rates });
+            masters_changed = true;
+        }
+        if let Some(path) = &args.voucher_format {
+            let format = VoucherFormat::from_yaml(&read(path, "voucher format")?)
+                .map_err(|e| format!("Failed to read voucher format {}: {}", path.display(), e))?;
+            ledger = ledger.with_voucher_format(format);
+            masters_changed = true;
+        }
+        if let Some(path) = &args.recurring {
+            let templates = RecurringTemplates::from_yaml(&read(path, "recurring templates")?)
+                .map_err(|e| format!("Failed to read recurring templates {}: {}", path.display(), e))?;
+            ledger = ledger.with_recurring(templates);
+            masters_changed = true;
+        }
+
+        // Books that have users are opened only with the token of one of
+        // them; a --users file protects books that have none yet
+        let users = match &args.users {
+            Some(path) => Some(
+                UserDirectory::from_yaml(&read(path, "users")?)
+                    .map_err(|e| format!("Failed to read users {}: {}", path.display(), e))?,
+            ),
+            None => None,
+        };
+        let directory = match &users {
+            Some(users) if ledger.users().is_empty() => users,
+            _ => ledger.users(),
+        };
+        let actor = if directory.is_empty() {
+            args.actor
+                .clone()
+                .or_else(|| std::env::var("USER").ok())
+                .unwrap_or_else(|| gl_core::SYSTEM_ACTOR.to_string())
+        } else {
+            let username = match args.token.as_deref().and_then(|t| directory.authenticate(t)) {
+                Some(user) => user.username.clone(),
+                None => {
+                    return Err(format!(
+                        "{} is protected by users; sign in with a valid --token (or $GL_TOKEN)",
+                        ledger_path.display()
+                    )
+                    .into())
+                }
+            };
+            if let Some(actor) = args.actor.as_ref().filter(|a| **a != username) {
+                return Err(format!("The token belongs to {}, not {}", username, actor).into());
+            }
+            username
+        };
+        ledger.set_actor(&actor);
+        if let Some(users) = users.filter(|u| u != ledger.users()) {
+            // Replacing the users of protected books needs a user who may manage them
+            ledger.set_users(users)?;
+            masters_changed = true;
+        }
+
+        let coa = company
+            .as_ref()
+            .map_or_else(load_vn_coa, Company::load_coa)
+            .map_err(|e| format!("Failed to load the chart of accounts: {}", e))?;
+        let periods = ledger.period_manager();
+        Ok(Context {
+            ledger,
+            periods,
+            coa,
+            company,
+            format: args.format,
+            ledger_path,
+            audit_path,
+            audit_written,
+            loaded_sequence,
+            masters_changed,
+        })
+    }
+
+    /// Appends the new audit events, then writes the ledger file, which
+    /// records where the trail now ends, if the command recorded anything
+    /// or loaded master files. May be called again after further changes.
+    pub fn save(&mut self) -> Result<(), Box<dyn Error>> {
+        let audited = self.ledger.audit_log().events().len() > self.audit_written;
+        let changed = match self.loaded_sequence {
+            Some(sequence) => sequence != self.ledger.sequence() || self.masters_changed || audited,
+            // A new ledger is only written once something is recorded in it
+            None => self.ledger.sequence() > 0 || audited,
+        };
+        self.ledger
+            .audit_log()
+            .append_to(&self.audit_path, self.audit_written)
+            .map_err(|e| format!("Failed to write audit trail {}: {}", self.audit_path.display(), e))?;
+        self.audit_written = self.ledger.audit_log().events().len();
+        if changed {
+            if let Some(parent) = self.ledger_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            // Write aside and rename, so a failed write leaves the old file
+            let tmp = self.ledger_path.with_extension("json.tmp");
+            std::fs::write(&tmp, serde_json::to_string(&self.ledger)?)?;
+            std::fs::rename(&tmp, &self.ledger_path)
+                .map_err(|e| format!("Failed to write ledger {}: {}", self.ledger_path.display(), e))?;
+            self.loaded_sequence = Some(self.ledger.sequence());
+            self.masters_changed = false;
+        }
+        Ok(())
+    }
+
+    /// Whether events were recorded since the ledger was last saved.
+    pub fn has_new_events(&self) -> bool {
+        self.ledger.sequence() != self.loaded_sequence.unwrap_or(0)
+    }
+
+    pub fn ledger_path(&self) -> &Path {
+        &self.ledger_path
+    }
+
+    /// The ledger of the selected books with its audit trail, checked
+    /// against each other, without signing in.
+    pub fn audit_trail(args: &GlobalArgs) -> Result<(PathBuf, Ledger), Box<dyn Error>> {
+        let (_, ledger_path, audit_path) = locate(args)?;
+        if !ledger_path.exists() {
+            return Err(format!("No ledger at {} to check the audit trail against", ledger_path.display()).into());
+        }
+        let ledger: Ledger = serde_json::from_str(&read(&ledger_path, "ledger")?)
+            .map_err(|e| format!("Failed to read ledger {}: {}", ledger_path.display(), e))?;
+        let audit = AuditLog::load(&audit_path)
+            .map_err(|e| format!("Failed to read audit trail {}: {}", audit_path.display(), e))?;
+        Ok((audit_path.clone(), attach_trail(ledger, audit, &ledger_path, &audit_path)?))
+    }
+}
+
+/// The selected company and the paths of its ledger file and audit trail.
+fn locate(args: &GlobalArgs) -> Result<(Option<Company>, PathBuf, PathBuf), Box<dyn Error>> {
+    let company = match &args.company {
+        Some(id) => {
+            let contents = read(&args.companies, "companies")?;
+            let registry = CompanyRegistry::from_yaml(&contents)
+                .map_err(|e| format!("Failed to read companies {}: {}", args.companies.display(), e))?;
+            Some(registry.get(id)?.clone())
+        }
+        None => None,
+    };
+    // Each company keeps its books and audit trail in its own directory
+    let dir = match &company {
+        Some(company) => PathBuf::from("data/companies").join(&company.id),
+        None => PathBuf::from("data"),
+    };
+    let ledger_path = args.ledger.clone().unwrap_or_else(|| dir.join("ledger.json"));
+    let audit_path = args.audit_log.clone().unwrap_or_else(|| match ledger_path.parent() {
+        Some(parent) => parent.join("audit_log.jsonl"),
+        None => PathBuf::from("audit_log.jsonl"),
+    });
+    Ok((company, ledger_path, audit_path))
+}
+
+/// Continues the ledger's audit trail; it must end where the ledger file says.
+fn attach_trail(ledger: Ledger, audit: AuditLog, ledger_path: &Path, audit_path: &Path) -> Result<Ledger, Box<dyn Error>> {
+    ledger.with_audit_log(audit).map_err(|e| {
+        format!("Audit trail {} does not match ledger {}: {}", audit_path.display(), ledger_path.display(), e).into()
+    })
+}
+
+fn read(path: &Path, what: &str) -> Result<String, Box<dyn Error>> {
+    std::fs::read_to_string(path).map_err(|e| format!("Failed to read {} {}: {}", what, path.display(), e).into())
+}
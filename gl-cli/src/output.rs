@@ -0,0 +1,134 @@
+// gl-cli/src/output.rs
+
+//! Rendering of listings and reports as an aligned table, JSON or CSV.
+
+use std::error::Error;
+use std::io;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Aligned columns for reading in a terminal
+    Table,
+    /// The report as JSON, for scripts
+    Json,
+    /// The table rows as CSV, for spreadsheets
+    Csv,
+}
+
+/// Rows of a listing; amounts are right-aligned in table output.
+pub struct Table {
+    title: String,
+    headers: Vec<String>,
+    right: Vec<bool>,
+    rows: Vec<Vec<String>>,
+    /// Index of the first totals row, set off by a rule in table output.
+    totals_from: Option<usize>,
+    notes: Vec<String>,
+}
+
+impl Table {
+    pub fn new(title: impl Into<String>) -> Self {
+        Table { title: title.into(), headers: vec![], right: vec![], rows: vec![], totals_from: None, notes: vec![] }
+    }
+
+    pub fn column(mut self, header: &str) -> Self {
+        self.headers.push(header.to_string());
+        self.right.push(false);
+        self
+    }
+
+    pub fn amount(mut self, header: &str) -> Self {
+        self.headers.push(header.to_string());
+        self.right.push(true);
+        self
+    }
+
+    pub fn row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    /// A totals row; also written to CSV.
+    pub fn total(&mut self, cells: Vec<String>) {
+        self.totals_from.get_or_insert(self.rows.len());
+        self.rows.push(cells);
+    }
+
+    /// A line printed under the table only.
+    pub fn note(&mut self, note: impl Into<String>) {
+        self.notes.push(note.into());
+    }
+
+    fn render(&self) -> String {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate().take(widths.len()) {
+                widths[i] = widths[i].max(cell.chars().count());
+            }
+        }
+        let line = |cells: &[String]| {
+            let padded: Vec<String> = cells
+                .iter()
+                .enumerate()
+                .take(widths.len())
+                .map(|(i, cell)| {
+                    let pad = " ".repeat(widths[i] - cell.chars().count());
+                    if self.right[i] { format!("{}{}", pad, cell) } else { format!("{}{}", cell, pad) }
+                })
+                .collect();
+            padded.join("  ").trim_end().to_string()
+        };
+        let rule = widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>().join("  ");
+
+        let mut out = format!("{}\n{}\n{}\n", self.title, line(&self.headers), rule);
+        for (i, row) in self.rows.iter().enumerate() {
+            if self.totals_from == Some(i) {
+                out.push_str(&rule);
+                out.push('\n');
+            }
+            out.push_str(&line(row));
+            out.push('\n');
+        }
+        for note in &self.notes {
+            out.push_str(note);
+            out.push('\n');
+        }
+        out
+    }
+
+    fn write_csv(&self, writer: impl io::Write) -> Result<(), Box<dyn Error>> {
+        let mut csv = csv::Writer::from_writer(writer);
+        csv.write_record(&self.headers)?;
+        for row in &self.rows {
+            csv.write_record(row)?;
+        }
+        csv.flush()?;
+        Ok(())
+    }
+}
+
+/// Prints `data` as JSON, or `table` as a table or CSV.
+pub fn print<T: Serialize + ?Sized>(format: Format, data: &T, table: &Table) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Table => print!("{}", table.render()),
+        Format::Json => println!("{}", serde_json::to_string_pretty(data)?),
+        Format::Csv => table.write_csv(io::stdout().lock())?,
+    }
+    Ok(())
+}
+
+/// Reports the outcome of a command: `message` for people, `data` as JSON.
+pub fn done<T: Serialize + ?Sized>(format: Format, message: &str, data: &T) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(data)?),
+        Format::Table | Format::Csv => println!("✅ {}", message),
+    }
+    Ok(())
+}
+
+/// Amounts as in the statutory forms: two decimals, no grouping.
+pub fn amount(value: rust_decimal::Decimal) -> String {
+    format!("{:.2}", value)
+}
@@ -0,0 +1,355 @@
+// gl-cli/src/tui/app.rs
+
+//! State of the entry screen, kept apart from drawing and the terminal.
+
+use chrono::NaiveDate;
+use gl_core::{
+    parse_amount, parse_date, Account, AccountCode, CoaConfig, JournalEntry, JournalLine, NumberFormat, PeriodManager,
+};
+use rust_decimal::Decimal;
+
+/// Suggestions shown under an account field.
+const MAX_SUGGESTIONS: usize = 8;
+/// Dates are typed as in the CSV import.
+pub const DATE_FORMAT: &str = "%d/%m/%Y";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum View {
+    Entry,
+    TrialBalance,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Account,
+    Debit,
+    Credit,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Focus {
+    Date,
+    Description,
+    Line(usize, Column),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LineInput {
+    pub account: String,
+    pub debit: String,
+    pub credit: String,
+}
+
+impl LineInput {
+    fn is_blank(&self) -> bool {
+        self.account.trim().is_empty() && self.debit.trim().is_empty() && self.credit.trim().is_empty()
+    }
+}
+
+/// Outcome of the last action, shown in the status bar.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Info(String),
+    Error(String),
+}
+
+pub struct App {
+    pub view: View,
+    pub date: String,
+    pub description: String,
+    pub lines: Vec<LineInput>,
+    pub focus: Focus,
+    /// Selected suggestion of the focused account field.
+    pub selected: usize,
+    pub status: Status,
+    pub quit: bool,
+}
+
+impl App {
+    pub fn new(date: NaiveDate) -> Self {
+        App {
+            view: View::Entry,
+            date: date.format(DATE_FORMAT).to_string(),
+            description: String::new(),
+            lines: vec![LineInput::default(), LineInput::default()],
+            focus: Focus::Line(0, Column::Account),
+            selected: 0,
+            status: Status::Info(HELP.to_string()),
+            quit: false,
+        }
+    }
+
+    /// Clears the voucher after posting, keeping its date for the next one.
+    pub fn reset(&mut self) {
+        self.description.clear();
+        self.lines = vec![LineInput::default(), LineInput::default()];
+        self.focus = Focus::Line(0, Column::Account);
+        self.selected = 0;
+    }
+
+    /// The text of the focused field.
+    pub fn field_mut(&mut self) -> &mut String {
+        match self.focus {
+            Focus::Date => &mut self.date,
+            Focus::Description => &mut self.description,
+            Focus::Line(i, Column::Account) => &mut self.lines[i].account,
+            Focus::Line(i, Column::Debit) => &mut self.lines[i].debit,
+            Focus::Line(i, Column::Credit) => &mut self.lines[i].credit,
+        }
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        self.field_mut().push(c);
+        self.selected = 0;
+    }
+
+    pub fn backspace(&mut self) {
+        self.field_mut().pop();
+        self.selected = 0;
+    }
+
+    /// Fields in tab order: date, description, then each line.
+    fn fields(&self) -> Vec<Focus> {
+        let mut fields = vec![Focus::Date, Focus::Description];
+        for i in 0..self.lines.len() {
+            fields.extend([Column::Account, Column::Debit, Column::Credit].map(|c| Focus::Line(i, c)));
+        }
+        fields
+    }
+
+    pub fn next_field(&mut self) {
+        // Enter on the last field starts a new line
+        if self.focus == Focus::Line(self.lines.len() - 1, Column::Credit) {
+            self.add_line();
+            return;
+        }
+        let fields = self.fields();
+        let at = fields.iter().position(|f| *f == self.focus).unwrap_or(0);
+        self.focus = fields[(at + 1).min(fields.len() - 1)];
+        self.selected = 0;
+    }
+
+    pub fn previous_field(&mut self) {
+        let fields = self.fields();
+        let at = fields.iter().position(|f| *f == self.focus).unwrap_or(0);
+        self.focus = fields[at.saturating_sub(1)];
+        self.selected = 0;
+    }
+
+    pub fn add_line(&mut self) {
+        self.lines.push(LineInput::default());
+        self.focus = Focus::Line(self.lines.len() - 1, Column::Account);
+        self.selected = 0;
+    }
+
+    pub fn remove_line(&mut self) {
+        if let Focus::Line(i, column) = self.focus {
+            if self.lines.len() > 1 {
+                self.lines.remove(i);
+                self.focus = Focus::Line(i.min(self.lines.len() - 1), column);
+            } else {
+                self.lines[0] = LineInput::default();
+            }
+        }
+    }
+
+    /// Moves the suggestion selection, or to the same column of another line.
+    pub fn move_vertical(&mut self, coa: &CoaConfig, down: bool) {
+        let count = self.suggestions(coa).len();
+        if count > 0 {
+            self.selected = if down { (self.selected + 1) % count } else { (self.selected + count - 1) % count };
+            return;
+        }
+        if let Focus::Line(i, column) = self.focus {
+            let i = if down { (i + 1).min(self.lines.len() - 1) } else { i.saturating_sub(1) };
+            self.focus = Focus::Line(i, column);
+        }
+    }
+
+    /// Accounts matching the focused account field, by code prefix or by
+    /// name; none once the field holds an account of the chart.
+    pub fn suggestions<'a>(&self, coa: &'a CoaConfig) -> Vec<&'a Account> {
+        let Focus::Line(i, Column::Account) = self.focus else { return vec![] };
+        let typed = self.lines[i].account.trim().to_lowercase();
+        if typed.is_empty() || coa.accounts.iter().any(|a| a.code == typed) {
+            return vec![];
+        }
+        let by_code = coa.accounts.iter().filter(|a| a.code.starts_with(&typed));
+        let by_name = coa
+            .accounts
+            .iter()
+            .filter(|a| !a.code.starts_with(&typed) && a.name.to_lowercase().contains(&typed));
+        by_code.chain(by_name).take(MAX_SUGGESTIONS).collect()
+    }
+
+    /// Fills the focused account field with the selected suggestion.
+    pub fn complete(&mut self, coa: &CoaConfig) -> bool {
+        let Some(code) = self.suggestions(coa).get(self.selected).map(|a| a.code.clone()) else { return false };
+        *self.field_mut() = code;
+        self.selected = 0;
+        true
+    }
+
+    /// Total debit and credit of the lines; amounts that do not parse count
+    /// as zero here and are reported when posting.
+    pub fn totals(&self) -> (Decimal, Decimal) {
+        let amount = |value: &str| parse_amount(value, &NumberFormat::Vietnamese).unwrap_or(Decimal::ZERO);
+        self.lines
+            .iter()
+            .fold((Decimal::ZERO, Decimal::ZERO), |(d, c), l| (d + amount(&l.debit), c + amount(&l.credit)))
+    }
+
+    /// Whether the date parses and falls in an open period.
+    pub fn date_check(&self, periods: &PeriodManager) -> Result<NaiveDate, String> {
+        let date = parse_date(&self.date, DATE_FORMAT)?;
+        if !periods.is_date_in_open_period(date) {
+            return Err(format!("{} is not in an open period", date.format(DATE_FORMAT)));
+        }
+        Ok(date)
+    }
+
+    /// The voucher as a journal entry, skipping blank lines.
+    pub fn entry(&self, periods: &PeriodManager) -> Result<JournalEntry, String> {
+        let date = self.date_check(periods)?;
+        let mut lines = vec![];
+        for (n, input) in self.lines.iter().enumerate().filter(|(_, l)| !l.is_blank()) {
+            let line = || -> Result<JournalLine, String> {
+                let account = AccountCode::new(input.account.trim()).map_err(|e| e.to_string())?;
+                let debit = parse_amount(&input.debit, &NumberFormat::Vietnamese)?;
+                let credit = parse_amount(&input.credit, &NumberFormat::Vietnamese)?;
+                JournalLine::new(account, debit, credit).map_err(|e| e.to_string())
+            };
+            lines.push(line().map_err(|e| format!("line {}: {}", n + 1, e))?);
+        }
+        JournalEntry::new(date, lines, self.description.trim().to_string()).map_err(|e| e.to_string())
+    }
+}
+
+pub const HELP: &str =
+    "Tab: complete/next  Enter: next  ↑↓: suggestion/line  Ctrl+N/Ctrl+D: add/delete line  Ctrl+S: post  F2: trial balance  Esc: quit";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gl_core::Period;
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn coa() -> CoaConfig {
+        let account = |code: &str, name: &str| Account {
+            code: code.to_string(),
+            name: name.to_string(),
+            classification: String::new(),
+            default_balance: String::new(),
+            description: String::new(),
+        };
+        CoaConfig {
+            accounts: vec![
+                account("1111", "Tiền Việt Nam"),
+                account("1121", "Tiền gửi ngân hàng"),
+                account("131", "Phải thu của khách hàng"),
+                account("5111", "Doanh thu bán hàng hóa"),
+            ],
+        }
+    }
+
+    /// January 2025 open, February closed.
+    fn periods() -> PeriodManager {
+        let mut periods = PeriodManager::empty();
+        periods.add_period(Period::new(ymd(2025, 1, 1), ymd(2025, 1, 31)));
+        periods.add_period(Period::new(ymd(2025, 2, 1), ymd(2025, 2, 28)));
+        periods.close_current_period();
+        periods
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        text.chars().for_each(|c| app.type_char(c));
+    }
+
+    /// A voucher of January 10th with these lines (account, debit, credit).
+    fn voucher(lines: &[(&str, &str, &str)]) -> App {
+        let mut app = App::new(ymd(2025, 1, 10));
+        app.description = "Bán hàng".to_string();
+        app.lines = lines
+            .iter()
+            .map(|(account, debit, credit)| LineInput {
+                account: account.to_string(),
+                debit: debit.to_string(),
+                credit: credit.to_string(),
+            })
+            .collect();
+        app
+    }
+
+    // --- Completion Tests ---
+    #[test]
+    fn test_completion_by_code() {
+        let coa = coa();
+        let mut app = App::new(ymd(2025, 1, 10));
+        type_text(&mut app, "11");
+        let codes: Vec<&str> = app.suggestions(&coa).iter().map(|a| a.code.as_str()).collect();
+        assert_eq!(codes, vec!["1111", "1121"]);
+
+        app.move_vertical(&coa, true);
+        assert!(app.complete(&coa));
+        assert_eq!(app.lines[0].account, "1121");
+        // A complete code suggests nothing more
+        assert!(app.suggestions(&coa).is_empty());
+        assert!(!app.complete(&coa));
+    }
+
+    #[test]
+    fn test_completion_by_vietnamese_name() {
+        let coa = coa();
+        let mut app = App::new(ymd(2025, 1, 10));
+        type_text(&mut app, "doanh thu");
+        let codes: Vec<&str> = app.suggestions(&coa).iter().map(|a| a.code.as_str()).collect();
+        assert_eq!(codes, vec!["5111"]);
+        assert!(app.complete(&coa));
+        assert_eq!(app.lines[0].account, "5111");
+
+        app.next_field();
+        type_text(&mut app, "tiền");
+        assert!(app.suggestions(&coa).is_empty(), "only account fields are completed");
+    }
+
+    // --- Voucher Tests ---
+    #[test]
+    fn test_totals_show_the_difference() {
+        let app = voucher(&[("1111", "1.500.000", ""), ("5111", "", "1.000.000"), ("131", "abc", "")]);
+        let (debit, credit) = app.totals();
+        assert_eq!(debit, Decimal::from(1_500_000));
+        assert_eq!(credit, Decimal::from(1_000_000));
+        assert_eq!(debit - credit, Decimal::from(500_000));
+    }
+
+    #[test]
+    fn test_entry_from_the_form() {
+        let periods = periods();
+        let app = voucher(&[("1111", "1.000.000", ""), ("", "", ""), ("5111", "", "1.000.000")]);
+        let entry = app.entry(&periods).unwrap();
+        assert_eq!(entry.date, ymd(2025, 1, 10));
+        assert_eq!(entry.lines.len(), 2, "blank lines are skipped");
+        assert_eq!(entry.description, "Bán hàng");
+
+        let unbalanced = voucher(&[("1111", "1.000.000", ""), ("5111", "", "900.000")]);
+        assert!(unbalanced.entry(&periods).is_err());
+        let bad_amount = voucher(&[("1111", "1.000.000", ""), ("5111", "", "một triệu")]);
+        assert!(bad_amount.entry(&periods).unwrap_err().starts_with("line 2"));
+    }
+
+    #[test]
+    fn test_closed_period_is_rejected() {
+        let periods = periods();
+        let mut app = voucher(&[("1111", "1.000.000", ""), ("5111", "", "1.000.000")]);
+        assert_eq!(app.date_check(&periods), Ok(ymd(2025, 1, 10)));
+
+        app.date = "15/02/2025".to_string();
+        assert!(app.date_check(&periods).unwrap_err().contains("not in an open period"));
+        assert!(app.entry(&periods).is_err());
+        app.date = "31/13/2025".to_string();
+        assert!(app.date_check(&periods).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+// gl-cli/src/tui/mod.rs
+
+//! Interactive entry of vouchers (`gl tui`): lines are typed with account
+//! completion from the chart, the debit/credit difference and the period of
+//! the date are checked as one types, and posting goes through the same
+//! validation as `gl entry add`. Each posted voucher is saved at once; a
+//! user who may not post directly submits it as a draft for review.
+
+mod app;
+mod ui;
+
+use std::error::Error;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use gl_core::{JournalEntry, LedgerError, Permission};
+use ratatui::DefaultTerminal;
+use tracing::info;
+
+use crate::commands::today;
+use crate::context::Context;
+use app::{App, Status, View, HELP};
+
+pub fn run(ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, ctx);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+    let mut app = App::new(today());
+    while !app.quit {
+        terminal.draw(|frame| ui::draw(frame, &app, ctx))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                handle_key(&mut app, ctx, key);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn handle_key(app: &mut App, ctx: &mut Context, key: KeyEvent) {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => app.quit = true,
+        KeyCode::Char('c') if ctrl => app.quit = true,
+        KeyCode::F(2) => {
+            app.view = match app.view {
+                View::Entry => View::TrialBalance,
+                View::TrialBalance => View::Entry,
+            }
+        }
+        _ if app.view == View::TrialBalance => {}
+        KeyCode::Char('s') if ctrl => post(app, ctx),
+        KeyCode::Char('n') if ctrl => app.add_line(),
+        KeyCode::Char('d') if ctrl => app.remove_line(),
+        KeyCode::Tab if !app.complete(&ctx.coa) => app.next_field(),
+        KeyCode::BackTab => app.previous_field(),
+        KeyCode::Enter => {
+            app.complete(&ctx.coa);
+            app.next_field();
+        }
+        KeyCode::Up => app.move_vertical(&ctx.coa, false),
+        KeyCode::Down => app.move_vertical(&ctx.coa, true),
+        KeyCode::Backspace => app.backspace(),
+        KeyCode::Char(c) if !ctrl => app.type_char(c),
+        _ => {}
+    }
+}
+
+/// Posts the voucher, or submits it as a draft when the user may not post
+/// directly, and saves the ledger; or shows why it cannot be.
+fn post(app: &mut App, ctx: &mut Context) {
+    let recorded = app
+        .entry(&ctx.periods)
+        .and_then(|entry| {
+            ctx.ledger.validate_entry(&entry, &ctx.coa, &ctx.periods).map_err(|e| e.to_string())?;
+            Ok(entry)
+        })
+        .and_then(|entry| record(ctx, entry).map_err(|e| e.to_string()));
+    app.status = match recorded {
+        Ok(done) => {
+            info!("{} from the terminal UI", done);
+            app.reset();
+            match ctx.save() {
+                Ok(()) => Status::Info(format!("✅ {}. {}", done, HELP)),
+                Err(e) => Status::Error(format!("{} but not saved: {}", done, e)),
+            }
+        }
+        Err(e) => Status::Error(format!("❌ {}", e)),
+    };
+}
+
+fn record(ctx: &mut Context, entry: JournalEntry) -> Result<String, LedgerError> {
+    if ctx.ledger.may(Permission::PostEntry) {
+        let id = ctx.ledger.post_entry(entry)?;
+        return Ok(format!("Entry #{} posted", id));
+    }
+    let id = ctx.ledger.save_draft(entry)?;
+    ctx.ledger.submit_draft(id)?;
+    Ok(format!("Draft #{} submitted for review", id))
+}
@@ -0,0 +1,122 @@
+// gl-cli/src/tui/ui.rs
+
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table};
+use ratatui::Frame;
+use rust_decimal::Decimal;
+
+use super::app::{App, Column, Focus, Status, View};
+use crate::context::Context;
+use crate::output::amount;
+
+pub fn draw(frame: &mut Frame, app: &App, ctx: &Context) {
+    let [body, status] = Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).areas(frame.area());
+    match app.view {
+        View::Entry => draw_entry(frame, body, app, ctx),
+        View::TrialBalance => draw_trial_balance(frame, body, ctx),
+    }
+    let (text, color) = match &app.status {
+        Status::Info(text) => (text.as_str(), Color::Gray),
+        Status::Error(text) => (text.as_str(), Color::Red),
+    };
+    frame.render_widget(
+        Paragraph::new(text).style(Style::new().fg(color)).block(Block::new().borders(Borders::TOP)),
+        status,
+    );
+}
+
+fn draw_entry(frame: &mut Frame, area: Rect, app: &App, ctx: &Context) {
+    let [header, lines, totals] =
+        Layout::vertical([Constraint::Length(4), Constraint::Min(5), Constraint::Length(3)]).areas(area);
+
+    let field = |label: &str, value: &str, focused: bool| {
+        let style = if focused { Style::new().reversed() } else { Style::new() };
+        Line::from(vec![Span::raw(format!("{:<10}", label)), Span::styled(format!(" {} ", value), style)])
+    };
+    let mut date = field("Ngày", &app.date, app.focus == Focus::Date);
+    if let Err(e) = app.date_check(&ctx.periods) {
+        date.push_span(Span::styled(format!("  ⚠ {}", e), Style::new().fg(Color::Red)));
+    }
+    let title = format!(" Chứng từ mới — {} ", ctx.company.as_ref().map_or("gl", |c| c.name.as_str()));
+    frame.render_widget(
+        Paragraph::new(vec![date, field("Diễn giải", &app.description, app.focus == Focus::Description)])
+            .block(Block::bordered().title(title)),
+        header,
+    );
+
+    let cell = |value: &str, focused: bool| {
+        let style = if focused { Style::new().reversed() } else { Style::new() };
+        Cell::from(Span::styled(value.to_string(), style))
+    };
+    let mut rows = vec![];
+    for (i, line) in app.lines.iter().enumerate() {
+        let name = ctx.coa.find(line.account.trim()).map(|a| a.name.clone()).unwrap_or_default();
+        rows.push(Row::new(vec![
+            Cell::from(format!("{}", i + 1)),
+            cell(&line.account, app.focus == Focus::Line(i, Column::Account)),
+            Cell::from(name).style(Style::new().fg(Color::Cyan)),
+            cell(&line.debit, app.focus == Focus::Line(i, Column::Debit)),
+            cell(&line.credit, app.focus == Focus::Line(i, Column::Credit)),
+        ]));
+        // Suggestions open under the account being typed
+        if app.focus == Focus::Line(i, Column::Account) {
+            for (n, account) in app.suggestions(&ctx.coa).iter().enumerate() {
+                let style = if n == app.selected { Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD) } else { Style::new().fg(Color::DarkGray) };
+                rows.push(Row::new(vec![
+                    Cell::from(""),
+                    Cell::from(format!("  {}", account.code)),
+                    Cell::from(account.name.clone()),
+                ])
+                .style(style));
+            }
+        }
+    }
+    let widths = [Constraint::Length(3), Constraint::Length(10), Constraint::Min(20), Constraint::Length(18), Constraint::Length(18)];
+    frame.render_widget(
+        Table::new(rows, widths)
+            .header(Row::new(vec!["#", "Tài khoản", "Tên tài khoản", "Nợ", "Có"]).bold())
+            .block(Block::bordered().title(" Định khoản ")),
+        lines,
+    );
+
+    let (debit, credit) = app.totals();
+    let difference = debit - credit;
+    let color = if difference == Decimal::ZERO && debit > Decimal::ZERO { Color::Green } else { Color::Red };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::raw(format!("Tổng Nợ: {}   Tổng Có: {}   ", amount(debit), amount(credit))),
+            Span::styled(format!("Chênh lệch: {}", amount(difference)), Style::new().fg(color).bold()),
+        ]))
+        .block(Block::bordered()),
+        totals,
+    );
+}
+
+fn draw_trial_balance(frame: &mut Frame, area: Rect, ctx: &Context) {
+    let tb = ctx.ledger.trial_balance();
+    let mut rows: Vec<Row> = tb
+        .balances
+        .iter()
+        .map(|b| {
+            let name = ctx.coa.find(b.account.as_str()).map(|a| a.name.clone()).unwrap_or_default();
+            Row::new(vec![
+                b.account.as_str().to_string(),
+                name,
+                amount(b.debit_balance),
+                amount(b.credit_balance),
+            ])
+        })
+        .collect();
+    rows.push(
+        Row::new(vec!["Tổng cộng".to_string(), String::new(), amount(tb.total_debit), amount(tb.total_credit)]).bold(),
+    );
+    let widths = [Constraint::Length(10), Constraint::Min(20), Constraint::Length(18), Constraint::Length(18)];
+    frame.render_widget(
+        Table::new(rows, widths)
+            .header(Row::new(vec!["Tài khoản", "Tên", "Nợ", "Có"]).bold())
+            .block(Block::bordered().title(" Bảng cân đối số phát sinh (F2: quay lại) ")),
+        area,
+    );
+}
@@ -0,0 +1,171 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use serde_json::{json, Value};
+
+/// A directory laid out as `gl` expects to be run from: `data/` with the
+/// chart of accounts, and no ledger yet.
+struct Books {
+    dir: PathBuf,
+}
+
+impl Books {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("gl-cli-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("data")).unwrap();
+        let coa = Path::new(env!("CARGO_MANIFEST_DIR")).join("../data/vn_coa.yaml");
+        std::fs::copy(coa, dir.join("data/vn_coa.yaml")).unwrap();
+        Books { dir }
+    }
+
+    fn gl(&self, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_gl"))
+            .args(args)
+            .current_dir(&self.dir)
+            .env_remove("GL_TOKEN")
+            .env("USER", "ketoan1")
+            .output()
+            .unwrap()
+    }
+
+    fn write(&self, name: &str, contents: &Value) -> String {
+        std::fs::write(self.dir.join(name), contents.to_string()).unwrap();
+        name.to_string()
+    }
+}
+
+impl Drop for Books {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn sale(date: &str, debit: i64, credit: i64) -> Value {
+    json!({
+        "date": date,
+        "lines": [
+            { "account": "1111", "debit": debit, "credit": 0 },
+            { "account": "5111", "debit": 0, "credit": credit }
+        ],
+        "description": "Bán hàng"
+    })
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+// --- CLI Tests ---
+#[test]
+fn test_ledger_init() {
+    let books = Books::new("init");
+    let output = books.gl(&["ledger", "init", "--year", "2025"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("periods 2025-01-01 → 2025-12-31 open"));
+    assert!(books.dir.join("data/ledger.json").exists());
+
+    let output = books.gl(&["--format", "json", "period", "list"]);
+    let periods: Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(periods.as_array().unwrap().len(), 12);
+
+    // A second init would overwrite the books
+    let output = books.gl(&["ledger", "init", "--year", "2025"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("already exists"));
+}
+
+#[test]
+fn test_entry_add() {
+    let books = Books::new("entry");
+    books.gl(&["ledger", "init", "--year", "2025"]);
+    let file = books.write("sale.json", &sale("2025-01-10", 1_000_000, 1_000_000));
+    let output = books.gl(&["entry", "add", &file]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Entry #1 posted"));
+
+    let unbalanced = books.write("unbalanced.json", &sale("2025-01-11", 1_000_000, 900_000));
+    let output = books.gl(&["entry", "add", &unbalanced]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains('❌'));
+    let outside = books.write("outside.json", &sale("2024-12-31", 500_000, 500_000));
+    let output = books.gl(&["entry", "add", &outside]);
+    assert_eq!(output.status.code(), Some(1));
+    let output = books.gl(&["entry", "add", "missing.json"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    // Only the valid entry reached the books
+    let output = books.gl(&["--format", "json", "entry", "list"]);
+    let entries: Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(entries.as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_report_tb_formats() {
+    let books = Books::new("tb");
+    books.gl(&["ledger", "init", "--year", "2025"]);
+    let file = books.write("sale.json", &sale("2025-01-10", 1_000_000, 1_000_000));
+    books.gl(&["entry", "add", &file]);
+
+    let output = books.gl(&["report", "tb", "--format", "csv"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let csv = stdout(&output);
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows[0], "Tài khoản,Tên,Nợ,Có");
+    assert!(rows.iter().any(|r| r.starts_with("1111,") && r.ends_with(",1000000.00,0.00")));
+    assert_eq!(*rows.last().unwrap(), "Tổng cộng,,1000000.00,1000000.00");
+
+    let output = books.gl(&["report", "tb", "--format", "json"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let tb: Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(tb["total_debit"], "1000000");
+    assert_eq!(tb["total_credit"], "1000000");
+    assert_eq!(tb["balances"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_audit_verify_against_the_ledger() {
+    let books = Books::new("audit");
+    books.gl(&["ledger", "init", "--year", "2025"]);
+    let file = books.write("sale.json", &sale("2025-01-10", 1_000_000, 1_000_000));
+    books.gl(&["entry", "add", &file]);
+    let output = books.gl(&["audit", "verify"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("intact: 13 events"));
+
+    // Dropping the last record leaves a valid chain, but not the one the ledger ends with
+    let trail = books.dir.join("data/audit_log.jsonl");
+    let contents = std::fs::read_to_string(&trail).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    std::fs::write(&trail, format!("{}\n", lines[..lines.len() - 1].join("\n"))).unwrap();
+    let output = books.gl(&["audit", "verify"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("events were deleted"));
+    assert_eq!(books.gl(&["report", "tb"]).status.code(), Some(1));
+
+    std::fs::remove_file(&trail).unwrap();
+    assert_eq!(books.gl(&["audit", "verify"]).status.code(), Some(1));
+    assert_eq!(books.gl(&["entry", "list"]).status.code(), Some(1));
+}
+
+#[test]
+fn test_entry_einvoice() {
+    let books = Books::new("einvoice");
+    books.gl(&["ledger", "init", "--year", "2025"]);
+    let xml = Path::new(env!("CARGO_MANIFEST_DIR")).join("../data/sample_einvoice.xml");
+    let xml = xml.to_str().unwrap();
+    let output = books.gl(&["--format", "json", "entry", "einvoice", xml, "--mst", "0309876543"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let posted: Value = serde_json::from_str(&stdout(&output)).unwrap();
+    let payable = posted["entry"]["lines"].as_array().unwrap().iter().find(|l| l["account"] == "331").unwrap();
+    assert_eq!(payable["reference"], "C25TAA-123");
+
+    let output = books.gl(&["entry", "einvoice", xml, "--mst", "0309876543"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("already imported"));
+    assert_eq!(books.gl(&["entry", "einvoice", xml]).status.code(), Some(1));
+}
@@ -0,0 +1,233 @@
+// gl-core/src/asset/depreciation.rs
+
+//! Depreciation amounts and their posting (Circular 45/2013).
+//!
+//! Depreciation runs by day: the first month counts from the day the asset
+//! was acquired, and a disposal stops it the day before. A full month of
+//! straight-line depreciation is cost / (years × 12). Declining balance
+//! charges a twelfth of the year's amount: the net book value at the start
+//! of the year of use times the straight-line rate times 1.5 (life up to 4
+//! years), 2 (up to 6) or 2.5 (longer), until that is no more than the net
+//! book value spread evenly over the years left. Amounts are rounded on the
+//! accumulated total, so the monthly charges add up to the cost exactly.
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use super::{AssetDisposal, DepreciationCharge, DepreciationMethod, FixedAsset, DISPOSAL_EXPENSE_ACCOUNT, DISPOSAL_INCOME_ACCOUNT};
+use crate::{
+    AccountCode, CoaConfig, EntryId, JournalEntry, JournalLine, Ledger, LedgerError, LedgerEvent, PeriodManager,
+    Permission, PrecisionPolicy, FUNCTIONAL_CURRENCY,
+};
+
+/// One month of an asset's depreciation schedule.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ScheduleLine {
+    /// Last day of the month.
+    pub month: NaiveDate,
+    pub charge: Decimal,
+    pub accumulated: Decimal,
+    pub net_book_value: Decimal,
+}
+
+fn month_end(date: NaiveDate) -> Option<NaiveDate> {
+    date.with_day(1)?.checked_add_months(Months::new(1))?.pred_opt()
+}
+
+/// Hệ số điều chỉnh of the declining-balance method.
+fn adjustment(years: u32) -> Decimal {
+    match years {
+        0..=4 => Decimal::new(15, 1),
+        5..=6 => Decimal::TWO,
+        _ => Decimal::new(25, 1),
+    }
+}
+
+/// Depreciation of a full month in month `n` of use, counting from 0.
+fn full_month(asset: &FixedAsset, n: u32) -> Decimal {
+    let years = asset.useful_life_years;
+    match asset.method {
+        DepreciationMethod::StraightLine => asset.cost / Decimal::from(years * 12),
+        DepreciationMethod::DecliningBalance => {
+            // Months past the useful life finish off what the first, partial month left
+            let year = (n / 12).min(years - 1);
+            let rate = adjustment(years) / Decimal::from(years);
+            let mut remaining = asset.cost;
+            let mut annual = Decimal::ZERO;
+            for y in 0..=year {
+                let declining = remaining * rate;
+                let even = remaining / Decimal::from(years - y);
+                annual = declining.max(even);
+                remaining -= annual;
+            }
+            annual / Decimal::from(12)
+        }
+    }
+}
+
+/// Depreciation of `asset` from its acquisition through `date`, rounded
+/// with `precision` and never more than the cost.
+pub fn accumulated_depreciation(asset: &FixedAsset, date: NaiveDate, precision: &PrecisionPolicy) -> Decimal {
+    let mut total = Decimal::ZERO;
+    let mut month = asset.acquired;
+    let mut n = 0;
+    while month <= date {
+        let Some(end) = month_end(month) else { break };
+        let days_in_use = (end.min(date) - month).num_days() + 1;
+        total += full_month(asset, n) * Decimal::from(days_in_use) / Decimal::from(end.day());
+        if total >= asset.cost {
+            return asset.cost;
+        }
+        let Some(next) = end.succ_opt() else { break };
+        month = next;
+        n += 1;
+    }
+    precision.round(FUNCTIONAL_CURRENCY, total).min(asset.cost)
+}
+
+/// Month by month depreciation of `asset` until it is fully depreciated.
+pub fn depreciation_schedule(asset: &FixedAsset, precision: &PrecisionPolicy) -> Vec<ScheduleLine> {
+    let mut lines = vec![];
+    let mut before = Decimal::ZERO;
+    let mut month = month_end(asset.acquired);
+    while let Some(end) = month.filter(|_| before < asset.cost) {
+        let accumulated = accumulated_depreciation(asset, end, precision);
+        lines.push(ScheduleLine {
+            month: end,
+            charge: accumulated - before,
+            accumulated,
+            net_book_value: asset.cost - accumulated,
+        });
+        before = accumulated;
+        month = end.succ_opt().and_then(month_end);
+    }
+    lines
+}
+
+fn line(account: &AccountCode, debit: Decimal, credit: Decimal) -> Result<JournalLine, LedgerError> {
+    JournalLine::new(account.clone(), debit, credit)
+}
+
+impl Ledger {
+    /// Posts the depreciation of the month containing `month` for every
+    /// asset in use: Nợ the expense account of each asset (642, 627), Có its
+    /// depreciation account (214). An asset whose earlier months were not
+    /// posted catches up. Returns `None` when nothing is due, also when the
+    /// month is already posted.
+    pub fn post_depreciation(
+        &mut self,
+        coa: &CoaConfig,
+        periods: &PeriodManager,
+        month: NaiveDate,
+    ) -> Result<Option<EntryId>, LedgerError> {
+        self.authorize(Permission::PostEntry)?;
+        let end = month_end(month).ok_or(LedgerError::InvalidDate)?;
+        let mut charges = vec![];
+        let mut debits: BTreeMap<AccountCode, Decimal> = BTreeMap::new();
+        let mut credits: BTreeMap<AccountCode, Decimal> = BTreeMap::new();
+        for record in &self.assets {
+            let asset = &record.asset;
+            if record.disposal.is_some() || asset.acquired > end || record.depreciated_through().is_some_and(|t| t >= end) {
+                continue;
+            }
+            let amount = accumulated_depreciation(asset, end, &self.precision) - record.accumulated_depreciation();
+            if amount <= Decimal::ZERO {
+                continue;
+            }
+            *debits.entry(asset.expense_account.clone()).or_default() += amount;
+            *credits.entry(asset.depreciation_account.clone()).or_default() += amount;
+            charges.push(DepreciationCharge { asset: asset.code.clone(), through: end, amount });
+        }
+        if charges.is_empty() {
+            return Ok(None);
+        }
+
+        let mut lines = vec![];
+        for (account, amount) in &debits {
+            lines.push(line(account, *amount, Decimal::ZERO)?);
+        }
+        for (account, amount) in &credits {
+            lines.push(line(account, Decimal::ZERO, *amount)?);
+        }
+        let entry = JournalEntry::new(end, lines, format!("Khấu hao TSCĐ tháng {}", end.format("%m/%Y")))?;
+        self.validate_entry(&entry, coa, periods)?;
+        let posted = self.next_entry(entry, None, None)?;
+        let id = posted.id;
+        info!("Posted depreciation of {} asset(s) for {} as entry {}", charges.len(), end.format("%m/%Y"), id);
+        self.emit(LedgerEvent::DepreciationPosted { entry: posted, charges });
+        Ok(Some(id))
+    }
+
+    /// Disposes of an asset on `date`: charges its depreciation up to the
+    /// day before, removes its cost and accumulated depreciation, books the
+    /// net book value to 811 and the proceeds, received on
+    /// `proceeds_account`, to 711. VAT on the sale is entered separately.
+    pub fn dispose_asset(
+        &mut self,
+        code: &str,
+        date: NaiveDate,
+        proceeds: Decimal,
+        proceeds_account: &AccountCode,
+        coa: &CoaConfig,
+        periods: &PeriodManager,
+    ) -> Result<EntryId, LedgerError> {
+        self.authorize(Permission::PostEntry)?;
+        let record = self.asset(code)?.clone();
+        let asset = &record.asset;
+        let invalid = |reason: String| LedgerError::InvalidAsset(format!("{}: {}", code, reason));
+        if let Some(disposal) = record.disposal() {
+            return Err(invalid(format!("already disposed of on {}", disposal.date)));
+        }
+        if date < asset.acquired {
+            return Err(invalid(format!("acquired on {}, after {}", asset.acquired, date)));
+        }
+        if proceeds < Decimal::ZERO {
+            return Err(LedgerError::NegativeAmount(proceeds));
+        }
+        let through = date.pred_opt().ok_or(LedgerError::InvalidDate)?;
+        if let Some(posted) = record.depreciated_through().filter(|t| *t > through) {
+            return Err(invalid(format!("depreciation is posted through {}; reverse it first", posted)));
+        }
+
+        let charged = record.accumulated_depreciation();
+        let charge = (accumulated_depreciation(asset, through, &self.precision) - charged).max(Decimal::ZERO);
+        let accumulated = charged + charge;
+        let net_book_value = asset.cost - accumulated;
+        let mut lines = vec![];
+        if charge > Decimal::ZERO {
+            lines.push(line(&asset.expense_account, charge, Decimal::ZERO)?);
+            lines.push(line(&asset.depreciation_account, Decimal::ZERO, charge)?);
+        }
+        if accumulated > Decimal::ZERO {
+            lines.push(line(&asset.depreciation_account, accumulated, Decimal::ZERO)?);
+        }
+        if net_book_value > Decimal::ZERO {
+            lines.push(line(&AccountCode(DISPOSAL_EXPENSE_ACCOUNT.to_string()), net_book_value, Decimal::ZERO)?);
+        }
+        lines.push(line(&asset.account, Decimal::ZERO, asset.cost)?);
+        if proceeds > Decimal::ZERO {
+            lines.push(line(proceeds_account, proceeds, Decimal::ZERO)?);
+            lines.push(line(&AccountCode(DISPOSAL_INCOME_ACCOUNT.to_string()), Decimal::ZERO, proceeds)?);
+        }
+        let entry = JournalEntry::new(date, lines, format!("Thanh lý TSCĐ {} - {}", asset.code, asset.name))?;
+        self.validate_entry(&entry, coa, periods)?;
+
+        let posted = self.next_entry(entry, None, None)?;
+        let id = posted.id;
+        let charge = (charge > Decimal::ZERO).then(|| DepreciationCharge { asset: code.to_string(), through, amount: charge });
+        let disposal = AssetDisposal {
+            asset: code.to_string(),
+            date,
+            proceeds,
+            accumulated_depreciation: accumulated,
+            net_book_value,
+        };
+        info!("Disposed of asset {} on {} as entry {}", code, date, id);
+        self.emit(LedgerEvent::AssetDisposed { entry: posted, disposal, charge });
+        Ok(id)
+    }
+}
@@ -0,0 +1,245 @@
+// gl-core/src/asset/mod.rs
+
+//! Fixed asset register (sổ tài sản cố định) and depreciation under
+//! Circular 45/2013/TT-BTC.
+//!
+//! An asset card records the cost, the date the asset was acquired, its
+//! useful life, which must fall within the range Appendix I of the circular
+//! gives its class, the depreciation method and the cost center using it.
+//! Cards, depreciation charges and disposals are ledger events, so the
+//! register is rebuilt with the books and a reversed depreciation or
+//! disposal entry takes its charges back off the cards.
+
+mod depreciation;
+mod report;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::{AccountCode, EntryId, Ledger, LedgerError, LedgerEvent, Permission};
+pub use depreciation::{accumulated_depreciation, depreciation_schedule, ScheduleLine};
+pub use report::{asset_movements, AssetMovement};
+
+/// Tài sản cố định hữu hình, the default asset account.
+pub const TANGIBLE_ASSET_ACCOUNT: &str = "211";
+/// Hao mòn tài sản cố định.
+pub const DEPRECIATION_ACCOUNT: &str = "214";
+/// Chi phí quản lý kinh doanh; production assets charge 627 instead.
+pub const DEPRECIATION_EXPENSE_ACCOUNT: &str = "642";
+/// Thu nhập khác, where disposal proceeds go.
+pub const DISPOSAL_INCOME_ACCOUNT: &str = "711";
+/// Chi phí khác, where the net book value of a disposed asset goes.
+pub const DISPOSAL_EXPENSE_ACCOUNT: &str = "811";
+
+/// A group of Appendix I of Circular 45/2013 and its useful life range.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AssetClass {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub min_years: u32,
+    pub max_years: u32,
+}
+
+const fn class(code: &'static str, name: &'static str, min_years: u32, max_years: u32) -> AssetClass {
+    AssetClass { code, name, min_years, max_years }
+}
+
+/// The groups of Appendix I most small companies use.
+pub const ASSET_CLASSES: &[AssetClass] = &[
+    class("A1", "Máy phát động lực", 8, 15),
+    class("A4", "Máy móc, thiết bị động lực khác", 6, 15),
+    class("B1", "Máy công cụ", 7, 15),
+    class("C1", "Thiết bị đo lường, thử nghiệm các đại lượng cơ học, âm học và nhiệt học", 5, 10),
+    class("D1", "Phương tiện vận tải đường bộ", 6, 10),
+    class("E1", "Thiết bị tính toán, đo lường", 5, 8),
+    class("E2", "Máy móc, thiết bị thông tin, điện tử và phần mềm tin học phục vụ quản lý", 3, 8),
+    class("E3", "Phương tiện và dụng cụ quản lý khác", 5, 10),
+    class("G1", "Nhà cửa loại kiên cố", 25, 50),
+    class("G2", "Nhà nghỉ giữa ca, nhà ăn giữa ca, nhà vệ sinh, nhà để xe", 6, 25),
+    class("G3", "Nhà cửa khác", 6, 25),
+    class("K", "Tài sản cố định vô hình khác", 2, 20),
+];
+
+pub fn asset_class(code: &str) -> Option<&'static AssetClass> {
+    ASSET_CLASSES.iter().find(|c| c.code == code)
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DepreciationMethod {
+    /// Đường thẳng: the cost spread evenly over the useful life.
+    #[default]
+    StraightLine,
+    /// Số dư giảm dần có điều chỉnh (Circular 45, Appendix II).
+    DecliningBalance,
+}
+
+fn default_asset_account() -> AccountCode {
+    AccountCode(TANGIBLE_ASSET_ACCOUNT.to_string())
+}
+
+fn default_depreciation_account() -> AccountCode {
+    AccountCode(DEPRECIATION_ACCOUNT.to_string())
+}
+
+fn default_expense_account() -> AccountCode {
+    AccountCode(DEPRECIATION_EXPENSE_ACCOUNT.to_string())
+}
+
+/// Thẻ tài sản cố định.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FixedAsset {
+    pub code: String,
+    pub name: String,
+    /// Group of Appendix I of Circular 45/2013, e.g. `E2`.
+    pub class: String,
+    pub cost: Decimal,
+    /// Depreciation starts on this day.
+    pub acquired: NaiveDate,
+    pub useful_life_years: u32,
+    #[serde(default)]
+    pub method: DepreciationMethod,
+    /// Bộ phận sử dụng, e.g. `VP` or `PX1`.
+    #[serde(default)]
+    pub cost_center: String,
+    #[serde(default = "default_asset_account")]
+    pub account: AccountCode,
+    #[serde(default = "default_depreciation_account")]
+    pub depreciation_account: AccountCode,
+    /// 642 for administration, 627 for a production cost center.
+    #[serde(default = "default_expense_account")]
+    pub expense_account: AccountCode,
+}
+
+impl FixedAsset {
+    fn check(&self) -> Result<(), LedgerError> {
+        let invalid = |reason: String| LedgerError::InvalidAsset(format!("{}: {}", self.code, reason));
+        if self.code.trim().is_empty() {
+            return Err(LedgerError::InvalidAsset("asset code is empty".to_string()));
+        }
+        if self.cost <= Decimal::ZERO {
+            return Err(invalid(format!("cost {} is not positive", self.cost)));
+        }
+        let class = asset_class(&self.class).ok_or_else(|| invalid(format!("unknown asset class {}", self.class)))?;
+        if !(class.min_years..=class.max_years).contains(&self.useful_life_years) {
+            return Err(LedgerError::UsefulLifeOutOfRange(
+                self.code.clone(),
+                self.useful_life_years,
+                class.min_years,
+                class.max_years,
+            ));
+        }
+        for account in [&self.account, &self.depreciation_account, &self.expense_account] {
+            AccountCode::new(account.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// Depreciation charged to an asset by one entry.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DepreciationCharge {
+    pub asset: String,
+    /// Last day the charge covers.
+    pub through: NaiveDate,
+    pub amount: Decimal,
+}
+
+/// Thanh lý, nhượng bán: how an asset left the books.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AssetDisposal {
+    pub asset: String,
+    pub date: NaiveDate,
+    /// Booked to 711.
+    pub proceeds: Decimal,
+    /// Depreciation as of the day before the disposal.
+    pub accumulated_depreciation: Decimal,
+    /// Booked to 811.
+    pub net_book_value: Decimal,
+}
+
+/// An asset card with what has been charged to it and its disposal.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AssetRecord {
+    pub asset: FixedAsset,
+    /// Charges with the entry that posted them, oldest first.
+    pub charges: Vec<(EntryId, DepreciationCharge)>,
+    pub disposal: Option<(EntryId, AssetDisposal)>,
+}
+
+impl AssetRecord {
+    /// Depreciation charged so far (hao mòn lũy kế).
+    pub fn accumulated_depreciation(&self) -> Decimal {
+        self.charges.iter().map(|(_, c)| c.amount).sum()
+    }
+
+    /// Depreciation charged through `date`.
+    pub fn accumulated_through(&self, date: NaiveDate) -> Decimal {
+        self.charges.iter().filter(|(_, c)| c.through <= date).map(|(_, c)| c.amount).sum()
+    }
+
+    pub fn net_book_value(&self) -> Decimal {
+        self.asset.cost - self.accumulated_depreciation()
+    }
+
+    /// Last day depreciation is charged through.
+    pub fn depreciated_through(&self) -> Option<NaiveDate> {
+        self.charges.iter().map(|(_, c)| c.through).max()
+    }
+
+    pub fn disposal(&self) -> Option<&AssetDisposal> {
+        self.disposal.as_ref().map(|(_, d)| d)
+    }
+}
+
+impl Ledger {
+    /// The asset register, in the order the cards were added.
+    pub fn assets(&self) -> &[AssetRecord] {
+        &self.assets
+    }
+
+    pub fn asset(&self, code: &str) -> Result<&AssetRecord, LedgerError> {
+        self.assets.iter().find(|r| r.asset.code == code).ok_or_else(|| LedgerError::UnknownAsset(code.to_string()))
+    }
+
+    /// Adds an asset card to the register. The purchase itself is an
+    /// ordinary entry (Nợ 211 Có 331/112); the card drives depreciation.
+    pub fn register_asset(&mut self, asset: FixedAsset) -> Result<(), LedgerError> {
+        self.authorize(Permission::PostEntry)?;
+        asset.check()?;
+        if self.assets.iter().any(|r| r.asset.code == asset.code) {
+            return Err(LedgerError::DuplicateAsset(asset.code));
+        }
+        self.emit(LedgerEvent::AssetRegistered { asset });
+        Ok(())
+    }
+
+    pub(crate) fn apply_asset_registered(&mut self, asset: &FixedAsset) {
+        self.assets.push(AssetRecord { asset: asset.clone(), charges: vec![], disposal: None });
+    }
+
+    pub(crate) fn apply_depreciation(&mut self, entry_id: EntryId, charges: &[DepreciationCharge]) {
+        for charge in charges {
+            if let Some(record) = self.assets.iter_mut().find(|r| r.asset.code == charge.asset) {
+                record.charges.push((entry_id, charge.clone()));
+            }
+        }
+    }
+
+    pub(crate) fn apply_disposal(&mut self, entry_id: EntryId, disposal: &AssetDisposal) {
+        if let Some(record) = self.assets.iter_mut().find(|r| r.asset.code == disposal.asset) {
+            record.disposal = Some((entry_id, disposal.clone()));
+        }
+    }
+
+    /// Takes the charges and disposal of a reversed entry off the cards.
+    pub(crate) fn unapply_asset_entry(&mut self, entry_id: EntryId) {
+        for record in self.assets.iter_mut() {
+            record.charges.retain(|(id, _)| *id != entry_id);
+            if record.disposal.as_ref().is_some_and(|(id, _)| *id == entry_id) {
+                record.disposal = None;
+            }
+        }
+    }
+}
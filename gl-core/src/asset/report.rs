@@ -0,0 +1,74 @@
+// gl-core/src/asset/report.rs
+
+//! Báo cáo tình hình tăng giảm tài sản cố định: cost and accumulated
+//! depreciation of each asset at the start of a period, what the period
+//! added and removed, and where they stand at its end.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::AssetRecord;
+use crate::Ledger;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AssetMovement {
+    pub code: String,
+    pub name: String,
+    pub cost_center: String,
+    pub opening_cost: Decimal,
+    /// Cost of assets acquired in the period.
+    pub additions: Decimal,
+    /// Cost of assets disposed of in the period.
+    pub disposals: Decimal,
+    pub closing_cost: Decimal,
+    pub opening_depreciation: Decimal,
+    /// Depreciation charged in the period.
+    pub depreciation: Decimal,
+    /// Accumulated depreciation of the assets disposed of.
+    pub depreciation_disposed: Decimal,
+    pub closing_depreciation: Decimal,
+    pub net_book_value: Decimal,
+}
+
+/// Whether the asset is on the books at the end of `date`.
+fn in_books(record: &AssetRecord, date: NaiveDate) -> bool {
+    record.asset.acquired <= date && record.disposal().is_none_or(|d| d.date > date)
+}
+
+/// Movements of every asset on the books at some point from `from` to `to`.
+pub fn asset_movements(ledger: &Ledger, from: NaiveDate, to: NaiveDate) -> Vec<AssetMovement> {
+    let before = from.pred_opt().unwrap_or(from);
+    let within = |date: NaiveDate| date >= from && date <= to;
+    ledger
+        .assets()
+        .iter()
+        .filter(|r| r.asset.acquired <= to && r.disposal().is_none_or(|d| d.date >= from))
+        .map(|record| {
+            let cost = record.asset.cost;
+            let held_before = in_books(record, before);
+            let disposed = record.disposal().is_some_and(|d| within(d.date));
+            let opening_depreciation =
+                if held_before { record.accumulated_through(before) } else { Decimal::ZERO };
+            let depreciation: Decimal =
+                record.charges.iter().filter(|(_, c)| within(c.through)).map(|(_, c)| c.amount).sum();
+            let depreciation_disposed = if disposed { record.accumulated_depreciation() } else { Decimal::ZERO };
+            let closing_cost = if in_books(record, to) { cost } else { Decimal::ZERO };
+            let closing_depreciation = opening_depreciation + depreciation - depreciation_disposed;
+            AssetMovement {
+                code: record.asset.code.clone(),
+                name: record.asset.name.clone(),
+                cost_center: record.asset.cost_center.clone(),
+                opening_cost: if held_before { cost } else { Decimal::ZERO },
+                additions: if within(record.asset.acquired) { cost } else { Decimal::ZERO },
+                disposals: if disposed { cost } else { Decimal::ZERO },
+                closing_cost,
+                opening_depreciation,
+                depreciation,
+                depreciation_disposed,
+                closing_depreciation,
+                net_book_value: closing_cost - closing_depreciation,
+            }
+        })
+        .collect()
+}
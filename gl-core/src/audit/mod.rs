@@ -0,0 +1,247 @@
+// gl-core/src/audit/mod.rs
+
+//! Append-only, hash-chained audit trail.
+//!
+//! Every event stores the SHA-256 of the previous event, and its own hash
+//! covers its content plus that link. Editing an event breaks its own hash;
+//! deleting or reordering events breaks the sequence or the chain. Dropping
+//! events from the tail, or the whole trail, can only be detected against a
+//! head kept elsewhere: the ledger file stores the `AuditHead` of its trail,
+//! and a trail is only attached to a ledger if it ends exactly there.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::LedgerError;
+
+/// Hash of the (virtual) event before the first one.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum AuditAction {
+    EntryPosted,
+    EntryReversed,
+    PeriodOpened,
+    PeriodClosed,
+    AccountAdded,
+    AccountUpdated,
+    AccountRemoved,
+    CustomerAdded,
+    CustomerUpdated,
+    VendorAdded,
+    VendorUpdated,
+    VendorInvoiceRecorded,
+    VendorPaymentRecorded,
+    ItemsMatched,
+    ItemsUnmatched,
+    ExchangeRateSet,
+    PermissionDenied,
+    UsersChanged,
+    DraftSaved,
+    DraftSubmitted,
+    DraftApproved,
+    DraftRejected,
+    DraftPosted,
+    RecurringEntryPosted,
+    AssetRegistered,
+    DepreciationPosted,
+    AssetDisposed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AuditEvent {
+    pub sequence: u64,
+    pub timestamp: DateTime<Utc>,
+    pub actor: String,
+    pub action: AuditAction,
+    pub payload: serde_json::Value,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+/// The hashed part of an event; field order is part of the format.
+#[derive(Serialize)]
+struct HashedContent<'a> {
+    sequence: u64,
+    timestamp: &'a DateTime<Utc>,
+    actor: &'a str,
+    action: &'a AuditAction,
+    payload: &'a serde_json::Value,
+    prev_hash: &'a str,
+}
+
+impl AuditEvent {
+    pub fn compute_hash(&self) -> String {
+        let content = HashedContent {
+            sequence: self.sequence,
+            timestamp: &self.timestamp,
+            actor: &self.actor,
+            action: &self.action,
+            payload: &self.payload,
+            prev_hash: &self.prev_hash,
+        };
+        let bytes = serde_json::to_vec(&content).expect("audit event is serializable");
+        let digest = Sha256::digest(&bytes);
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+/// Where a trail ends: its number of events and the hash of the last one.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AuditHead {
+    pub events: u64,
+    pub hash: String,
+}
+
+impl Default for AuditHead {
+    fn default() -> Self {
+        AuditHead { events: 0, hash: GENESIS_HASH.to_string() }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct AuditLog {
+    events: Vec<AuditEvent>,
+    /// The events before the first one held, when the log continues a
+    /// trail that was not loaded.
+    #[serde(skip)]
+    start: AuditHead,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        AuditLog { events: vec![], start: AuditHead::default() }
+    }
+
+    /// An empty log continuing a trail that ends at `head`; what a ledger
+    /// read without its trail records into.
+    pub fn continuing(head: AuditHead) -> Self {
+        AuditLog { events: vec![], start: head }
+    }
+
+    pub fn events(&self) -> &[AuditEvent] {
+        &self.events
+    }
+
+    /// Hash of the last event, or the genesis hash for an empty log.
+    pub fn head_hash(&self) -> &str {
+        self.events.last().map_or(self.start.hash.as_str(), |e| e.hash.as_str())
+    }
+
+    pub fn head(&self) -> AuditHead {
+        AuditHead { events: self.start.events + self.events.len() as u64, hash: self.head_hash().to_string() }
+    }
+
+    pub fn record<T: Serialize>(&mut self, actor: &str, action: AuditAction, payload: &T) -> &AuditEvent {
+        let mut event = AuditEvent {
+            sequence: self.head().events + 1,
+            timestamp: Utc::now(),
+            actor: actor.to_string(),
+            action,
+            payload: serde_json::to_value(payload).expect("audit payload is serializable"),
+            prev_hash: self.head_hash().to_string(),
+            hash: String::new(),
+        };
+        event.hash = event.compute_hash();
+        self.events.push(event);
+        self.events.last().expect("event was just pushed")
+    }
+
+    /// Checks sequence numbers, links and hashes; returns the head hash.
+    pub fn verify(&self) -> Result<String, LedgerError> {
+        let mut prev_hash = self.start.hash.clone();
+        for (i, event) in self.events.iter().enumerate() {
+            let expected = self.start.events + i as u64 + 1;
+            if event.sequence != expected {
+                return Err(LedgerError::AuditChainBroken(
+                    expected,
+                    format!("found sequence {}, a record was deleted or reordered", event.sequence),
+                ));
+            }
+            if event.prev_hash != prev_hash {
+                return Err(LedgerError::AuditChainBroken(
+                    expected,
+                    "previous hash does not match, the chain was altered".to_string(),
+                ));
+            }
+            if event.compute_hash() != event.hash {
+                return Err(LedgerError::AuditChainBroken(
+                    expected,
+                    "content does not match its hash, the record was edited".to_string(),
+                ));
+            }
+            prev_hash = event.hash.clone();
+        }
+        Ok(prev_hash)
+    }
+
+    /// Verifies the chain and that it ends exactly at `expected`, the head
+    /// recorded by the ledger; returns the head hash.
+    pub fn check_head(&self, expected: &AuditHead) -> Result<String, LedgerError> {
+        let hash = self.verify()?;
+        let head = self.head();
+        if head.events < expected.events {
+            return Err(LedgerError::AuditChainBroken(
+                head.events + 1,
+                format!(
+                    "the trail ends at event {} but the ledger records {}, events were deleted",
+                    head.events, expected.events
+                ),
+            ));
+        }
+        if head.events > expected.events {
+            return Err(LedgerError::AuditChainBroken(
+                expected.events + 1,
+                format!("the ledger records only {} events, it is older than its trail", expected.events),
+            ));
+        }
+        if head.hash != expected.hash {
+            return Err(LedgerError::AuditChainBroken(
+                head.events,
+                "the last event is not the one the ledger records, the trail was replaced".to_string(),
+            ));
+        }
+        Ok(hash)
+    }
+
+    /// Reads a JSONL audit file (one event per line). A missing file is an empty log.
+    pub fn load(path: &Path) -> Result<Self, LedgerError> {
+        if !path.exists() {
+            return Ok(AuditLog::new());
+        }
+        let file = File::open(path).map_err(|e| LedgerError::AuditIo(e.to_string()))?;
+        let mut events = Vec::new();
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|e| LedgerError::AuditIo(e.to_string()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = serde_json::from_str(&line)
+                .map_err(|e| LedgerError::AuditIo(format!("line {}: {}", i + 1, e)))?;
+            events.push(event);
+        }
+        Ok(AuditLog { events, start: AuditHead::default() })
+    }
+
+    /// Appends the events after the first `already_written` to a JSONL file.
+    pub fn append_to(&self, path: &Path, already_written: usize) -> Result<(), LedgerError> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| LedgerError::AuditIo(e.to_string()))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| LedgerError::AuditIo(e.to_string()))?;
+        for event in self.events.iter().skip(already_written) {
+            let line = serde_json::to_string(event).map_err(|e| LedgerError::AuditIo(e.to_string()))?;
+            writeln!(file, "{}", line).map_err(|e| LedgerError::AuditIo(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,200 @@
+// gl-core/src/auth/mod.rs
+
+//! Users, roles and permissions, for separation of duties in the firm.
+//!
+//! A ledger given a user directory checks the permission of its actor
+//! before posting, preparing drafts, reversing, approving drafts, opening or closing periods
+//! and editing the chart of accounts; matching open items and setting
+//! exchange rates take the permission to post. Refusals are recorded in
+//! the audit trail like any other action. The directory is stored with the ledger, so once a ledger
+//! has users it stays protected; only a user who may manage users can
+//! replace them. Without a directory every actor may do everything, as
+//! before.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{AuditAction, Ledger, LedgerError};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Permission {
+    /// Posting entries straight to the books, without review.
+    PostEntry,
+    /// Saving, editing and submitting drafts, and posting them once they
+    /// are approved (see the `workflow` module).
+    PrepareEntry,
+    ReverseEntry,
+    /// Opening and closing accounting periods.
+    ManagePeriods,
+    EditCoa,
+    /// Adding and updating customers and vendors.
+    ManageMasters,
+    /// Approving or rejecting submitted drafts (see the `workflow` module).
+    ApproveEntry,
+    /// Replacing the user directory of a ledger.
+    ManageUsers,
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Permission::PostEntry => "post entries",
+            Permission::PrepareEntry => "prepare draft entries",
+            Permission::ReverseEntry => "reverse entries",
+            Permission::ManagePeriods => "open or close periods",
+            Permission::EditCoa => "edit the chart of accounts",
+            Permission::ManageMasters => "manage customers and vendors",
+            Permission::ApproveEntry => "approve entries",
+            Permission::ManageUsers => "manage users",
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// Kế toán viên: prepares the day-to-day entries as drafts, which
+    /// reach the books once they are approved.
+    Clerk,
+    /// Kế toán trưởng: reviews and corrects entries, closes periods, owns
+    /// the chart of accounts and the customer and vendor masters.
+    ChiefAccountant,
+    /// Giám đốc: reads the books and approves entries.
+    Director,
+    /// Kiểm toán: read-only.
+    Auditor,
+}
+
+impl Role {
+    pub fn permissions(self) -> &'static [Permission] {
+        match self {
+            Role::Clerk => &[Permission::PrepareEntry],
+            Role::ChiefAccountant => &[
+                Permission::PostEntry,
+                Permission::PrepareEntry,
+                Permission::ReverseEntry,
+                Permission::ManagePeriods,
+                Permission::EditCoa,
+                Permission::ManageMasters,
+                Permission::ApproveEntry,
+                Permission::ManageUsers,
+            ],
+            Role::Director => &[Permission::ApproveEntry],
+            Role::Auditor => &[],
+        }
+    }
+
+    pub fn allows(self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct User {
+    /// Login name, recorded as the actor in the audit trail.
+    pub username: String,
+    #[serde(default)]
+    pub name: String,
+    pub roles: Vec<Role>,
+    /// SHA-256 (hex) of the user's API token; see `hash_token`.
+    #[serde(default)]
+    pub token_sha256: Option<String>,
+}
+
+impl User {
+    pub fn can(&self, permission: Permission) -> bool {
+        self.roles.iter().any(|r| r.allows(permission))
+    }
+}
+
+/// Hex SHA-256 of a token, as stored in `User::token_sha256`.
+pub fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct UserDirectory {
+    pub users: Vec<User>,
+}
+
+impl UserDirectory {
+    pub fn new() -> Self {
+        UserDirectory { users: vec![] }
+    }
+
+    /// Reads `users:` from YAML; usernames must be unique.
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let directory: UserDirectory = serde_yaml::from_str(contents)?;
+        for (i, user) in directory.users.iter().enumerate() {
+            let name_ok = !user.username.is_empty()
+                && user.username.chars().all(|c| c.is_ascii_alphanumeric() || "._-".contains(c));
+            if !name_ok {
+                return Err(Box::new(LedgerError::InvalidUser(format!(
+                    "username '{}' must be letters, digits, '.', '_' or '-'",
+                    user.username
+                ))));
+            }
+            if directory.users[..i].iter().any(|u| u.username == user.username) {
+                return Err(Box::new(LedgerError::InvalidUser(format!("{} is listed twice", user.username))));
+            }
+        }
+        Ok(directory)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+
+    pub fn get(&self, username: &str) -> Option<&User> {
+        self.users.iter().find(|u| u.username == username)
+    }
+
+    /// The user whose token this is.
+    pub fn authenticate(&self, token: &str) -> Option<&User> {
+        let hash = hash_token(token);
+        self.users.iter().find(|u| u.token_sha256.as_deref() == Some(hash.as_str()))
+    }
+}
+
+impl Ledger {
+    /// Enforces the roles of a user directory on the ledger's actor.
+    pub fn with_users(mut self, users: UserDirectory) -> Self {
+        self.users = users;
+        self
+    }
+
+    /// Replaces the user directory; once the ledger has users, only an
+    /// actor who may manage users can do so.
+    pub fn set_users(&mut self, users: UserDirectory) -> Result<(), LedgerError> {
+        self.authorize(Permission::ManageUsers)?;
+        let usernames: Vec<&str> = users.users.iter().map(|u| u.username.as_str()).collect();
+        self.audit(AuditAction::UsersChanged, &serde_json::json!({ "users": usernames }));
+        self.users = users;
+        Ok(())
+    }
+
+    pub fn users(&self) -> &UserDirectory {
+        &self.users
+    }
+
+    /// Whether the actor may do `permission`; anyone may without users.
+    pub fn may(&self, permission: Permission) -> bool {
+        self.users.is_empty() || self.users.get(self.actor()).is_some_and(|u| u.can(permission))
+    }
+
+    /// Checks that the actor may do `permission`; a refusal is audited.
+    pub(crate) fn authorize(&mut self, permission: Permission) -> Result<(), LedgerError> {
+        if self.users.is_empty() {
+            return Ok(());
+        }
+        let actor = self.actor().to_string();
+        let error = match self.users.get(&actor) {
+            Some(user) if user.can(permission) => return Ok(()),
+            Some(_) => LedgerError::PermissionDenied(actor, permission),
+            None => LedgerError::UnknownUser(actor),
+        };
+        self.audit(AuditAction::PermissionDenied, &serde_json::json!({ "permission": permission }));
+        Err(error)
+    }
+}
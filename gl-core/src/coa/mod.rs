@@ -4,7 +4,8 @@ use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::Read;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Account {
     pub code: String,
     pub name: String,
@@ -18,8 +19,27 @@ pub struct CoaConfig {
     pub accounts: Vec<Account>,
 }
 
+impl CoaConfig {
+    /// Finds the account itself or, for sub-accounts like 6421, its nearest parent.
+    pub fn find(&self, code: &str) -> Option<&Account> {
+        self.accounts
+            .iter()
+            .filter(|a| code.starts_with(a.code.as_str()))
+            .max_by_key(|a| a.code.len())
+    }
+
+    pub fn contains(&self, code: &str) -> bool {
+        self.find(code).is_some()
+    }
+}
+
+/// The bundled Circular 133 chart of accounts.
 pub fn load_vn_coa() -> Result<CoaConfig, Box<dyn std::error::Error>> {
-    let mut file = File::open("data/vn_coa.yaml")?;
+    load_coa("data/vn_coa.yaml")
+}
+
+pub fn load_coa(path: &str) -> Result<CoaConfig, Box<dyn std::error::Error>> {
+    let mut file = File::open(path)?;
     let mut contents = String::new();
     file.read_to_string(&mut contents)?;
     let coa: CoaConfig = serde_yaml::from_str(&contents)?;
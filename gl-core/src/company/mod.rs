@@ -0,0 +1,281 @@
+// gl-core/src/company/mod.rs
+
+//! Companies (tenants) of a multi-client workspace, as kept by an
+//! accounting firm. Each company owns its ledger, periods and chart of
+//! accounts; a ledger is tagged with its company when created and is
+//! refused by any other, so entries cannot cross from one set of books to
+//! another.
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::{load_coa, load_vn_coa, CoaConfig, CompanyInfo, DraftId, EntryId, JournalEntry, Ledger, LedgerError, Period, PeriodManager};
+
+/// Accounting regime, which decides the chart of accounts and statements.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum CoaRegime {
+    /// Circular 133/2016/TT-BTC for small and medium enterprises.
+    #[default]
+    Circular133,
+    /// Circular 200/2014/TT-BTC for all enterprises.
+    Circular200,
+}
+
+/// Inventory accounting method (phương pháp kế toán hàng tồn kho).
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum AccountingMethod {
+    /// Kê khai thường xuyên: inventory accounts move with every receipt and issue.
+    #[default]
+    Perpetual,
+    /// Kiểm kê định kỳ: inventory is counted and adjusted at period end.
+    Periodic,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Company {
+    /// Short code used to select the company, e.g. `phuthanh`.
+    pub id: String,
+    pub name: String,
+    /// Mã số thuế: 10 digits, or 10-3 for a branch.
+    pub tax_code: String,
+    #[serde(default)]
+    pub address: String,
+    /// Month the fiscal year starts in (1 for a calendar year).
+    #[serde(default = "default_fiscal_year_start")]
+    pub fiscal_year_start: u32,
+    #[serde(default)]
+    pub coa_regime: CoaRegime,
+    #[serde(default)]
+    pub accounting_method: AccountingMethod,
+    /// Chart of accounts file; the bundled Circular 133 chart when absent.
+    #[serde(default)]
+    pub coa_file: Option<String>,
+}
+
+fn default_fiscal_year_start() -> u32 {
+    1
+}
+
+impl Company {
+    pub fn check(&self) -> Result<(), LedgerError> {
+        let id_ok = !self.id.is_empty()
+            && self.id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !id_ok {
+            return Err(LedgerError::InvalidCompany(format!(
+                "id '{}' must be letters, digits, '-' or '_'",
+                self.id
+            )));
+        }
+        if self.name.trim().is_empty() {
+            return Err(LedgerError::InvalidCompany(format!("{} has no name", self.id)));
+        }
+        let digits = |s: &str, n: usize| s.len() == n && s.chars().all(|c| c.is_ascii_digit());
+        let tax_code_ok = match self.tax_code.split_once('-') {
+            Some((main, branch)) => digits(main, 10) && digits(branch, 3),
+            None => digits(&self.tax_code, 10),
+        };
+        if !tax_code_ok {
+            return Err(LedgerError::InvalidCompany(format!(
+                "MST '{}' of {} must be 10 digits or 10-3 digits",
+                self.tax_code, self.id
+            )));
+        }
+        if !(1..=12).contains(&self.fiscal_year_start) {
+            return Err(LedgerError::InvalidCompany(format!(
+                "fiscal year of {} cannot start in month {}",
+                self.id, self.fiscal_year_start
+            )));
+        }
+        Ok(())
+    }
+
+    /// The fiscal year containing `date`.
+    pub fn fiscal_year_of(&self, date: NaiveDate) -> Result<Period, LedgerError> {
+        let year = if date.month() >= self.fiscal_year_start { date.year() } else { date.year() - 1 };
+        self.fiscal_year(year)
+    }
+
+    /// The fiscal year starting in calendar year `year`.
+    pub fn fiscal_year(&self, year: i32) -> Result<Period, LedgerError> {
+        let start = NaiveDate::from_ymd_opt(year, self.fiscal_year_start, 1).ok_or(LedgerError::InvalidDate)?;
+        let end = start
+            .checked_add_months(Months::new(12))
+            .and_then(|d| d.pred_opt())
+            .ok_or(LedgerError::InvalidDate)?;
+        Ok(Period::new(start, end))
+    }
+
+    /// The company's chart of accounts: `coa_file`, or the bundled chart of
+    /// its regime.
+    pub fn load_coa(&self) -> Result<CoaConfig, Box<dyn std::error::Error>> {
+        match (&self.coa_file, self.coa_regime) {
+            (Some(path), _) => load_coa(path),
+            (None, CoaRegime::Circular133) => load_vn_coa(),
+            (None, CoaRegime::Circular200) => Err(Box::new(LedgerError::InvalidCompany(format!(
+                "no Circular 200 chart is bundled; set coa_file for {}",
+                self.id
+            )))),
+        }
+    }
+
+    /// Header of the company's vouchers and documents.
+    pub fn info(&self) -> CompanyInfo {
+        CompanyInfo { name: self.name.clone(), address: self.address.clone() }
+    }
+}
+
+/// The companies served, as read from `companies.yaml`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct CompanyRegistry {
+    pub companies: Vec<Company>,
+}
+
+impl CompanyRegistry {
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let registry: CompanyRegistry = serde_yaml::from_str(contents)?;
+        let mut seen = Vec::new();
+        for company in &registry.companies {
+            company.check()?;
+            if seen.contains(&&company.id) {
+                return Err(Box::new(LedgerError::DuplicateCompany(company.id.clone())));
+            }
+            seen.push(&company.id);
+        }
+        Ok(registry)
+    }
+
+    pub fn get(&self, id: &str) -> Result<&Company, LedgerError> {
+        self.companies
+            .iter()
+            .find(|c| c.id == id)
+            .ok_or_else(|| LedgerError::UnknownCompany(id.to_string()))
+    }
+}
+
+/// One company's books: its ledger, periods and chart of accounts.
+pub struct CompanyBooks {
+    company: Company,
+    ledger: Ledger,
+    periods: PeriodManager,
+    coa: CoaConfig,
+}
+
+impl CompanyBooks {
+    /// Opens empty books for a company.
+    pub fn new(company: Company, coa: CoaConfig) -> Result<Self, LedgerError> {
+        Self::with_ledger(company, coa, Ledger::new())
+    }
+
+    /// Opens a company's books on an existing ledger, which must be the
+    /// company's own (or not yet tagged with any company). The periods are
+    /// those opened and closed in the ledger.
+    pub fn with_ledger(company: Company, coa: CoaConfig, ledger: Ledger) -> Result<Self, LedgerError> {
+        company.check()?;
+        let ledger = ledger.for_company(&company.id)?;
+        let periods = ledger.period_manager();
+        Ok(CompanyBooks { company, ledger, periods, coa })
+    }
+
+    pub fn company(&self) -> &Company {
+        &self.company
+    }
+
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    pub fn ledger_mut(&mut self) -> &mut Ledger {
+        &mut self.ledger
+    }
+
+    pub fn periods(&self) -> &PeriodManager {
+        &self.periods
+    }
+
+    pub fn coa(&self) -> &CoaConfig {
+        &self.coa
+    }
+
+    pub fn open_period(&mut self, period: Period) -> Result<(), LedgerError> {
+        if period.end_date < period.start_date {
+            return Err(LedgerError::InvalidDate);
+        }
+        self.ledger.open_period(&mut self.periods, period)
+    }
+
+    pub fn close_current_period(&mut self) -> Result<(), LedgerError> {
+        self.ledger.close_current_period(&mut self.periods)
+    }
+
+    /// Opens the twelve monthly periods of the fiscal year starting in `year`.
+    pub fn open_fiscal_year(&mut self, year: i32) -> Result<Period, LedgerError> {
+        let fiscal_year = self.company.fiscal_year(year)?;
+        for month in fiscal_year.months() {
+            self.open_period(month)?;
+        }
+        Ok(fiscal_year)
+    }
+
+    /// Posts an entry after checking it against this company's chart of
+    /// accounts and open periods.
+    pub fn post_entry(&mut self, entry: JournalEntry) -> Result<EntryId, LedgerError> {
+        self.ledger.validate_entry(&entry, &self.coa, &self.periods)?;
+        self.ledger.post_entry(entry)
+    }
+
+    /// Posts an approved draft, checked like `post_entry`.
+    pub fn post_draft(&mut self, id: DraftId) -> Result<EntryId, LedgerError> {
+        self.ledger.post_draft(id, &self.coa, &self.periods)
+    }
+
+    /// Gives back the ledger, e.g. to store it.
+    pub fn into_ledger(self) -> Ledger {
+        self.ledger
+    }
+}
+
+/// The books of every company served, kept apart by company id.
+#[derive(Default)]
+pub struct Workspace {
+    books: BTreeMap<String, CompanyBooks>,
+}
+
+impl Workspace {
+    pub fn new() -> Self {
+        Workspace { books: BTreeMap::new() }
+    }
+
+    pub fn add_company(&mut self, company: Company, coa: CoaConfig) -> Result<&mut CompanyBooks, LedgerError> {
+        self.insert(CompanyBooks::new(company, coa)?)
+    }
+
+    pub fn insert(&mut self, books: CompanyBooks) -> Result<&mut CompanyBooks, LedgerError> {
+        let id = books.company.id.clone();
+        if self.books.contains_key(&id) {
+            return Err(LedgerError::DuplicateCompany(id));
+        }
+        Ok(self.books.entry(id).or_insert(books))
+    }
+
+    pub fn companies(&self) -> impl Iterator<Item = &Company> {
+        self.books.values().map(|b| &b.company)
+    }
+
+    pub fn books(&self, id: &str) -> Result<&CompanyBooks, LedgerError> {
+        self.books.get(id).ok_or_else(|| LedgerError::UnknownCompany(id.to_string()))
+    }
+
+    pub fn books_mut(&mut self, id: &str) -> Result<&mut CompanyBooks, LedgerError> {
+        self.books.get_mut(id).ok_or_else(|| LedgerError::UnknownCompany(id.to_string()))
+    }
+
+    /// Posts an entry to the books of company `id` only.
+    pub fn post_entry(&mut self, id: &str, entry: JournalEntry) -> Result<EntryId, LedgerError> {
+        self.books_mut(id)?.post_entry(entry)
+    }
+}
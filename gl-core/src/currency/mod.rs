@@ -0,0 +1,135 @@
+// gl-core/src/currency/mod.rs
+
+//! Foreign-currency transactions. The ledger is kept in VND, the functional
+//! currency: a line in another currency still carries its debit or credit
+//! in VND, with the original currency, amount and exchange rate alongside.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::{AccountCode, JournalLine, Ledger, LedgerError};
+
+mod precision;
+mod rates;
+mod revaluation;
+pub use self::precision::{Allocation, PrecisionPolicy, RoundingMode};
+pub use self::rates::{import_rates_csv, ExchangeRate, RateTable};
+pub use self::revaluation::{
+    revaluation, RevaluationLine, EXCHANGE_DIFFERENCE_ACCOUNT, FX_GAIN_ACCOUNT, FX_LOSS_ACCOUNT, REVALUED_ACCOUNTS,
+};
+
+/// Đồng Việt Nam, the currency of account (đơn vị tiền tệ trong kế toán).
+pub const FUNCTIONAL_CURRENCY: &str = "VND";
+
+/// Original-currency amount of a line and the rate it was booked at.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ForeignAmount {
+    /// ISO 4217 code, e.g. `USD`.
+    pub currency: String,
+    pub amount: Decimal,
+    /// VND per unit of `currency`.
+    pub rate: Decimal,
+}
+
+impl ForeignAmount {
+    pub fn new(currency: &str, amount: Decimal, rate: Decimal) -> Result<Self, LedgerError> {
+        let foreign = ForeignAmount { currency: currency.to_string(), amount, rate };
+        foreign.check()?;
+        Ok(foreign)
+    }
+
+    /// The amount in VND, rounded with the ledger's policy (`Ledger::precision`).
+    pub fn functional_amount(&self, precision: &PrecisionPolicy) -> Decimal {
+        precision.round(FUNCTIONAL_CURRENCY, self.amount * self.rate)
+    }
+
+    fn check(&self) -> Result<(), LedgerError> {
+        check_currency_code(&self.currency)?;
+        if self.currency == FUNCTIONAL_CURRENCY {
+            return Err(LedgerError::InvalidCurrency(format!(
+                "{} is the functional currency, not a foreign one",
+                FUNCTIONAL_CURRENCY
+            )));
+        }
+        if self.amount < Decimal::ZERO {
+            return Err(LedgerError::NegativeAmount(self.amount));
+        }
+        if self.rate <= Decimal::ZERO {
+            return Err(LedgerError::InvalidCurrency(format!(
+                "exchange rate {} for {} must be positive",
+                self.rate, self.currency
+            )));
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn check_currency_code(code: &str) -> Result<(), LedgerError> {
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(LedgerError::InvalidCurrency(format!("'{}' is not an ISO 4217 code", code)));
+    }
+    Ok(())
+}
+
+/// A foreign amount must be valid and agree with the line's VND amount to
+/// the đồng. A zero foreign amount is an exchange difference, booked in VND
+/// only.
+pub(crate) fn check_line(line: &JournalLine) -> Result<(), LedgerError> {
+    let Some(foreign) = &line.foreign else {
+        return Ok(());
+    };
+    foreign.check()?;
+    if foreign.amount.is_zero() {
+        return Ok(());
+    }
+    let booked = line.debit + line.credit;
+    if (booked - foreign.amount * foreign.rate).abs() >= Decimal::ONE {
+        return Err(LedgerError::CurrencyAmountMismatch(
+            line.account.as_str().to_string(),
+            booked,
+            foreign.functional_amount(&PrecisionPolicy::default()),
+        ));
+    }
+    Ok(())
+}
+
+/// Balance of one account in one foreign currency, debit positive.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CurrencyBalance {
+    pub account: AccountCode,
+    pub currency: String,
+    pub foreign_balance: Decimal,
+    /// VND booked for the same lines, at their historical rates.
+    pub balance: Decimal,
+}
+
+/// Foreign-currency balances per account and currency as of a date.
+pub fn currency_balances(ledger: &Ledger, as_of: NaiveDate) -> Vec<CurrencyBalance> {
+    let mut balances: BTreeMap<(AccountCode, String), (Decimal, Decimal)> = BTreeMap::new();
+    for posted in ledger.entries().iter().filter(|e| e.entry.date <= as_of) {
+        for line in &posted.entry.lines {
+            let Some(foreign) = &line.foreign else {
+                continue;
+            };
+            let sign = if line.debit > Decimal::ZERO { Decimal::ONE } else { Decimal::NEGATIVE_ONE };
+            let (fx, vnd) = balances
+                .entry((line.account.clone(), foreign.currency.clone()))
+                .or_insert((Decimal::ZERO, Decimal::ZERO));
+            *fx += sign * foreign.amount;
+            *vnd += line.debit - line.credit;
+        }
+    }
+    balances
+        .into_iter()
+        .map(|((account, currency), (foreign_balance, balance))| CurrencyBalance {
+            account,
+            currency,
+            foreign_balance,
+            balance,
+        })
+        .collect()
+}
@@ -0,0 +1,193 @@
+// gl-core/src/currency/precision.rs
+
+//! Amount precision and rounding. VND has no minor unit, so every amount
+//! posted to the books must be whole đồng; foreign amounts keep the minor
+//! unit of their currency. Computed amounts (VAT, allocations) are rounded
+//! explicitly and any difference is booked on its own line.
+
+use std::collections::BTreeMap;
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+use super::FUNCTIONAL_CURRENCY;
+use crate::{AccountCode, JournalEntry, JournalLine, Ledger, LedgerError};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Half away from zero (làm tròn thông thường): 0.5 → 1.
+    HalfUp,
+    /// Half to even (banker's rounding): 0.5 → 0, 1.5 → 2.
+    HalfEven,
+    /// Towards zero: drops the excess.
+    Down,
+    /// Away from zero.
+    Up,
+}
+
+impl RoundingMode {
+    pub fn round(self, amount: Decimal, decimals: u32) -> Decimal {
+        let strategy = match self {
+            RoundingMode::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            RoundingMode::HalfEven => RoundingStrategy::MidpointNearestEven,
+            RoundingMode::Down => RoundingStrategy::ToZero,
+            RoundingMode::Up => RoundingStrategy::AwayFromZero,
+        };
+        amount.round_dp_with_strategy(decimals, strategy)
+    }
+}
+
+/// Split of an amount into rounded parts; `rounding_difference` is what the
+/// parts fall short of the total (negative when they exceed it).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Allocation {
+    pub parts: Vec<Decimal>,
+    pub rounding_difference: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct PrecisionPolicy {
+    /// Decimal places per currency code.
+    pub decimals: BTreeMap<String, u32>,
+    /// Decimal places of currencies not listed.
+    pub default_decimals: u32,
+    /// Rounding of allocations and conversions.
+    pub rounding: RoundingMode,
+    /// Rounding of VAT and other tax computed from a base amount.
+    pub tax_rounding: RoundingMode,
+    /// Thu nhập khác, credited with rounding differences.
+    pub rounding_gain_account: String,
+    /// Chi phí khác, debited with rounding differences.
+    pub rounding_loss_account: String,
+}
+
+impl Default for PrecisionPolicy {
+    fn default() -> Self {
+        let decimals = [(FUNCTIONAL_CURRENCY, 0), ("JPY", 0), ("KRW", 0)]
+            .into_iter()
+            .map(|(code, dp)| (code.to_string(), dp))
+            .collect();
+        PrecisionPolicy {
+            decimals,
+            default_decimals: 2,
+            rounding: RoundingMode::HalfUp,
+            tax_rounding: RoundingMode::HalfUp,
+            rounding_gain_account: "711".to_string(),
+            rounding_loss_account: "811".to_string(),
+        }
+    }
+}
+
+impl PrecisionPolicy {
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(serde_yaml::from_str(contents)?)
+    }
+
+    pub fn decimals_for(&self, currency: &str) -> u32 {
+        self.decimals.get(currency).copied().unwrap_or(self.default_decimals)
+    }
+
+    /// Rounds an amount of `currency` to its precision.
+    pub fn round(&self, currency: &str, amount: Decimal) -> Decimal {
+        self.rounding.round(amount, self.decimals_for(currency))
+    }
+
+    pub fn check(&self, currency: &str, amount: Decimal) -> Result<(), LedgerError> {
+        let decimals = self.decimals_for(currency);
+        if amount.normalize().scale() > decimals {
+            return Err(LedgerError::PrecisionExceeded(currency.to_string(), amount, decimals));
+        }
+        Ok(())
+    }
+
+    /// Debits and credits must be whole đồng, foreign amounts within the
+    /// precision of their currency, and the VND amount of a foreign line
+    /// its conversion rounded with this policy.
+    pub fn check_entry(&self, entry: &JournalEntry) -> Result<(), LedgerError> {
+        for line in &entry.lines {
+            self.check(FUNCTIONAL_CURRENCY, line.debit)?;
+            self.check(FUNCTIONAL_CURRENCY, line.credit)?;
+            if let Some(foreign) = &line.foreign {
+                self.check(&foreign.currency, foreign.amount)?;
+                let booked = line.debit + line.credit;
+                let converted = foreign.functional_amount(self);
+                if !foreign.amount.is_zero() && booked != converted {
+                    return Err(LedgerError::CurrencyAmountMismatch(
+                        line.account.as_str().to_string(),
+                        booked,
+                        converted,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Tax at `rate_percent` of `base`, in VND rounded with the tax rounding mode.
+    pub fn tax(&self, base: Decimal, rate_percent: Decimal) -> Decimal {
+        self.tax_rounding.round(base * rate_percent / Decimal::ONE_HUNDRED, self.decimals_for(FUNCTIONAL_CURRENCY))
+    }
+
+    /// Splits `total` in proportion to `weights`, rounding each part.
+    pub fn allocate(&self, currency: &str, total: Decimal, weights: &[Decimal]) -> Result<Allocation, LedgerError> {
+        self.check(currency, total)?;
+        let sum: Decimal = weights.iter().copied().sum();
+        if weights.is_empty() || sum.is_zero() {
+            return Err(LedgerError::InvalidAllocation("weights must not sum to zero".to_string()));
+        }
+        if weights.iter().any(|w| *w < Decimal::ZERO) {
+            return Err(LedgerError::InvalidAllocation("weights must not be negative".to_string()));
+        }
+        let parts: Vec<Decimal> = weights.iter().map(|w| self.round(currency, total * *w / sum)).collect();
+        let rounding_difference = total - parts.iter().copied().sum::<Decimal>();
+        Ok(Allocation { parts, rounding_difference })
+    }
+
+    /// Lines spreading a VND `total` over `targets` (account and weight) on
+    /// the debit or credit side, plus a line on the rounding gain or loss
+    /// account when the rounded parts do not add up to the total.
+    pub fn allocation_lines(
+        &self,
+        total: Decimal,
+        targets: &[(AccountCode, Decimal)],
+        debit: bool,
+    ) -> Result<Vec<JournalLine>, LedgerError> {
+        let weights: Vec<Decimal> = targets.iter().map(|(_, w)| *w).collect();
+        let allocation = self.allocate(FUNCTIONAL_CURRENCY, total, &weights)?;
+        let side = |account: AccountCode, amount: Decimal, on_debit: bool| {
+            if on_debit {
+                JournalLine::new(account, amount, Decimal::ZERO)
+            } else {
+                JournalLine::new(account, Decimal::ZERO, amount)
+            }
+        };
+
+        let mut lines = Vec::new();
+        for ((account, _), part) in targets.iter().zip(allocation.parts) {
+            if !part.is_zero() {
+                lines.push(side(account.clone(), part, debit)?);
+            }
+        }
+        let difference = allocation.rounding_difference;
+        if !difference.is_zero() {
+            // A shortfall is topped up on the same side, an excess taken back on the other
+            let on_debit = (difference > Decimal::ZERO) == debit;
+            let account = if on_debit { &self.rounding_loss_account } else { &self.rounding_gain_account };
+            lines.push(side(AccountCode::new(account)?, difference.abs(), on_debit)?);
+        }
+        Ok(lines)
+    }
+}
+
+impl Ledger {
+    /// Uses a precision policy other than whole đồng with half-up rounding.
+    pub fn with_precision(mut self, precision: PrecisionPolicy) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    pub fn precision(&self) -> &PrecisionPolicy {
+        &self.precision
+    }
+}
@@ -0,0 +1,152 @@
+// gl-core/src/currency/rates.rs
+
+//! Exchange rates by currency and date, entered by hand or imported from
+//! the rate tables published by the SBV or a commercial bank.
+
+use std::io::Read;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::check_currency_code;
+use crate::import::{parse_amount, parse_date, NumberFormat};
+use crate::{AuditAction, Ledger, LedgerError, Permission, FUNCTIONAL_CURRENCY};
+
+/// VND per unit of `currency` on `date`. Banks quote a buying and a selling
+/// rate; a single published rate (e.g. the SBV central rate) is both.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExchangeRate {
+    pub currency: String,
+    pub date: NaiveDate,
+    /// Tỷ giá mua, used for assets (1122, 131).
+    pub buying: Decimal,
+    /// Tỷ giá bán, used for liabilities (331).
+    pub selling: Decimal,
+    /// Where the rate comes from, e.g. `SBV` or `VCB`.
+    #[serde(default)]
+    pub source: String,
+}
+
+impl ExchangeRate {
+    pub fn new(currency: &str, date: NaiveDate, buying: Decimal, selling: Decimal) -> Result<Self, LedgerError> {
+        let rate = ExchangeRate { currency: currency.to_string(), date, buying, selling, source: String::new() };
+        rate.check()?;
+        Ok(rate)
+    }
+
+    pub fn with_source(mut self, source: &str) -> Self {
+        self.source = source.to_string();
+        self
+    }
+
+    fn check(&self) -> Result<(), LedgerError> {
+        check_currency_code(&self.currency)?;
+        if self.currency == FUNCTIONAL_CURRENCY {
+            return Err(LedgerError::InvalidCurrency(format!(
+                "no exchange rate is kept for {}",
+                FUNCTIONAL_CURRENCY
+            )));
+        }
+        if self.buying <= Decimal::ZERO || self.selling <= Decimal::ZERO {
+            return Err(LedgerError::InvalidCurrency(format!(
+                "exchange rate of {} on {} must be positive",
+                self.currency, self.date
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct RateTable {
+    pub rates: Vec<ExchangeRate>,
+}
+
+impl RateTable {
+    pub fn new() -> Self {
+        RateTable { rates: vec![] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rates.is_empty()
+    }
+
+    /// The latest rate of `currency` dated on or before `date`.
+    pub fn get(&self, currency: &str, date: NaiveDate) -> Option<&ExchangeRate> {
+        self.rates
+            .iter()
+            .filter(|r| r.currency == currency && r.date <= date)
+            .max_by_key(|r| r.date)
+    }
+
+    /// Adds a rate, replacing the one of the same currency and date; returns
+    /// the replaced rate.
+    pub(crate) fn set(&mut self, rate: ExchangeRate) -> Result<Option<ExchangeRate>, LedgerError> {
+        rate.check()?;
+        match self.rates.iter_mut().find(|r| r.currency == rate.currency && r.date == rate.date) {
+            Some(existing) => Ok(Some(std::mem::replace(existing, rate))),
+            None => {
+                self.rates.push(rate);
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Reads rates from CSV with the header `date,currency,buying,selling`
+/// (optionally `source`): dates as `dd/mm/yyyy`, amounts in Vietnamese
+/// format. An empty `selling` takes the buying rate.
+pub fn import_rates_csv<R: Read>(reader: R, delimiter: u8) -> Result<Vec<ExchangeRate>, LedgerError> {
+    let mut reader = ::csv::ReaderBuilder::new().delimiter(delimiter).flexible(true).from_reader(reader);
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| LedgerError::InvalidImport(e.to_string()))?
+        .iter()
+        .map(|h| h.trim_start_matches('\u{feff}').trim().to_string())
+        .collect();
+    let find = |name: &str| headers.iter().position(|h| h == name);
+    let column = |name: &str| find(name).ok_or_else(|| LedgerError::InvalidImport(format!("missing column '{}'", name)));
+    let (date, currency, buying) = (column("date")?, column("currency")?, column("buying")?);
+    let (selling, source) = (find("selling"), find("source"));
+
+    let mut rates = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| LedgerError::InvalidImport(e.to_string()))?;
+        let line = record.position().map(|p| p.line()).unwrap_or(0);
+        let cell = |i: usize| record.get(i).unwrap_or("").trim();
+        let invalid = |message: String| LedgerError::InvalidImport(format!("line {}: {}", line, message));
+
+        let day = parse_date(cell(date), "%d/%m/%Y").map_err(invalid)?;
+        let buy = parse_amount(cell(buying), &NumberFormat::Vietnamese).map_err(invalid)?;
+        let sell = match selling.map(cell).filter(|s| !s.is_empty()) {
+            Some(s) => parse_amount(s, &NumberFormat::Vietnamese).map_err(invalid)?,
+            None => buy,
+        };
+        let rate = ExchangeRate::new(cell(currency), day, buy, sell)
+            .map_err(|e| invalid(e.to_string()))?
+            .with_source(source.map(cell).unwrap_or(""));
+        rates.push(rate);
+    }
+    Ok(rates)
+}
+
+impl Ledger {
+    pub fn with_rates(mut self, rates: RateTable) -> Self {
+        self.rates = rates;
+        self
+    }
+
+    pub fn rates(&self) -> &RateTable {
+        &self.rates
+    }
+
+    /// Enters or corrects the rate of a currency for a date; rates decide
+    /// the amounts booked, so this takes the permission to post.
+    pub fn set_exchange_rate(&mut self, rate: ExchangeRate) -> Result<(), LedgerError> {
+        self.authorize(Permission::PostEntry)?;
+        let before = self.rates.set(rate.clone())?;
+        self.audit(AuditAction::ExchangeRateSet, &serde_json::json!({ "before": before, "after": rate }));
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+// gl-core/src/currency/revaluation.rs
+
+//! Period-end revaluation of foreign-currency monetary items (VAS 10,
+//! Circular 133 art. 52). Balances of 1122, 131 and 331 in each foreign
+//! currency are restated at the closing rate: assets at the bank's buying
+//! rate, liabilities at its selling rate. The difference is booked against
+//! 413 and 413 is cleared to 515 (gain) or 635 (loss). The entry reverses
+//! itself on the first day of the next period.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::{ForeignAmount, FUNCTIONAL_CURRENCY};
+use crate::{AccountCode, EntryId, JournalEntry, JournalLine, Ledger, LedgerError, PeriodManager};
+
+/// Monetary accounts revalued at period end, with their sub-accounts.
+pub const REVALUED_ACCOUNTS: [&str; 3] = ["1122", "131", "331"];
+/// Chênh lệch tỷ giá hối đoái.
+pub const EXCHANGE_DIFFERENCE_ACCOUNT: &str = "413";
+/// Doanh thu hoạt động tài chính, where net gains are cleared.
+pub const FX_GAIN_ACCOUNT: &str = "515";
+/// Chi phí tài chính, where net losses are cleared.
+pub const FX_LOSS_ACCOUNT: &str = "635";
+
+/// One balance restated at the closing rate; amounts are debit positive.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RevaluationLine {
+    pub account: AccountCode,
+    pub party: Option<String>,
+    pub currency: String,
+    pub foreign_balance: Decimal,
+    /// VND carried in the books before revaluation.
+    pub booked: Decimal,
+    pub rate: Decimal,
+    pub revalued: Decimal,
+    /// `revalued - booked`: a gain when positive.
+    pub difference: Decimal,
+}
+
+/// Restates every foreign-currency balance of the revalued accounts as of
+/// `as_of`; balances already at the closing rate are left out.
+pub fn revaluation(ledger: &Ledger, as_of: NaiveDate) -> Result<Vec<RevaluationLine>, LedgerError> {
+    type Key = (AccountCode, Option<String>, String);
+    let mut balances: BTreeMap<Key, (Decimal, Decimal)> = BTreeMap::new();
+    for posted in ledger.entries().iter().filter(|e| e.entry.date <= as_of) {
+        for line in &posted.entry.lines {
+            let Some(foreign) = &line.foreign else {
+                continue;
+            };
+            if !REVALUED_ACCOUNTS.iter().any(|a| line.account.as_str().starts_with(a)) {
+                continue;
+            }
+            let sign = if line.debit > Decimal::ZERO { Decimal::ONE } else { Decimal::NEGATIVE_ONE };
+            let (fx, vnd) = balances
+                .entry((line.account.clone(), line.party.clone(), foreign.currency.clone()))
+                .or_insert((Decimal::ZERO, Decimal::ZERO));
+            *fx += sign * foreign.amount;
+            *vnd += line.debit - line.credit;
+        }
+    }
+
+    let mut lines = Vec::new();
+    for ((account, party, currency), (foreign_balance, booked)) in balances {
+        let quote = ledger
+            .rates()
+            .get(&currency, as_of)
+            .ok_or_else(|| LedgerError::MissingExchangeRate(currency.clone(), as_of))?;
+        let rate = if foreign_balance >= Decimal::ZERO { quote.buying } else { quote.selling };
+        let revalued = ledger.precision().round(FUNCTIONAL_CURRENCY, foreign_balance * rate);
+        let difference = revalued - booked;
+        if difference.is_zero() {
+            continue;
+        }
+        lines.push(RevaluationLine { account, party, currency, foreign_balance, booked, rate, revalued, difference });
+    }
+    Ok(lines)
+}
+
+fn account(code: &str) -> AccountCode {
+    AccountCode(code.to_string())
+}
+
+impl Ledger {
+    /// Posts the period-end revaluation as of `as_of` as an accrual of the
+    /// period containing that date, so it is reversed when the next period
+    /// starts (see `run_auto_reversals`). Returns `None` when every balance
+    /// is already at the closing rate.
+    pub fn revalue_currencies(
+        &mut self,
+        periods: &PeriodManager,
+        as_of: NaiveDate,
+    ) -> Result<Option<EntryId>, LedgerError> {
+        let restated = revaluation(self, as_of)?;
+        if restated.is_empty() {
+            return Ok(None);
+        }
+
+        let mut lines = Vec::new();
+        let mut net = Decimal::ZERO;
+        for r in &restated {
+            let amount = r.difference.abs();
+            let (debit, credit) = if r.difference > Decimal::ZERO { (amount, Decimal::ZERO) } else { (Decimal::ZERO, amount) };
+            // A zero foreign amount marks the line as an exchange difference in the currency's books
+            let mut line = JournalLine::new(r.account.clone(), debit, credit)?
+                .with_foreign(ForeignAmount::new(&r.currency, Decimal::ZERO, r.rate)?)?;
+            if let Some(party) = &r.party {
+                line = line.with_party(party);
+            }
+            lines.push(line);
+            lines.push(JournalLine::new(account(EXCHANGE_DIFFERENCE_ACCOUNT), credit, debit)?);
+            net += r.difference;
+        }
+        if net > Decimal::ZERO {
+            lines.push(JournalLine::new(account(EXCHANGE_DIFFERENCE_ACCOUNT), net, Decimal::ZERO)?);
+            lines.push(JournalLine::new(account(FX_GAIN_ACCOUNT), Decimal::ZERO, net)?);
+        } else if net < Decimal::ZERO {
+            lines.push(JournalLine::new(account(FX_LOSS_ACCOUNT), -net, Decimal::ZERO)?);
+            lines.push(JournalLine::new(account(EXCHANGE_DIFFERENCE_ACCOUNT), Decimal::ZERO, -net)?);
+        }
+
+        let entry = JournalEntry::new(as_of, lines, format!("Đánh giá lại ngoại tệ cuối kỳ {}", as_of.format("%d/%m/%Y")))?;
+        self.post_accrual(entry, periods).map(Some)
+    }
+}
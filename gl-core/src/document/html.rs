@@ -0,0 +1,76 @@
+// gl-core/src/document/html.rs
+
+use super::{account_list, body_lines, date_line, CashVoucher, FORM_NOTE};
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const STYLE: &str = "\
+body { font-family: 'Times New Roman', serif; font-size: 13pt; width: 190mm; margin: 10mm auto; }
+.header { display: flex; justify-content: space-between; }
+.form { text-align: center; }
+.title { text-align: center; margin: 6mm 0 0; }
+.date { text-align: center; font-style: italic; }
+.accounts { text-align: right; }
+.line { margin: 2mm 0; }
+.signatures { display: flex; justify-content: space-between; text-align: center; margin-top: 8mm; }
+.signatures div { width: 20%; }
+.hint { font-style: italic; font-size: 11pt; }
+";
+
+pub(super) fn render(voucher: &CashVoucher) -> String {
+    let kind = voucher.kind;
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"vi\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{} {}</title>\n", kind.title(), escape(&voucher.number)));
+    html.push_str(&format!("<style>\n{}</style>\n</head>\n<body>\n", STYLE));
+
+    html.push_str("<div class=\"header\">\n<div>\n");
+    html.push_str(&format!("<div><b>Đơn vị:</b> {}</div>\n", escape(&voucher.company.name)));
+    html.push_str(&format!("<div><b>Địa chỉ:</b> {}</div>\n", escape(&voucher.company.address)));
+    html.push_str("</div>\n<div class=\"form\">\n");
+    html.push_str(&format!("<div><b>Mẫu số {}</b></div>\n", kind.form()));
+    html.push_str(&format!("<div class=\"hint\">{}</div>\n", FORM_NOTE));
+    html.push_str("</div>\n</div>\n");
+
+    html.push_str(&format!("<h2 class=\"title\">{}</h2>\n", kind.title()));
+    html.push_str(&format!("<div class=\"date\">{}</div>\n", date_line(voucher.date)));
+    html.push_str("<div class=\"accounts\">\n");
+    html.push_str("<div>Quyển số: ............</div>\n");
+    html.push_str(&format!("<div>Số: {}</div>\n", escape(&voucher.number)));
+    html.push_str(&format!("<div>Nợ: {}</div>\n", account_list(&voucher.debit_accounts)));
+    html.push_str(&format!("<div>Có: {}</div>\n", account_list(&voucher.credit_accounts)));
+    html.push_str("</div>\n");
+
+    for (label, value) in body_lines(voucher) {
+        html.push_str(&format!("<div class=\"line\">{}: {}</div>\n", escape(&label), escape(&value)));
+    }
+
+    html.push_str(&format!("<div class=\"date\" style=\"text-align: right\">{}</div>\n", date_line(voucher.date)));
+    html.push_str("<div class=\"signatures\">\n");
+    for title in kind.signatures() {
+        html.push_str(&format!(
+            "<div><b>{}</b><br><span class=\"hint\">(Ký, họ tên)</span></div>\n",
+            title
+        ));
+    }
+    html.push_str("</div>\n");
+    html.push_str(&format!(
+        "<div class=\"line\">Đã nhận đủ số tiền (viết bằng chữ): {}</div>\n",
+        escape(&voucher.amount_in_words())
+    ));
+    html.push_str("</body>\n</html>\n");
+    html
+}
@@ -0,0 +1,219 @@
+// gl-core/src/document/mod.rs
+
+//! Printable vouchers on the Circular 133/2016/TT-BTC forms: Phiếu thu
+//! (01-TT) and Phiếu chi (02-TT), rendered as HTML or PDF.
+
+use chrono::NaiveDate;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+use crate::{AccountCode, JournalEntry, LedgerError, VoucherType};
+
+mod html;
+mod pdf;
+mod words;
+pub use words::amount_in_words;
+
+/// Cash accounts (tiền mặt); their side of the entry decides receipt or payment.
+const CASH_PREFIX: &str = "111";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CashVoucherKind {
+    /// Phiếu thu, form 01-TT.
+    Receipt,
+    /// Phiếu chi, form 02-TT.
+    Payment,
+}
+
+impl CashVoucherKind {
+    pub fn form(&self) -> &'static str {
+        match self {
+            CashVoucherKind::Receipt => "01-TT",
+            CashVoucherKind::Payment => "02-TT",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            CashVoucherKind::Receipt => "PHIẾU THU",
+            CashVoucherKind::Payment => "PHIẾU CHI",
+        }
+    }
+
+    /// Label of the person paying in or receiving the cash.
+    fn party_label(&self) -> &'static str {
+        match self {
+            CashVoucherKind::Receipt => "Họ và tên người nộp tiền",
+            CashVoucherKind::Payment => "Họ và tên người nhận tiền",
+        }
+    }
+
+    fn reason_label(&self) -> &'static str {
+        match self {
+            CashVoucherKind::Receipt => "Lý do nộp",
+            CashVoucherKind::Payment => "Lý do chi",
+        }
+    }
+
+    /// Signature boxes, left to right, as laid out on the form.
+    fn signatures(&self) -> [&'static str; 5] {
+        match self {
+            CashVoucherKind::Receipt => ["Giám đốc", "Kế toán trưởng", "Người nộp tiền", "Người lập phiếu", "Thủ quỹ"],
+            CashVoucherKind::Payment => ["Giám đốc", "Kế toán trưởng", "Thủ quỹ", "Người lập phiếu", "Người nhận tiền"],
+        }
+    }
+}
+
+/// The entity issuing the voucher (Đơn vị, Địa chỉ in the form header).
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct CompanyInfo {
+    pub name: String,
+    pub address: String,
+}
+
+/// Payer of a receipt or payee of a payment.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct VoucherParty {
+    pub name: String,
+    pub address: String,
+}
+
+/// Content of a Phiếu thu / Phiếu chi.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CashVoucher {
+    pub kind: CashVoucherKind,
+    pub company: CompanyInfo,
+    pub number: String,
+    pub date: NaiveDate,
+    pub party: VoucherParty,
+    pub reason: String,
+    /// Cash amount, rounded to whole đồng.
+    pub amount: Decimal,
+    pub debit_accounts: Vec<AccountCode>,
+    pub credit_accounts: Vec<AccountCode>,
+    /// Number of attached source documents (Kèm theo ... chứng từ gốc).
+    pub attachments: u32,
+}
+
+impl CashVoucher {
+    /// Builds the voucher from a cash entry: a Phiếu thu when cash (111) is
+    /// debited, a Phiếu chi when it is credited. A PT/PC voucher type on the
+    /// entry must agree with that side.
+    pub fn from_entry(
+        entry: &JournalEntry,
+        company: CompanyInfo,
+        party: VoucherParty,
+    ) -> Result<Self, LedgerError> {
+        let is_cash = |a: &AccountCode| a.as_str().starts_with(CASH_PREFIX);
+        let cash_debit: Decimal = entry.lines.iter().filter(|l| is_cash(&l.account)).map(|l| l.debit).sum();
+        let cash_credit: Decimal = entry.lines.iter().filter(|l| is_cash(&l.account)).map(|l| l.credit).sum();
+
+        let (kind, amount) = if cash_debit > cash_credit {
+            (CashVoucherKind::Receipt, cash_debit - cash_credit)
+        } else if cash_credit > cash_debit {
+            (CashVoucherKind::Payment, cash_credit - cash_debit)
+        } else {
+            return Err(LedgerError::InvalidDocument(format!(
+                "entry '{}' does not move cash (account {})",
+                entry.description, CASH_PREFIX
+            )));
+        };
+        if let Some(voucher) = &entry.voucher {
+            let expected = match kind {
+                CashVoucherKind::Receipt => VoucherType::PhieuThu,
+                CashVoucherKind::Payment => VoucherType::PhieuChi,
+            };
+            if voucher.voucher_type != expected {
+                return Err(LedgerError::InvalidDocument(format!(
+                    "voucher {} is a {}, but the entry is a {}",
+                    voucher.number,
+                    voucher.voucher_type.name(),
+                    expected.name()
+                )));
+            }
+        }
+
+        let accounts = |debit: bool| {
+            let mut accounts: Vec<AccountCode> = Vec::new();
+            for line in &entry.lines {
+                let amount = if debit { line.debit } else { line.credit };
+                if amount > Decimal::ZERO && !accounts.contains(&line.account) {
+                    accounts.push(line.account.clone());
+                }
+            }
+            accounts
+        };
+
+        Ok(CashVoucher {
+            kind,
+            company,
+            number: entry.voucher.as_ref().map(|v| v.number.clone()).unwrap_or_default(),
+            date: entry.date,
+            party,
+            reason: entry.description.clone(),
+            amount: amount.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero),
+            debit_accounts: accounts(true),
+            credit_accounts: accounts(false),
+            attachments: 0,
+        })
+    }
+
+    pub fn with_attachments(mut self, attachments: u32) -> Self {
+        self.attachments = attachments;
+        self
+    }
+
+    pub fn amount_in_words(&self) -> String {
+        amount_in_words(self.amount)
+    }
+
+    pub fn render_html(&self) -> String {
+        html::render(self)
+    }
+
+    /// Renders an A5 landscape PDF. The TrueType font must cover Vietnamese
+    /// (e.g. DejaVu Sans or Times New Roman); the builtin PDF fonts do not.
+    pub fn render_pdf(&self, font: &[u8]) -> Result<Vec<u8>, LedgerError> {
+        pdf::render(self, font)
+    }
+}
+
+/// `1234567` → `1.234.567`, the Vietnamese grouping used on vouchers.
+pub fn format_vnd(amount: Decimal) -> String {
+    let rounded = amount.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero);
+    let digits = rounded.abs().to_string();
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push('.');
+        }
+        grouped.push(c);
+    }
+    if rounded.is_sign_negative() && !rounded.is_zero() {
+        grouped.insert(0, '-');
+    }
+    grouped
+}
+
+/// "Ngày 05 tháng 01 năm 2025", as written under the voucher title.
+fn date_line(date: NaiveDate) -> String {
+    date.format("Ngày %d tháng %m năm %Y").to_string()
+}
+
+fn account_list(accounts: &[AccountCode]) -> String {
+    accounts.iter().map(|a| a.as_str()).collect::<Vec<_>>().join(", ")
+}
+
+/// Labelled lines of the voucher body, shared by the HTML and PDF layouts.
+fn body_lines(voucher: &CashVoucher) -> Vec<(String, String)> {
+    vec![
+        (voucher.kind.party_label().to_string(), voucher.party.name.clone()),
+        ("Địa chỉ".to_string(), voucher.party.address.clone()),
+        (voucher.kind.reason_label().to_string(), voucher.reason.clone()),
+        ("Số tiền".to_string(), format!("{} đồng", format_vnd(voucher.amount))),
+        ("Viết bằng chữ".to_string(), voucher.amount_in_words()),
+        ("Kèm theo".to_string(), format!("{} chứng từ gốc", voucher.attachments)),
+    ]
+}
+
+const FORM_NOTE: &str = "(Ban hành theo Thông tư số 133/2016/TT-BTC ngày 26/8/2016 của Bộ Tài chính)";
@@ -0,0 +1,83 @@
+// gl-core/src/document/pdf.rs
+
+use std::io::Cursor;
+
+use printpdf::{IndirectFontRef, Mm, PdfDocument, PdfLayerReference};
+
+use super::{account_list, body_lines, date_line, CashVoucher, FORM_NOTE};
+use crate::LedgerError;
+
+fn pdf_error(e: printpdf::Error) -> LedgerError {
+    LedgerError::Pdf(e.to_string())
+}
+
+/// A5 landscape, the usual size of a printed Phiếu thu / Phiếu chi.
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 148.0;
+const MARGIN: f32 = 12.0;
+
+/// Rough width of a string, for centring; exact metrics are not needed here.
+fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * size * 0.19
+}
+
+fn centred(layer: &PdfLayerReference, text: &str, size: f32, x: f32, y: f32, font: &IndirectFontRef) {
+    layer.use_text(text, size, Mm(x - text_width(text, size) / 2.0), Mm(y), font);
+}
+
+pub(super) fn render(voucher: &CashVoucher, font_bytes: &[u8]) -> Result<Vec<u8>, LedgerError> {
+    let kind = voucher.kind;
+    let (doc, page, layer) = PdfDocument::new(
+        format!("{} {}", kind.title(), voucher.number),
+        Mm(PAGE_WIDTH),
+        Mm(PAGE_HEIGHT),
+        "Voucher",
+    );
+    let font = doc.add_external_font(Cursor::new(font_bytes)).map_err(pdf_error)?;
+    let layer = doc.get_page(page).get_layer(layer);
+
+    let mut y = PAGE_HEIGHT - MARGIN;
+    layer.use_text(format!("Đơn vị: {}", voucher.company.name), 10.0, Mm(MARGIN), Mm(y), &font);
+    centred(&layer, &format!("Mẫu số {}", kind.form()), 10.0, 165.0, y, &font);
+    y -= 5.0;
+    layer.use_text(format!("Địa chỉ: {}", voucher.company.address), 10.0, Mm(MARGIN), Mm(y), &font);
+    centred(&layer, FORM_NOTE, 6.5, 165.0, y, &font);
+
+    y -= 12.0;
+    centred(&layer, kind.title(), 16.0, PAGE_WIDTH / 2.0, y, &font);
+    let right = PAGE_WIDTH - MARGIN - 40.0;
+    layer.use_text("Quyển số: ........", 10.0, Mm(right), Mm(y), &font);
+    y -= 6.0;
+    centred(&layer, &date_line(voucher.date), 10.0, PAGE_WIDTH / 2.0, y, &font);
+    layer.use_text(format!("Số: {}", voucher.number), 10.0, Mm(right), Mm(y), &font);
+    y -= 5.0;
+    layer.use_text(format!("Nợ: {}", account_list(&voucher.debit_accounts)), 10.0, Mm(right), Mm(y), &font);
+    y -= 5.0;
+    layer.use_text(format!("Có: {}", account_list(&voucher.credit_accounts)), 10.0, Mm(right), Mm(y), &font);
+
+    y -= 8.0;
+    for (label, value) in body_lines(voucher) {
+        layer.use_text(format!("{}: {}", label, value), 11.0, Mm(MARGIN), Mm(y), &font);
+        y -= 7.0;
+    }
+
+    y -= 2.0;
+    centred(&layer, &date_line(voucher.date), 10.0, PAGE_WIDTH - MARGIN - 20.0, y, &font);
+    y -= 6.0;
+    let column = (PAGE_WIDTH - 2.0 * MARGIN) / 5.0;
+    for (i, title) in kind.signatures().iter().enumerate() {
+        let x = MARGIN + column * (i as f32 + 0.5);
+        centred(&layer, title, 10.0, x, y, &font);
+        centred(&layer, "(Ký, họ tên)", 8.0, x, y - 4.5, &font);
+    }
+
+    layer.use_text(
+        format!("Đã nhận đủ số tiền (viết bằng chữ): {}", voucher.amount_in_words()),
+        10.0,
+        Mm(MARGIN),
+        Mm(MARGIN),
+        &font,
+    );
+
+    doc.save_to_bytes().map_err(pdf_error)
+}
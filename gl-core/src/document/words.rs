@@ -0,0 +1,82 @@
+// gl-core/src/document/words.rs
+
+//! Vietnamese reading of VND amounts ("Một trăm nghìn đồng chẵn").
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+const DIGITS: [&str; 10] = ["không", "một", "hai", "ba", "bốn", "năm", "sáu", "bảy", "tám", "chín"];
+const BILLION: u128 = 1_000_000_000;
+
+/// Reads a group of three digits. `full` groups follow a higher group and
+/// keep their hundreds ("không trăm") and "linh".
+fn read_group(n: u32, full: bool) -> Vec<&'static str> {
+    let (hundreds, tens, units) = ((n / 100) as usize, ((n / 10) % 10) as usize, (n % 10) as usize);
+    let mut words = Vec::new();
+    if hundreds > 0 || full {
+        words.extend([DIGITS[hundreds], "trăm"]);
+    }
+    match tens {
+        0 if units > 0 && !words.is_empty() => words.push("linh"),
+        0 => {}
+        1 => words.push("mười"),
+        _ => words.extend([DIGITS[tens], "mươi"]),
+    }
+    match units {
+        0 => {}
+        1 if tens >= 2 => words.push("mốt"),
+        4 if tens >= 2 => words.push("tư"),
+        5 if tens >= 1 => words.push("lăm"),
+        _ => words.push(DIGITS[units]),
+    }
+    words
+}
+
+/// Reads a number below one billion.
+fn read_below_billion(n: u128, full: bool) -> Vec<&'static str> {
+    let groups = [((n / 1_000_000) as u32, "triệu"), (((n / 1_000) % 1_000) as u32, "nghìn"), ((n % 1_000) as u32, "")];
+    let mut words = Vec::new();
+    for (group, unit) in groups {
+        if group == 0 {
+            continue;
+        }
+        words.extend(read_group(group, full || !words.is_empty()));
+        if !unit.is_empty() {
+            words.push(unit);
+        }
+    }
+    words
+}
+
+/// Reads a number of any size; billions repeat ("nghìn tỷ", "tỷ tỷ").
+fn read_number(n: u128, full: bool) -> Vec<&'static str> {
+    if n < BILLION {
+        return read_below_billion(n, full);
+    }
+    let mut words = read_number(n / BILLION, full);
+    words.push("tỷ");
+    let rest = n % BILLION;
+    if rest > 0 {
+        words.extend(read_below_billion(rest, true));
+    }
+    words
+}
+
+/// Reads a VND amount in words, rounded to whole đồng, capitalized and
+/// ending in "đồng chẵn" as printed on vouchers.
+pub fn amount_in_words(amount: Decimal) -> String {
+    let rounded = amount.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero);
+    let n = rounded.abs().to_u128().unwrap_or_default();
+    let mut words = if n == 0 { vec![DIGITS[0]] } else { read_number(n, false) };
+    if rounded.is_sign_negative() && n > 0 {
+        words.insert(0, "âm");
+    }
+    words.extend(["đồng", "chẵn"]);
+
+    let text = words.join(" ");
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => text,
+    }
+}
@@ -0,0 +1,568 @@
+// gl-core/src/einvoice/mod.rs
+
+//! Import of Vietnamese e-invoices (Decree 123/2020/ND-CP, Circular 78/2021/TT-BTC).
+//!
+//! The XML layout follows the tax authority schema: `HDon` wraps the invoice
+//! data `DLHDon` (general info `TTChung` and content `NDHDon`) and the
+//! signature list `DSCKS`. Each parsed invoice is turned into a draft
+//! `JournalEntry` using an `EInvoiceMapping`. Its line on 131/331 carries
+//! the partner, the invoice as reference and a due date, and an invoice is
+//! a duplicate when a posted entry already carries it.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::Read;
+
+use chrono::{Days, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::{AccountCode, JournalEntry, JournalLine, Ledger, LedgerError, PrecisionPolicy, FUNCTIONAL_CURRENCY};
+
+// === Parsed invoice ===
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EInvoiceParty {
+    pub name: String,
+    pub tax_code: String,
+    pub address: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum EInvoiceItemKind {
+    Goods,
+    Promotion,
+    Discount,
+    Note,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EInvoiceItem {
+    pub kind: EInvoiceItemKind,
+    pub code: Option<String>,
+    pub name: String,
+    pub unit: Option<String>,
+    pub quantity: Option<Decimal>,
+    pub unit_price: Option<Decimal>,
+    pub amount: Decimal,
+    /// VAT rate as printed on the invoice: "10%", "8%", "0%", "KCT", "KKKNT".
+    pub vat_rate: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EInvoice {
+    pub template: String,
+    pub series: String,
+    pub number: String,
+    pub issue_date: NaiveDate,
+    pub currency: String,
+    pub exchange_rate: Decimal,
+    pub seller: EInvoiceParty,
+    pub buyer: EInvoiceParty,
+    pub items: Vec<EInvoiceItem>,
+    pub total_before_tax: Decimal,
+    pub total_tax: Decimal,
+    pub total_payment: Decimal,
+    /// Whether the seller signature block (`DSCKS/NBan/Signature`) is present.
+    /// The signature itself is not cryptographically verified.
+    pub seller_signed: bool,
+    /// Tax authority code (`MCCQT`) for invoices issued with a code.
+    pub authority_code: Option<String>,
+}
+
+impl EInvoice {
+    /// Key used for duplicate detection: seller MST, invoice series and number.
+    pub fn key(&self) -> (String, String, String) {
+        (
+            self.seller.tax_code.clone(),
+            self.series.clone(),
+            self.number.clone(),
+        )
+    }
+
+    /// How the ledger lines on 131/331 refer to the invoice: its series and
+    /// number, e.g. `C25TAA-123`.
+    pub fn reference(&self) -> String {
+        format!("{}-{}", self.series, self.number)
+    }
+
+    fn is_foreign(&self) -> bool {
+        self.currency != FUNCTIONAL_CURRENCY
+    }
+
+    /// The single VAT percentage of the goods, when every item carries the
+    /// same numeric `TSuat` ("10%", "8%", ...).
+    fn uniform_vat_rate(&self) -> Option<Decimal> {
+        let mut rates = self
+            .items
+            .iter()
+            .filter(|i| matches!(i.kind, EInvoiceItemKind::Goods | EInvoiceItemKind::Discount))
+            .map(|i| i.vat_rate.as_deref().and_then(|r| r.trim().strip_suffix('%')).and_then(|r| r.parse().ok()));
+        let first: Decimal = rates.next()??;
+        rates.all(|r| r == Some(first)).then_some(first)
+    }
+}
+
+pub fn parse_einvoice(xml: &str) -> Result<EInvoice, LedgerError> {
+    let raw: raw::HDon =
+        quick_xml::de::from_str(xml).map_err(|e| LedgerError::InvalidInvoice(e.to_string()))?;
+    raw.into_invoice()
+}
+
+pub fn load_einvoice(path: &str) -> Result<EInvoice, LedgerError> {
+    let mut file = File::open(path).map_err(|e| LedgerError::InvalidInvoice(e.to_string()))?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .map_err(|e| LedgerError::InvalidInvoice(e.to_string()))?;
+    parse_einvoice(&contents)
+}
+
+// === Account mapping ===
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum InvoiceDirection {
+    Purchase,
+    Sale,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AccountMapping {
+    /// Account for the goods/services amount (e.g. 156, 642 or 5111).
+    pub line_account: AccountCode,
+    /// VAT account (133 for input VAT, 3331 for output VAT).
+    pub vat_account: AccountCode,
+    /// Counterparty account (331 for purchases, 131 for sales).
+    pub counter_account: AccountCode,
+    /// Per-item overrides keyed by the item code (`MHHDVu`).
+    #[serde(default)]
+    pub item_accounts: HashMap<String, AccountCode>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EInvoiceMapping {
+    /// MST of the company keeping the books; decides purchase vs. sale.
+    pub company_tax_code: String,
+    pub purchase: AccountMapping,
+    pub sale: AccountMapping,
+}
+
+impl EInvoiceMapping {
+    /// Default Decree 133 mapping: purchases Dr 156 + 133 / Cr 331,
+    /// sales Dr 131 / Cr 5111 + 3331.
+    pub fn new(company_tax_code: &str) -> Self {
+        EInvoiceMapping {
+            company_tax_code: company_tax_code.to_string(),
+            purchase: AccountMapping {
+                line_account: AccountCode("156".to_string()),
+                vat_account: AccountCode("133".to_string()),
+                counter_account: AccountCode("331".to_string()),
+                item_accounts: HashMap::new(),
+            },
+            sale: AccountMapping {
+                line_account: AccountCode("5111".to_string()),
+                vat_account: AccountCode("3331".to_string()),
+                counter_account: AccountCode("131".to_string()),
+                item_accounts: HashMap::new(),
+            },
+        }
+    }
+
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(serde_yaml::from_str(contents)?)
+    }
+
+    pub fn direction(&self, invoice: &EInvoice) -> Result<InvoiceDirection, LedgerError> {
+        if invoice.buyer.tax_code == self.company_tax_code {
+            Ok(InvoiceDirection::Purchase)
+        } else if invoice.seller.tax_code == self.company_tax_code {
+            Ok(InvoiceDirection::Sale)
+        } else {
+            Err(LedgerError::InvalidInvoice(format!(
+                "neither seller ({}) nor buyer ({}) matches company MST {}",
+                invoice.seller.tax_code, invoice.buyer.tax_code, self.company_tax_code
+            )))
+        }
+    }
+
+    pub fn accounts(&self, direction: &InvoiceDirection) -> &AccountMapping {
+        match direction {
+            InvoiceDirection::Purchase => &self.purchase,
+            InvoiceDirection::Sale => &self.sale,
+        }
+    }
+
+    /// Builds a draft (unposted, not period-checked) journal entry. The line
+    /// on the counter account is the partner's, by MST, for the invoice
+    /// `reference()`, due on the issue date. Amounts
+    /// of a foreign-currency invoice are converted at `TGia` and rounded
+    /// with `precision`: the net amount is spread over the line accounts
+    /// with a rounding-difference line if needed, and VAT is rounded with
+    /// the tax rounding mode.
+    pub fn to_entry(&self, invoice: &EInvoice, precision: &PrecisionPolicy) -> Result<JournalEntry, LedgerError> {
+        let direction = self.direction(invoice)?;
+        let mapping = self.accounts(&direction);
+
+        // Net amount per line account, discounts reduce, promotions and notes carry no value
+        let mut per_account: BTreeMap<String, Decimal> = BTreeMap::new();
+        for item in &invoice.items {
+            let signed = match item.kind {
+                EInvoiceItemKind::Goods => item.amount,
+                EInvoiceItemKind::Discount => -item.amount,
+                EInvoiceItemKind::Promotion | EInvoiceItemKind::Note => continue,
+            };
+            let account = item
+                .code
+                .as_ref()
+                .and_then(|c| mapping.item_accounts.get(c))
+                .unwrap_or(&mapping.line_account);
+            *per_account.entry(account.0.clone()).or_insert(Decimal::ZERO) += signed;
+        }
+
+        let net: Decimal = per_account.values().copied().sum();
+        if net != invoice.total_before_tax {
+            return Err(LedgerError::InvalidInvoice(format!(
+                "item total {} does not match TgTCThue {}",
+                net, invoice.total_before_tax
+            )));
+        }
+        if invoice.total_before_tax + invoice.total_tax != invoice.total_payment {
+            return Err(LedgerError::InvalidInvoice(format!(
+                "TgTCThue {} + TgTThue {} does not match TgTTTBSo {}",
+                invoice.total_before_tax, invoice.total_tax, invoice.total_payment
+            )));
+        }
+
+        let mut lines = Vec::new();
+        if invoice.is_foreign() {
+            let net = precision.round(FUNCTIONAL_CURRENCY, invoice.total_before_tax * invoice.exchange_rate);
+            let targets = per_account
+                .into_iter()
+                .map(|(code, amount)| Ok((AccountCode::new(&code)?, amount)))
+                .collect::<Result<Vec<_>, LedgerError>>()?;
+            lines = precision.allocation_lines(net, &targets, direction == InvoiceDirection::Purchase)?;
+        } else {
+            for (code, amount) in per_account {
+                lines.push(self.side(&direction, AccountCode::new(&code)?, amount)?);
+            }
+        }
+        if invoice.total_tax != Decimal::ZERO {
+            let tax = if !invoice.is_foreign() {
+                invoice.total_tax
+            } else if let Some(rate) = invoice.uniform_vat_rate() {
+                // VAT in VND is the rate applied to the converted base
+                let base = precision.round(FUNCTIONAL_CURRENCY, invoice.total_before_tax * invoice.exchange_rate);
+                precision.tax(base, rate)
+            } else {
+                let decimals = precision.decimals_for(FUNCTIONAL_CURRENCY);
+                precision.tax_rounding.round(invoice.total_tax * invoice.exchange_rate, decimals)
+            };
+            lines.push(self.side(&direction, mapping.vat_account.clone(), tax)?);
+        }
+        let partner = match direction {
+            InvoiceDirection::Purchase => &invoice.seller,
+            InvoiceDirection::Sale => &invoice.buyer,
+        };
+        let net_debit: Decimal = lines.iter().map(|l| l.debit - l.credit).sum();
+        let counter_line = match direction {
+            InvoiceDirection::Purchase => {
+                JournalLine::new(mapping.counter_account.clone(), Decimal::ZERO, net_debit)?
+            }
+            InvoiceDirection::Sale => {
+                JournalLine::new(mapping.counter_account.clone(), -net_debit, Decimal::ZERO)?
+            }
+        }
+        .with_party(&partner.tax_code)
+        .with_reference(&invoice.reference())
+        .with_due_date(invoice.issue_date);
+        match direction {
+            InvoiceDirection::Purchase => lines.push(counter_line),
+            InvoiceDirection::Sale => lines.insert(0, counter_line),
+        }
+
+        Ok(JournalEntry {
+            date: invoice.issue_date,
+            lines,
+            description: format!(
+                "HĐ {}{} số {} - {}",
+                invoice.template, invoice.series, invoice.number, partner.name
+            ),
+            voucher: None,
+        })
+    }
+
+    /// Goods and VAT lines sit on the debit side for purchases, credit side for sales.
+    fn side(
+        &self,
+        direction: &InvoiceDirection,
+        account: AccountCode,
+        amount: Decimal,
+    ) -> Result<JournalLine, LedgerError> {
+        match direction {
+            InvoiceDirection::Purchase if amount < Decimal::ZERO => {
+                JournalLine::new(account, Decimal::ZERO, -amount)
+            }
+            InvoiceDirection::Purchase => JournalLine::new(account, amount, Decimal::ZERO),
+            InvoiceDirection::Sale if amount < Decimal::ZERO => {
+                JournalLine::new(account, -amount, Decimal::ZERO)
+            }
+            InvoiceDirection::Sale => JournalLine::new(account, Decimal::ZERO, amount),
+        }
+    }
+}
+
+// === Importer ===
+/// Turns e-invoices into entries for a ledger, using its customers,
+/// vendors and precision policy.
+pub struct EInvoiceImporter {
+    mapping: EInvoiceMapping,
+}
+
+impl EInvoiceImporter {
+    pub fn new(mapping: EInvoiceMapping) -> Self {
+        EInvoiceImporter { mapping }
+    }
+
+    /// Whether an entry posted to `ledger`, and not reversed, already
+    /// carries the invoice on its counter account.
+    pub fn is_imported(&self, ledger: &Ledger, invoice: &EInvoice) -> Result<bool, LedgerError> {
+        let (direction, party) = self.counterparty(ledger, invoice)?;
+        let counter_account = &self.mapping.accounts(&direction).counter_account;
+        let reference = invoice.reference();
+        Ok(ledger
+            .entries()
+            .iter()
+            .filter(|e| e.reverses.is_none() && e.reversed_by.is_none())
+            .flat_map(|e| &e.entry.lines)
+            .any(|l| {
+                l.account.as_str().starts_with(counter_account.as_str())
+                    && l.party.as_deref() == Some(party.as_str())
+                    && l.reference.as_deref() == Some(reference.as_str())
+            }))
+    }
+
+    pub fn import_xml(&self, ledger: &Ledger, xml: &str) -> Result<JournalEntry, LedgerError> {
+        let invoice = parse_einvoice(xml)?;
+        self.import(ledger, &invoice)
+    }
+
+    /// The entry of an invoice not yet posted to `ledger`. The partner is
+    /// the customer or vendor with its MST, when there is one, and a
+    /// purchase falls due after the vendor's payment terms.
+    pub fn import(&self, ledger: &Ledger, invoice: &EInvoice) -> Result<JournalEntry, LedgerError> {
+        if self.is_imported(ledger, invoice)? {
+            warn!(
+                "Duplicate e-invoice {}-{} from {}",
+                invoice.series, invoice.number, invoice.seller.tax_code
+            );
+            return Err(LedgerError::DuplicateInvoice(
+                invoice.series.clone(),
+                invoice.number.clone(),
+            ));
+        }
+        let mut entry = self.mapping.to_entry(invoice, ledger.precision())?;
+        let (direction, party) = self.counterparty(ledger, invoice)?;
+        let terms = match direction {
+            InvoiceDirection::Purchase => ledger.vendors().get(&party).map_or(0, |v| v.payment_terms_days),
+            InvoiceDirection::Sale => 0,
+        };
+        let due_date = invoice
+            .issue_date
+            .checked_add_days(Days::new(terms as u64))
+            .ok_or(LedgerError::InvalidDate)?;
+        let counter_account = &self.mapping.accounts(&direction).counter_account;
+        for line in entry.lines.iter_mut().filter(|l| l.account == *counter_account) {
+            line.party = Some(party.clone());
+            line.due_date = Some(due_date);
+        }
+        info!(
+            "Imported e-invoice {}-{} as draft entry",
+            invoice.series, invoice.number
+        );
+        Ok(entry)
+    }
+
+    /// The invoice's direction and the partner's code in the ledger: the
+    /// customer or vendor with the partner's MST, or the MST itself.
+    fn counterparty(&self, ledger: &Ledger, invoice: &EInvoice) -> Result<(InvoiceDirection, String), LedgerError> {
+        let direction = self.mapping.direction(invoice)?;
+        let code = match direction {
+            InvoiceDirection::Purchase => {
+                let tax_code = &invoice.seller.tax_code;
+                let vendor = ledger.vendors().vendors.iter().find(|v| v.tax_code == *tax_code);
+                vendor.map_or_else(|| tax_code.clone(), |v| v.code.clone())
+            }
+            InvoiceDirection::Sale => {
+                let tax_code = &invoice.buyer.tax_code;
+                let customer = ledger.customers().customers.iter().find(|c| c.tax_code == *tax_code);
+                customer.map_or_else(|| tax_code.clone(), |c| c.code.clone())
+            }
+        };
+        Ok((direction, code))
+    }
+}
+
+// === Raw XML schema ===
+// Field names mirror the XML element names of the schema.
+#[allow(non_snake_case, clippy::upper_case_acronyms)]
+mod raw {
+    use std::str::FromStr;
+
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use serde::de::IgnoredAny;
+    use serde::Deserialize;
+
+    use super::{EInvoice, EInvoiceItem, EInvoiceItemKind, EInvoiceParty};
+    use crate::LedgerError;
+
+    #[derive(Deserialize)]
+    pub struct HDon {
+        pub DLHDon: DLHDon,
+        pub MCCQT: Option<String>,
+        pub DSCKS: Option<DSCKS>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct DLHDon {
+        pub TTChung: TTChung,
+        pub NDHDon: NDHDon,
+    }
+
+    #[derive(Deserialize)]
+    pub struct TTChung {
+        pub KHMSHDon: String,
+        pub KHHDon: String,
+        pub SHDon: String,
+        pub NLap: NaiveDate,
+        pub DVTTe: Option<String>,
+        pub TGia: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct NDHDon {
+        pub NBan: Party,
+        pub NMua: Party,
+        pub DSHHDVu: DSHHDVu,
+        pub TToan: TToan,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Party {
+        pub Ten: Option<String>,
+        pub MST: Option<String>,
+        pub DChi: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct DSHHDVu {
+        #[serde(default)]
+        pub HHDVu: Vec<HHDVu>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct HHDVu {
+        pub TChat: Option<u8>,
+        pub MHHDVu: Option<String>,
+        pub THHDVu: String,
+        pub DVTinh: Option<String>,
+        pub SLuong: Option<String>,
+        pub DGia: Option<String>,
+        pub ThTien: Option<String>,
+        pub TSuat: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct TToan {
+        pub TgTCThue: String,
+        pub TgTThue: Option<String>,
+        pub TgTTTBSo: String,
+    }
+
+    #[derive(Deserialize)]
+    pub struct DSCKS {
+        pub NBan: Option<Signed>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Signed {
+        pub Signature: Option<IgnoredAny>,
+    }
+
+    /// Amounts are plain decimals with `.` as separator per the XML schema.
+    fn amount(field: &str, value: &str) -> Result<Decimal, LedgerError> {
+        Decimal::from_str(value.trim())
+            .map_err(|_| LedgerError::InvalidInvoice(format!("{} is not a number: {}", field, value)))
+    }
+
+    fn optional(field: &str, value: Option<String>) -> Result<Option<Decimal>, LedgerError> {
+        value.map(|v| amount(field, &v)).transpose()
+    }
+
+    impl Party {
+        fn into_party(self) -> EInvoiceParty {
+            EInvoiceParty {
+                name: self.Ten.unwrap_or_default(),
+                tax_code: self.MST.unwrap_or_default(),
+                address: self.DChi.unwrap_or_default(),
+            }
+        }
+    }
+
+    impl HDon {
+        pub fn into_invoice(self) -> Result<EInvoice, LedgerError> {
+            let general = self.DLHDon.TTChung;
+            let content = self.DLHDon.NDHDon;
+            let items = content
+                .DSHHDVu
+                .HHDVu
+                .into_iter()
+                .map(|i| {
+                    let kind = match i.TChat.unwrap_or(1) {
+                        1 => EInvoiceItemKind::Goods,
+                        2 => EInvoiceItemKind::Promotion,
+                        3 => EInvoiceItemKind::Discount,
+                        4 => EInvoiceItemKind::Note,
+                        other => {
+                            return Err(LedgerError::InvalidInvoice(format!(
+                                "unknown TChat {}",
+                                other
+                            )))
+                        }
+                    };
+                    Ok(EInvoiceItem {
+                        kind,
+                        code: i.MHHDVu,
+                        name: i.THHDVu,
+                        unit: i.DVTinh,
+                        quantity: optional("SLuong", i.SLuong)?,
+                        unit_price: optional("DGia", i.DGia)?,
+                        amount: optional("ThTien", i.ThTien)?.unwrap_or(Decimal::ZERO),
+                        vat_rate: i.TSuat,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let seller_signed = self
+                .DSCKS
+                .and_then(|s| s.NBan)
+                .map(|n| n.Signature.is_some())
+                .unwrap_or(false);
+
+            Ok(EInvoice {
+                template: general.KHMSHDon,
+                series: general.KHHDon,
+                number: general.SHDon,
+                issue_date: general.NLap,
+                currency: general.DVTTe.unwrap_or_else(|| "VND".to_string()),
+                exchange_rate: optional("TGia", general.TGia)?.unwrap_or(Decimal::ONE),
+                seller: content.NBan.into_party(),
+                buyer: content.NMua.into_party(),
+                items,
+                total_before_tax: amount("TgTCThue", &content.TToan.TgTCThue)?,
+                total_tax: optional("TgTThue", content.TToan.TgTThue)?.unwrap_or(Decimal::ZERO),
+                total_payment: amount("TgTTTBSo", &content.TToan.TgTTTBSo)?,
+                seller_signed,
+                authority_code: self.MCCQT,
+            })
+        }
+    }
+}
@@ -0,0 +1,489 @@
+// gl-core/src/events/mod.rs
+
+//! Domain events of the ledger.
+//!
+//! The ordered event stream is the source of truth: balances and posted
+//! entries are a projection rebuilt by applying the events in sequence.
+//! Snapshots of the projection, taken when a period closes, let `state_at`
+//! start part-way through the stream instead of replaying it from the first
+//! event; each is checked against the replay when the ledger is loaded.
+//!
+//! Every event is also recorded in the audit trail with the same payload
+//! and time, and a ledger attached to its trail must agree with it event by
+//! event, so an entry edited in the ledger file is refused even though the
+//! stream still replays.
+//!
+//! Master data (customers, vendors, exchange rates, precision, recurring
+//! templates and users) is not event-sourced: it is stored as it is now,
+//! and its changes are only in the audit trail. `state_at` and
+//! `as_known_at` rebuild balances, entries and subledgers as they were, but
+//! with today's master data.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AccountBalance, AccountCode, AssetDisposal, AssetRecord, AuditAction, DepreciationCharge, FixedAsset, AuditHead, AuditLog, CustomerMaster, DraftAction, DraftEntry, EntryId, InvoiceAllocation, ItemMatch, Ledger, LedgerError, MatchId,
+    Period, PostedEntry, PrecisionPolicy, RateTable, RecurringTemplates, TrialBalance, UserDirectory, VendorInvoice, VendorMaster, VoucherFormat,
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum LedgerEvent {
+    EntryPosted { entry: PostedEntry },
+    EntryReversed { original: EntryId, reversal: PostedEntry, reason: String },
+    PeriodOpened { period: Period },
+    PeriodClosed { period: Period },
+    /// A vendor invoice posted to 331; `entry` is its journal entry.
+    VendorInvoiceRecorded { entry: PostedEntry, invoice: VendorInvoice },
+    /// A payment to a vendor and the invoices it settles.
+    VendorPaymentRecorded { entry: PostedEntry, vendor: String, allocations: Vec<InvoiceAllocation> },
+    /// A settlement matched to a charge on 131 or 331.
+    ItemsMatched { matched: ItemMatch },
+    ItemsUnmatched { id: MatchId },
+    /// A draft entry as it is after a step of its review.
+    DraftRecorded { draft: DraftEntry },
+    /// An entry generated from the recurring template `template`.
+    RecurringEntryPosted { entry: PostedEntry, template: String },
+    /// An asset card added to the fixed asset register.
+    AssetRegistered { asset: FixedAsset },
+    /// A month of depreciation and what it charged to each asset.
+    DepreciationPosted { entry: PostedEntry, charges: Vec<DepreciationCharge> },
+    /// A disposal with the depreciation of its last days, if any.
+    AssetDisposed { entry: PostedEntry, disposal: AssetDisposal, charge: Option<DepreciationCharge> },
+}
+
+impl LedgerEvent {
+    fn audit_action(&self) -> AuditAction {
+        match self {
+            LedgerEvent::EntryPosted { .. } => AuditAction::EntryPosted,
+            LedgerEvent::EntryReversed { .. } => AuditAction::EntryReversed,
+            LedgerEvent::PeriodOpened { .. } => AuditAction::PeriodOpened,
+            LedgerEvent::PeriodClosed { .. } => AuditAction::PeriodClosed,
+            LedgerEvent::VendorInvoiceRecorded { .. } => AuditAction::VendorInvoiceRecorded,
+            LedgerEvent::VendorPaymentRecorded { .. } => AuditAction::VendorPaymentRecorded,
+            LedgerEvent::ItemsMatched { .. } => AuditAction::ItemsMatched,
+            LedgerEvent::ItemsUnmatched { .. } => AuditAction::ItemsUnmatched,
+            LedgerEvent::DraftRecorded { draft } => match draft.last_action() {
+                DraftAction::Saved => AuditAction::DraftSaved,
+                DraftAction::Submitted => AuditAction::DraftSubmitted,
+                DraftAction::Approved => AuditAction::DraftApproved,
+                DraftAction::Rejected => AuditAction::DraftRejected,
+                DraftAction::Posted => AuditAction::DraftPosted,
+            },
+            LedgerEvent::RecurringEntryPosted { .. } => AuditAction::RecurringEntryPosted,
+            LedgerEvent::AssetRegistered { .. } => AuditAction::AssetRegistered,
+            LedgerEvent::DepreciationPosted { .. } => AuditAction::DepreciationPosted,
+            LedgerEvent::AssetDisposed { .. } => AuditAction::AssetDisposed,
+        }
+    }
+
+    /// Whether the audit trail records `action` for an event of the stream,
+    /// rather than for a change outside it.
+    fn is_event_action(action: &AuditAction) -> bool {
+        matches!(
+            action,
+            AuditAction::EntryPosted
+                | AuditAction::EntryReversed
+                | AuditAction::PeriodOpened
+                | AuditAction::PeriodClosed
+                | AuditAction::VendorInvoiceRecorded
+                | AuditAction::VendorPaymentRecorded
+                | AuditAction::ItemsMatched
+                | AuditAction::ItemsUnmatched
+                | AuditAction::DraftSaved
+                | AuditAction::DraftSubmitted
+                | AuditAction::DraftApproved
+                | AuditAction::DraftRejected
+                | AuditAction::DraftPosted
+                | AuditAction::RecurringEntryPosted
+                | AuditAction::AssetRegistered
+                | AuditAction::DepreciationPosted
+                | AuditAction::AssetDisposed
+        )
+    }
+
+    fn audit_payload(&self) -> serde_json::Value {
+        match self {
+            LedgerEvent::EntryPosted { entry } => serde_json::json!(entry),
+            LedgerEvent::EntryReversed { reversal, reason, .. } => {
+                serde_json::json!({ "reversal": reversal, "reason": reason })
+            }
+            LedgerEvent::PeriodOpened { period } | LedgerEvent::PeriodClosed { period } => {
+                serde_json::json!(period)
+            }
+            LedgerEvent::VendorInvoiceRecorded { entry, invoice } => {
+                serde_json::json!({ "entry": entry, "invoice": invoice })
+            }
+            LedgerEvent::VendorPaymentRecorded { entry, vendor, allocations } => {
+                serde_json::json!({ "entry": entry, "vendor": vendor, "allocations": allocations })
+            }
+            LedgerEvent::ItemsMatched { matched } => serde_json::json!(matched),
+            LedgerEvent::ItemsUnmatched { id } => serde_json::json!({ "id": id }),
+            LedgerEvent::DraftRecorded { draft } => serde_json::json!(draft),
+            LedgerEvent::RecurringEntryPosted { entry, template } => {
+                serde_json::json!({ "entry": entry, "template": template })
+            }
+            LedgerEvent::AssetRegistered { asset } => serde_json::json!(asset),
+            LedgerEvent::DepreciationPosted { entry, charges } => {
+                serde_json::json!({ "entry": entry, "charges": charges })
+            }
+            LedgerEvent::AssetDisposed { entry, disposal, charge } => {
+                serde_json::json!({ "entry": entry, "disposal": disposal, "charge": charge })
+            }
+        }
+    }
+}
+
+/// An event with its position in the stream, starting at 1.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RecordedEvent {
+    pub sequence: u64,
+    pub recorded_at: DateTime<Utc>,
+    pub event: LedgerEvent,
+}
+
+/// The projection after applying every event up to and including `sequence`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Snapshot {
+    pub sequence: u64,
+    pub balances: Vec<AccountBalance>,
+    pub entries: Vec<PostedEntry>,
+    #[serde(default)]
+    pub vendor_invoices: Vec<VendorInvoice>,
+    #[serde(default)]
+    pub item_matches: Vec<ItemMatch>,
+    #[serde(default)]
+    pub match_counter: MatchId,
+    #[serde(default)]
+    pub drafts: Vec<DraftEntry>,
+    #[serde(default)]
+    pub assets: Vec<AssetRecord>,
+}
+
+/// What is persisted of a ledger; the projection is rebuilt on load.
+#[derive(Serialize, Deserialize, Default)]
+pub(crate) struct StoredLedger {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    company: Option<String>,
+    #[serde(default)]
+    events: Vec<RecordedEvent>,
+    #[serde(default)]
+    snapshots: Vec<Snapshot>,
+    #[serde(default)]
+    voucher_format: VoucherFormat,
+    #[serde(default)]
+    customers: CustomerMaster,
+    #[serde(default)]
+    vendors: VendorMaster,
+    #[serde(default)]
+    rates: RateTable,
+    #[serde(default)]
+    precision: PrecisionPolicy,
+    #[serde(default)]
+    recurring: RecurringTemplates,
+    #[serde(default, skip_serializing_if = "UserDirectory::is_empty")]
+    users: UserDirectory,
+    /// Where the audit trail kept beside the ledger file must end.
+    #[serde(default)]
+    audit_head: AuditHead,
+}
+
+impl From<Ledger> for StoredLedger {
+    fn from(ledger: Ledger) -> Self {
+        StoredLedger {
+            company: ledger.company,
+            events: ledger.events,
+            snapshots: ledger.snapshots,
+            voucher_format: ledger.vouchers.format,
+            customers: ledger.customers,
+            vendors: ledger.vendors,
+            rates: ledger.rates,
+            precision: ledger.precision,
+            recurring: ledger.recurring,
+            users: ledger.users,
+            audit_head: ledger.audit.head(),
+        }
+    }
+}
+
+impl TryFrom<StoredLedger> for Ledger {
+    type Error = LedgerError;
+
+    fn try_from(stored: StoredLedger) -> Result<Self, Self::Error> {
+        let mut ledger = Ledger::rebuild(&stored.events, &stored.snapshots)?;
+        ledger.events = stored.events;
+        ledger.company = stored.company;
+        ledger.vouchers.format = stored.voucher_format;
+        ledger.customers = stored.customers;
+        ledger.vendors = stored.vendors;
+        ledger.rates = stored.rates;
+        ledger.precision = stored.precision;
+        ledger.recurring = stored.recurring;
+        ledger.users = stored.users;
+        ledger.snapshots = stored.snapshots;
+        ledger.audit = AuditLog::continuing(stored.audit_head);
+        Ok(ledger)
+    }
+}
+
+/// Net debit (debit - credit) per account.
+fn net_balances(balances: &[AccountBalance]) -> BTreeMap<AccountCode, Decimal> {
+    let mut net = BTreeMap::new();
+    for b in balances {
+        *net.entry(b.account.clone()).or_insert(Decimal::ZERO) += b.debit_balance - b.credit_balance;
+    }
+    net.retain(|_, v| !v.is_zero());
+    net
+}
+
+impl Ledger {
+    /// Appends an event to the stream, records it in the audit trail and
+    /// applies it to the projection.
+    pub(crate) fn emit(&mut self, event: LedgerEvent) {
+        let actor = self.actor().to_string();
+        let recorded_at = self.audit.record(&actor, event.audit_action(), &event.audit_payload()).timestamp;
+        self.apply_event(&event);
+        self.events.push(RecordedEvent { sequence: self.events.len() as u64 + 1, recorded_at, event });
+        if matches!(self.events.last(), Some(RecordedEvent { event: LedgerEvent::PeriodClosed { .. }, .. })) {
+            self.take_snapshot();
+        }
+    }
+
+    /// Checks the stream against the audit trail that recorded it: in
+    /// order, each event must have a record with its action, payload and
+    /// time. A trail that continues one not loaded covers the latest events.
+    pub(crate) fn check_trail(&self, audit: &AuditLog) -> Result<(), LedgerError> {
+        let records: Vec<_> = audit.events().iter().filter(|r| LedgerEvent::is_event_action(&r.action)).collect();
+        let from_genesis = audit.head().events == audit.events().len() as u64;
+        if records.len() > self.events.len() || (from_genesis && records.len() != self.events.len()) {
+            return Err(LedgerError::AuditChainBroken(
+                audit.head().events,
+                format!("the trail records {} ledger events but the ledger holds {}", records.len(), self.events.len()),
+            ));
+        }
+        let recorded = &self.events[self.events.len() - records.len()..];
+        for (record, recorded) in records.into_iter().zip(recorded) {
+            if record.action != recorded.event.audit_action()
+                || record.payload != recorded.event.audit_payload()
+                || record.timestamp != recorded.recorded_at
+            {
+                return Err(LedgerError::AuditChainBroken(
+                    record.sequence,
+                    format!("event {} of the ledger differs from its audit record", recorded.sequence),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_event(&mut self, event: &LedgerEvent) {
+        match event {
+            LedgerEvent::EntryPosted { entry } => self.apply_posted(entry),
+            LedgerEvent::EntryReversed { original, reversal, .. } => {
+                self.apply(&reversal.entry);
+                self.entries.push(reversal.clone());
+                if let Some(original) = self.entries.iter_mut().find(|e| e.id == *original) {
+                    original.reversed_by = Some(reversal.id);
+                    original.auto_reverse_on = None;
+                }
+                // A reversed invoice or payment no longer counts in the AP subledger
+                self.vendor_invoices.retain(|i| i.entry_id != *original);
+                for invoice in self.vendor_invoices.iter_mut() {
+                    invoice.payments.retain(|p| p.entry_id != *original);
+                }
+                self.item_matches.retain(|m| m.charge != *original && m.settlement != *original);
+                self.unapply_asset_entry(*original);
+            }
+            LedgerEvent::PeriodOpened { .. } | LedgerEvent::PeriodClosed { .. } => {}
+            LedgerEvent::VendorInvoiceRecorded { entry, invoice } => {
+                self.apply_posted(entry);
+                self.vendor_invoices.push(invoice.clone());
+            }
+            LedgerEvent::VendorPaymentRecorded { entry, vendor, allocations } => {
+                self.apply_posted(entry);
+                self.apply_vendor_payment(vendor, entry.id, allocations);
+            }
+            LedgerEvent::ItemsMatched { matched } => self.apply_match(matched.clone()),
+            LedgerEvent::ItemsUnmatched { id } => self.apply_unmatch(*id),
+            LedgerEvent::DraftRecorded { draft } => self.apply_draft(draft),
+            LedgerEvent::RecurringEntryPosted { entry, .. } => self.apply_posted(entry),
+            LedgerEvent::AssetRegistered { asset } => self.apply_asset_registered(asset),
+            LedgerEvent::DepreciationPosted { entry, charges } => {
+                self.apply_posted(entry);
+                self.apply_depreciation(entry.id, charges);
+            }
+            LedgerEvent::AssetDisposed { entry, disposal, charge } => {
+                self.apply_posted(entry);
+                self.apply_depreciation(entry.id, charge.as_slice());
+                self.apply_disposal(entry.id, disposal);
+            }
+        }
+    }
+
+    fn apply_posted(&mut self, entry: &PostedEntry) {
+        self.apply(&entry.entry);
+        if let Some(voucher) = &entry.entry.voucher {
+            self.vouchers.register(voucher);
+        }
+        self.entries.push(entry.clone());
+    }
+
+    /// Rebuilds a ledger by applying an event stream from the beginning.
+    pub fn replay(events: Vec<RecordedEvent>) -> Result<Ledger, LedgerError> {
+        let mut ledger = Ledger::rebuild(&events, &[])?;
+        ledger.events = events;
+        Ok(ledger)
+    }
+
+    /// The projection of `events`, checking each of `snapshots` against it
+    /// at the snapshot's sequence. The events themselves are not kept.
+    fn rebuild(events: &[RecordedEvent], snapshots: &[Snapshot]) -> Result<Ledger, LedgerError> {
+        let mut ledger = Ledger::new();
+        let mut snapshots = snapshots.iter().peekable();
+        while let Some(snapshot) = snapshots.next_if(|s| s.sequence == 0) {
+            check_snapshot(snapshot, &ledger)?;
+        }
+        for (i, recorded) in events.iter().enumerate() {
+            if recorded.sequence != i as u64 + 1 {
+                return Err(LedgerError::InvalidSequence(recorded.sequence));
+            }
+            ledger.apply_event(&recorded.event);
+            while let Some(snapshot) = snapshots.next_if(|s| s.sequence == recorded.sequence) {
+                check_snapshot(snapshot, &ledger)?;
+            }
+        }
+        if let Some(snapshot) = snapshots.next() {
+            return Err(LedgerError::ProjectionMismatch(format!(
+                "snapshot at event {} is out of order or past the end of the stream",
+                snapshot.sequence
+            )));
+        }
+        Ok(ledger)
+    }
+
+    /// The event stream in sequence order.
+    pub fn events(&self) -> &[RecordedEvent] {
+        &self.events
+    }
+
+    pub fn snapshots(&self) -> &[Snapshot] {
+        &self.snapshots
+    }
+
+    /// Sequence number of the last event, 0 for an empty ledger.
+    pub fn sequence(&self) -> u64 {
+        self.events.len() as u64
+    }
+
+    /// Stores the current projection so later replays can start from it.
+    pub fn take_snapshot(&mut self) -> u64 {
+        let sequence = self.sequence();
+        if self.snapshots.last().is_none_or(|s| s.sequence < sequence) {
+            self.snapshots.push(self.snapshot(sequence));
+        }
+        sequence
+    }
+
+    fn snapshot(&self, sequence: u64) -> Snapshot {
+        Snapshot {
+            sequence,
+            balances: self.balances.clone(),
+            entries: self.entries.clone(),
+            vendor_invoices: self.vendor_invoices.clone(),
+            item_matches: self.item_matches.clone(),
+            match_counter: self.match_counter,
+            drafts: self.drafts.clone(),
+            assets: self.assets.clone(),
+        }
+    }
+
+    /// The ledger as it was right after event `sequence` (0 for the empty
+    /// ledger), replayed from the latest snapshot at or before it. Master
+    /// data is today's (see the module documentation).
+    pub fn state_at(&self, sequence: u64) -> Result<Ledger, LedgerError> {
+        if sequence > self.sequence() {
+            return Err(LedgerError::InvalidSequence(sequence));
+        }
+        let mut state = Ledger::new();
+        state.company = self.company.clone();
+        state.vouchers.format = self.vouchers.format.clone();
+        state.customers = self.customers.clone();
+        state.vendors = self.vendors.clone();
+        state.rates = self.rates.clone();
+        state.precision = self.precision.clone();
+        state.recurring = self.recurring.clone();
+        state.users = self.users.clone();
+        let mut from = 0;
+        if let Some(snapshot) = self.snapshots.iter().rev().find(|s| s.sequence <= sequence) {
+            state.balances = snapshot.balances.clone();
+            state.entries = snapshot.entries.clone();
+            state.vendor_invoices = snapshot.vendor_invoices.clone();
+            state.item_matches = snapshot.item_matches.clone();
+            state.match_counter = snapshot.match_counter;
+            state.drafts = snapshot.drafts.clone();
+            state.assets = snapshot.assets.clone();
+            for voucher in state.entries.iter().filter_map(|e| e.entry.voucher.as_ref()) {
+                state.vouchers.register(voucher);
+            }
+            from = snapshot.sequence;
+        }
+        for recorded in &self.events[from as usize..sequence as usize] {
+            state.apply_event(&recorded.event);
+        }
+        state.events = self.events[..sequence as usize].to_vec();
+        Ok(state)
+    }
+
+    /// The ledger as it was known at `at` (posting time): only events
+    /// recorded at or before that instant are applied. Reports built from it
+    /// reproduce what was issued at that time, before later adjustments.
+    pub fn as_known_at(&self, at: DateTime<Utc>) -> Ledger {
+        let sequence = self.events.partition_point(|e| e.recorded_at <= at) as u64;
+        self.state_at(sequence).expect("sequence is within the stream")
+    }
+
+    /// Trial balance by transaction date as it was known at posting time `at`.
+    pub fn trial_balance_at(&self, as_of_date: NaiveDate, as_known_at: DateTime<Utc>) -> TrialBalance {
+        self.as_known_at(as_known_at).as_of_date(as_of_date)
+    }
+
+    /// Proves the projection is consistent: balances equal the sum of the
+    /// posted journal lines, a full replay of the stream reproduces them,
+    /// and every snapshot matches the replay at its sequence.
+    pub fn verify_projection(&self) -> Result<(), LedgerError> {
+        let mut journal = Ledger::new();
+        for posted in &self.entries {
+            journal.apply(&posted.entry);
+        }
+        if net_balances(&journal.balances) != net_balances(&self.balances) {
+            return Err(LedgerError::ProjectionMismatch(
+                "balances differ from the sum of the journal".to_string(),
+            ));
+        }
+
+        let replayed = Ledger::rebuild(&self.events, &self.snapshots)?;
+        if net_balances(&replayed.balances) != net_balances(&self.balances)
+            || replayed.entries.len() != self.entries.len()
+        {
+            return Err(LedgerError::ProjectionMismatch(
+                "replaying the event stream gives different balances".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A snapshot must hold exactly the projection replayed up to its sequence.
+fn check_snapshot(snapshot: &Snapshot, replayed: &Ledger) -> Result<(), LedgerError> {
+    let expected = serde_json::to_value(replayed.snapshot(snapshot.sequence)).expect("snapshot is serializable");
+    if serde_json::to_value(snapshot).expect("snapshot is serializable") != expected {
+        return Err(LedgerError::ProjectionMismatch(format!(
+            "snapshot at event {} differs from the replayed ledger",
+            snapshot.sequence
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,20 @@
+// gl-core/src/import/batch.rs
+
+use crate::{JournalEntry, LedgerError};
+
+/// Parses a batch of entries given either as a JSON array or as JSONL
+/// (one entry per line, blank lines ignored).
+pub fn parse_entry_batch(contents: &str) -> Result<Vec<JournalEntry>, LedgerError> {
+    if contents.trim_start().starts_with('[') {
+        return serde_json::from_str(contents).map_err(|e| LedgerError::InvalidImport(e.to_string()));
+    }
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| LedgerError::InvalidImport(format!("line {}: {}", i + 1, e)))
+        })
+        .collect()
+}
@@ -0,0 +1,33 @@
+// gl-core/src/import/csv.rs
+
+use std::io::Read;
+
+use super::{import_rows, ColumnMapping, ImportMode, ImportResult};
+use crate::LedgerError;
+
+/// Imports journal entries from CSV. The first record must be the header row.
+pub fn import_csv<R: Read>(
+    reader: R,
+    delimiter: u8,
+    mapping: &ColumnMapping,
+    mode: &ImportMode,
+) -> Result<ImportResult, LedgerError> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(reader);
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| LedgerError::InvalidImport(e.to_string()))?
+        .iter()
+        .map(|h| h.trim_start_matches('\u{feff}').to_string())
+        .collect();
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| LedgerError::InvalidImport(e.to_string()))?;
+        let line = record.position().map(|p| p.line() as usize).unwrap_or(0);
+        rows.push((line, record.iter().map(str::to_string).collect()));
+    }
+    import_rows(&headers, rows, mapping, mode)
+}
@@ -0,0 +1,407 @@
+// gl-core/src/import/mod.rs
+
+//! Bulk import of journal entries from tabular files.
+//!
+//! Rows are grouped by voucher number into `JournalEntry`s. The column
+//! mapping and the number/date parsing are shared by every tabular format.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::{AccountCode, ForeignAmount, JournalEntry, JournalLine, LedgerError, VoucherFormat, VoucherType};
+
+mod batch;
+mod csv;
+mod xlsx;
+pub use self::batch::parse_entry_batch;
+pub use self::csv::import_csv;
+pub use self::xlsx::import_xlsx;
+
+// === Column mapping ===
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum NumberFormat {
+    /// `1.000.000,50`: dot groups thousands, comma marks decimals.
+    Vietnamese,
+    /// `1000000.50`: no grouping, dot marks decimals.
+    Plain,
+}
+
+/// Header names of each field in the source file.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ColumnMapping {
+    pub voucher: String,
+    pub date: String,
+    pub account: String,
+    pub debit: String,
+    pub credit: String,
+    pub description: String,
+    /// Optional column with the customer or vendor code of the line.
+    #[serde(default = "default_party_column")]
+    pub party: String,
+    /// Optional column with the due date of an invoice line, in `date_format`.
+    #[serde(default = "default_due_date_column")]
+    pub due_date: String,
+    /// Optional column with the invoice reference of the line.
+    #[serde(default = "default_reference_column")]
+    pub reference: String,
+    /// Optional columns of a foreign-currency line: currency code, amount in
+    /// that currency and exchange rate; debit/credit stay in VND.
+    #[serde(default = "default_currency_column")]
+    pub currency: String,
+    #[serde(default = "default_foreign_amount_column")]
+    pub foreign_amount: String,
+    #[serde(default = "default_rate_column")]
+    pub rate: String,
+    pub number_format: NumberFormat,
+    /// chrono format string, `%d/%m/%Y` by default.
+    pub date_format: String,
+    /// When set, voucher numbers must follow this format (e.g. `PT2501-0001`),
+    /// match the voucher date and have no gaps within the file.
+    #[serde(default)]
+    pub voucher_format: Option<VoucherFormat>,
+}
+
+fn default_party_column() -> String {
+    "party".to_string()
+}
+
+fn default_due_date_column() -> String {
+    "due_date".to_string()
+}
+
+fn default_reference_column() -> String {
+    "reference".to_string()
+}
+
+fn default_currency_column() -> String {
+    "currency".to_string()
+}
+
+fn default_foreign_amount_column() -> String {
+    "foreign_amount".to_string()
+}
+
+fn default_rate_column() -> String {
+    "rate".to_string()
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        ColumnMapping {
+            voucher: "voucher".to_string(),
+            date: "date".to_string(),
+            account: "account".to_string(),
+            debit: "debit".to_string(),
+            credit: "credit".to_string(),
+            description: "description".to_string(),
+            party: default_party_column(),
+            due_date: default_due_date_column(),
+            reference: default_reference_column(),
+            currency: default_currency_column(),
+            foreign_amount: default_foreign_amount_column(),
+            rate: default_rate_column(),
+            number_format: NumberFormat::Vietnamese,
+            date_format: "%d/%m/%Y".to_string(),
+            voucher_format: None,
+        }
+    }
+}
+
+impl ColumnMapping {
+    /// Resolves header names to column indexes.
+    fn resolve(&self, headers: &[String]) -> Result<Columns, LedgerError> {
+        let find = |name: &str| {
+            headers
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(name))
+                .ok_or_else(|| LedgerError::InvalidImport(format!("missing column '{}'", name)))
+        };
+        Ok(Columns {
+            voucher: find(&self.voucher)?,
+            date: find(&self.date)?,
+            account: find(&self.account)?,
+            debit: find(&self.debit)?,
+            credit: find(&self.credit)?,
+            description: find(&self.description).ok(),
+            party: find(&self.party).ok(),
+            due_date: find(&self.due_date).ok(),
+            reference: find(&self.reference).ok(),
+            currency: match find(&self.currency) {
+                Ok(currency) => Some((currency, find(&self.foreign_amount)?, find(&self.rate)?)),
+                Err(_) => None,
+            },
+        })
+    }
+}
+
+struct Columns {
+    voucher: usize,
+    date: usize,
+    account: usize,
+    debit: usize,
+    credit: usize,
+    description: Option<usize>,
+    party: Option<usize>,
+    due_date: Option<usize>,
+    reference: Option<usize>,
+    /// Currency, foreign amount and rate columns.
+    currency: Option<(usize, usize, usize)>,
+}
+
+pub fn parse_amount(value: &str, format: &NumberFormat) -> Result<Decimal, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(Decimal::ZERO);
+    }
+    let normalized = match format {
+        NumberFormat::Vietnamese => value.replace('.', "").replace(',', "."),
+        NumberFormat::Plain => value.replace(',', ""),
+    };
+    Decimal::from_str(&normalized).map_err(|_| format!("invalid amount '{}'", value))
+}
+
+pub fn parse_date(value: &str, format: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), format)
+        .map_err(|_| format!("invalid date '{}' (expected {})", value, format))
+}
+
+// === Import results ===
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Import every valid voucher, report the others.
+    BestEffort,
+    /// Reject the whole file if any row or voucher is invalid.
+    AllOrNothing,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ImportError {
+    /// 1-based line (or spreadsheet row) number in the source file.
+    pub line: usize,
+    pub voucher: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.voucher {
+            Some(v) => write!(f, "line {} (voucher {}): {}", self.line, v, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ImportedVoucher {
+    pub voucher: String,
+    /// Line of the first row of the voucher.
+    pub line: usize,
+    pub entry: JournalEntry,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ImportResult {
+    pub vouchers: Vec<ImportedVoucher>,
+    pub errors: Vec<ImportError>,
+}
+
+impl ImportResult {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+struct PendingVoucher {
+    line: usize,
+    date: NaiveDate,
+    description: String,
+    lines: Vec<JournalLine>,
+    has_errors: bool,
+}
+
+/// Groups raw rows (line number, cells) into vouchers and validates them.
+pub fn import_rows<I>(
+    headers: &[String],
+    rows: I,
+    mapping: &ColumnMapping,
+    mode: &ImportMode,
+) -> Result<ImportResult, LedgerError>
+where
+    I: IntoIterator<Item = (usize, Vec<String>)>,
+{
+    let columns = mapping.resolve(headers)?;
+    let mut order: Vec<String> = Vec::new();
+    let mut pending: HashMap<String, PendingVoucher> = HashMap::new();
+    let mut result = ImportResult::default();
+
+    for (line, cells) in rows {
+        let cell = |i: usize| cells.get(i).map(|c| c.trim()).unwrap_or("");
+        if cells.iter().all(|c| c.trim().is_empty()) {
+            continue;
+        }
+        let voucher = cell(columns.voucher).to_string();
+        if voucher.is_empty() {
+            result.errors.push(ImportError {
+                line,
+                voucher: None,
+                message: "missing voucher number".to_string(),
+            });
+            continue;
+        }
+        let error = |message: String| ImportError {
+            line,
+            voucher: Some(voucher.clone()),
+            message,
+        };
+
+        let parsed = parse_date(cell(columns.date), &mapping.date_format).and_then(|date| {
+            let account = AccountCode::new(cell(columns.account)).map_err(|e| e.to_string())?;
+            let debit = parse_amount(cell(columns.debit), &mapping.number_format)?;
+            let credit = parse_amount(cell(columns.credit), &mapping.number_format)?;
+            let mut line = JournalLine::new(account, debit, credit).map_err(|e| e.to_string())?;
+            if let Some(party) = columns.party.map(cell).filter(|p| !p.is_empty()) {
+                line = line.with_party(party);
+            }
+            if let Some(due_date) = columns.due_date.map(cell).filter(|d| !d.is_empty()) {
+                line = line.with_due_date(parse_date(due_date, &mapping.date_format)?);
+            }
+            if let Some(reference) = columns.reference.map(cell).filter(|r| !r.is_empty()) {
+                line = line.with_reference(reference);
+            }
+            if let Some((currency, amount, rate)) = columns.currency.filter(|(c, _, _)| !cell(*c).is_empty()) {
+                let amount = parse_amount(cell(amount), &mapping.number_format)?;
+                let rate = parse_amount(cell(rate), &mapping.number_format)?;
+                let foreign = ForeignAmount::new(cell(currency), amount, rate).map_err(|e| e.to_string())?;
+                line = line.with_foreign(foreign).map_err(|e| e.to_string())?;
+            }
+            Ok((date, line))
+        });
+        let description = columns.description.map(cell).unwrap_or("").to_string();
+
+        if !pending.contains_key(&voucher) {
+            order.push(voucher.clone());
+        }
+        match parsed {
+            Ok((date, journal_line)) => {
+                let entry = pending.entry(voucher.clone()).or_insert_with(|| PendingVoucher {
+                    line,
+                    date,
+                    description: description.clone(),
+                    lines: vec![],
+                    has_errors: false,
+                });
+                if entry.has_errors {
+                    // Voucher is already rejected; its date may not be known
+                } else if entry.date != date {
+                    result.errors.push(error(format!(
+                        "date {} differs from voucher date {}",
+                        date, entry.date
+                    )));
+                    entry.has_errors = true;
+                } else {
+                    if entry.description.is_empty() {
+                        entry.description = description;
+                    }
+                    entry.lines.push(journal_line);
+                }
+            }
+            Err(message) => {
+                result.errors.push(error(message));
+                pending
+                    .entry(voucher.clone())
+                    .or_insert_with(|| PendingVoucher {
+                        line,
+                        date: NaiveDate::MIN,
+                        description: description.clone(),
+                        lines: vec![],
+                        has_errors: true,
+                    })
+                    .has_errors = true;
+            }
+        }
+    }
+
+    for voucher in order {
+        let pending = pending.remove(&voucher).expect("voucher was recorded");
+        if pending.has_errors {
+            continue;
+        }
+        let entry = JournalEntry::new(pending.date, pending.lines, pending.description).and_then(|entry| {
+            match &mapping.voucher_format {
+                Some(format) => {
+                    let parsed = format.parse(&voucher, entry.date)?;
+                    if !parsed.matches_date(entry.date) {
+                        return Err(LedgerError::VoucherPeriodMismatch(voucher.clone(), entry.date));
+                    }
+                    Ok(entry.with_voucher(parsed))
+                }
+                None => Ok(entry),
+            }
+        });
+        match entry {
+            Ok(entry) => result.vouchers.push(ImportedVoucher {
+                voucher,
+                line: pending.line,
+                entry,
+            }),
+            Err(e) => result.errors.push(ImportError {
+                line: pending.line,
+                voucher: Some(voucher),
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    if mapping.voucher_format.is_some() {
+        check_voucher_gaps(&mut result);
+    }
+
+    result.errors.sort_by_key(|e| e.line);
+    if *mode == ImportMode::AllOrNothing && !result.errors.is_empty() {
+        warn!(
+            "Rejecting import: {} error(s), {} voucher(s) discarded",
+            result.errors.len(),
+            result.vouchers.len()
+        );
+        result.vouchers.clear();
+    } else {
+        info!("Imported {} voucher(s)", result.vouchers.len());
+    }
+    Ok(result)
+}
+
+/// Rejects vouchers that leave a gap after the previous number of their
+/// series in the file; the first number of a series is checked on posting.
+fn check_voucher_gaps(result: &mut ImportResult) {
+    let mut series: HashMap<(VoucherType, i32, u32), Vec<(u32, usize)>> = HashMap::new();
+    for (i, imported) in result.vouchers.iter().enumerate() {
+        if let Some(v) = &imported.entry.voucher {
+            series.entry((v.voucher_type, v.year, v.month)).or_default().push((v.sequence, i));
+        }
+    }
+    let mut rejected = Vec::new();
+    for numbers in series.values_mut() {
+        numbers.sort();
+        for pair in numbers.windows(2) {
+            let ((previous, _), (sequence, index)) = (pair[0], pair[1]);
+            if sequence != previous + 1 {
+                rejected.push(index);
+            }
+        }
+    }
+    rejected.sort_unstable();
+    for index in rejected.into_iter().rev() {
+        let imported = result.vouchers.remove(index);
+        result.errors.push(ImportError {
+            line: imported.line,
+            message: format!("voucher number gap before {}", imported.voucher),
+            voucher: Some(imported.voucher),
+        });
+    }
+}
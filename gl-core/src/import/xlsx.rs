@@ -0,0 +1,75 @@
+// gl-core/src/import/xlsx.rs
+
+use std::io::{Read, Seek};
+
+use calamine::{open_workbook_from_rs, Data, DataType, Reader, Xlsx, XlsxError};
+use rust_decimal::Decimal;
+
+use super::{import_rows, ColumnMapping, ImportMode, ImportResult, NumberFormat};
+use crate::LedgerError;
+
+/// Imports journal entries from an Excel worksheet (the first one if `sheet`
+/// is `None`). The first non-empty row must be the header row.
+pub fn import_xlsx<R: Read + Seek>(
+    reader: R,
+    sheet: Option<&str>,
+    mapping: &ColumnMapping,
+    mode: &ImportMode,
+) -> Result<ImportResult, LedgerError> {
+    let mut workbook: Xlsx<R> = open_workbook_from_rs(reader)
+        .map_err(|e: XlsxError| LedgerError::Spreadsheet(e.to_string()))?;
+    let name = match sheet {
+        Some(name) => name.to_string(),
+        None => workbook
+            .sheet_names()
+            .first()
+            .cloned()
+            .ok_or_else(|| LedgerError::InvalidImport("workbook has no worksheet".to_string()))?,
+    };
+    let range = workbook
+        .worksheet_range(&name)
+        .map_err(|e| LedgerError::Spreadsheet(e.to_string()))?;
+
+    // Spreadsheet row numbers are 1-based and account for leading empty rows
+    let first_row = range.start().map(|(row, _)| row as usize).unwrap_or(0);
+    let mut rows = range
+        .rows()
+        .enumerate()
+        .map(|(i, row)| {
+            let cells: Vec<String> = row.iter().map(|c| cell_text(c, mapping)).collect();
+            (first_row + i + 1, cells)
+        })
+        .skip_while(|(_, cells)| cells.iter().all(|c| c.is_empty()));
+    let headers = match rows.next() {
+        Some((_, headers)) => headers,
+        None => return Err(LedgerError::InvalidImport(format!("worksheet '{}' is empty", name))),
+    };
+    import_rows(&headers, rows, mapping, mode)
+}
+
+/// Renders a cell as the text the CSV importer would have seen, so numbers
+/// and dates stored natively in Excel go through the same parsing rules. A
+/// float with no decimal value (NaN, infinite, out of range) keeps its own
+/// text, which the row's parsing then rejects with the row number.
+fn cell_text(cell: &Data, mapping: &ColumnMapping) -> String {
+    match cell {
+        Data::Empty => String::new(),
+        Data::String(s) => s.clone(),
+        Data::Int(i) => i.to_string(),
+        Data::Float(f) => {
+            let Ok(amount) = Decimal::try_from(*f) else {
+                return f.to_string();
+            };
+            let amount = amount.normalize().to_string();
+            match mapping.number_format {
+                NumberFormat::Vietnamese => amount.replace('.', ","),
+                NumberFormat::Plain => amount,
+            }
+        }
+        Data::DateTime(_) | Data::DateTimeIso(_) => match cell.as_date() {
+            Some(date) => date.format(&mapping.date_format).to_string(),
+            None => cell.to_string(),
+        },
+        other => other.to_string(),
+    }
+}
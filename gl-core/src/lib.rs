@@ -1,11 +1,27 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 mod period;
 pub use period::{Period, PeriodStatus};
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use thiserror::Error;
-use tracing::{info, debug, error};
+use tracing::{info, debug};
+mod asset;
+mod audit;
+mod auth;
 mod coa;
+mod company;
+mod currency;
+mod document;
+mod events;
+mod einvoice;
+mod import;
+mod recurring;
+mod report;
+mod subledger;
+mod voucher;
+mod workflow;
 // === Errors ===
 #[derive(Error, Debug)]
 pub enum LedgerError {
@@ -17,15 +33,140 @@ pub enum LedgerError {
     NegativeAmount(Decimal),
     #[error("Date must be a valid date")]
     InvalidDate,
+    #[error("Invalid e-invoice: {0}")]
+    InvalidInvoice(String),
+    #[error("E-invoice already imported: series {0}, number {1}")]
+    DuplicateInvoice(String, String),
+    #[error("Invalid import file: {0}")]
+    InvalidImport(String),
+    #[error("Spreadsheet error: {0}")]
+    Spreadsheet(String),
+    #[error("Account {0} is not in the chart of accounts")]
+    UnknownAccount(String),
+    #[error("No open accounting period for {0}")]
+    DateNotInOpenPeriod(NaiveDate),
+    #[error("Journal entry {0} not found")]
+    EntryNotFound(u64),
+    #[error("Journal entry {0} is already reversed")]
+    AlreadyReversed(u64),
+    #[error("Journal entry {0} is a reversal and cannot be reversed")]
+    ReversalOfReversal(u64),
+    #[error("Account {0} already exists in the chart of accounts")]
+    DuplicateAccount(String),
+    #[error("Audit trail broken at event {0}: {1}")]
+    AuditChainBroken(u64, String),
+    #[error("Audit trail I/O error: {0}")]
+    AuditIo(String),
+    #[error("Invalid event sequence number: {0}")]
+    InvalidSequence(u64),
+    #[error("Ledger projection does not match its event stream: {0}")]
+    ProjectionMismatch(String),
+    #[error("Invalid voucher number: {0}")]
+    InvalidVoucherNumber(String),
+    #[error("Voucher {0} is already used")]
+    DuplicateVoucher(String),
+    #[error("Voucher {0} is out of sequence, expected {1}")]
+    VoucherOutOfSequence(String, String),
+    #[error("Voucher {0} does not belong to the period of {1}")]
+    VoucherPeriodMismatch(String, NaiveDate),
+    #[error("Cannot build document: {0}")]
+    InvalidDocument(String),
+    #[error("PDF error: {0}")]
+    Pdf(String),
+    #[error("Invalid customer: {0}")]
+    InvalidCustomer(String),
+    #[error("Customer {0} already exists")]
+    DuplicateCustomer(String),
+    #[error("Customer {0} is not in the customer master")]
+    UnknownCustomer(String),
+    #[error("Line on account {0} needs a party (customer or vendor) reference")]
+    MissingParty(String),
+    #[error("Credit limit of {0} exceeded: limit {1}, balance would be {2}")]
+    CreditLimitExceeded(String, Decimal, Decimal),
+    #[error("Subledger of {0} does not reconcile: subledger {1} != ledger {2}")]
+    SubledgerMismatch(String, Decimal, Decimal),
+    #[error("Invalid vendor: {0}")]
+    InvalidVendor(String),
+    #[error("Vendor {0} already exists")]
+    DuplicateVendor(String),
+    #[error("Vendor {0} is not in the vendor master")]
+    UnknownVendor(String),
+    #[error("Invalid vendor invoice: {0}")]
+    InvalidVendorInvoice(String),
+    #[error("Invoice {1} of vendor {0} is already recorded")]
+    DuplicateVendorInvoice(String, String),
+    #[error("Invoice {1} of vendor {0} is not recorded")]
+    UnknownVendorInvoice(String, String),
+    #[error("Payment of {1} to invoice {0} exceeds its outstanding amount {2}")]
+    PaymentExceedsInvoice(String, Decimal, Decimal),
+    #[error("Allocations total {0} but the payment is only {1}")]
+    AllocationExceedsPayment(Decimal, Decimal),
+    #[error("Cannot export report: {0}")]
+    Export(String),
+    #[error("Account {0} is not a control account with open items (131, 331)")]
+    NotAControlAccount(String),
+    #[error("Entry {0} has no open item of {1}")]
+    UnknownOpenItem(u64, String),
+    #[error("Invalid match: {0}")]
+    InvalidMatch(String),
+    #[error("Match of {0} exceeds the open amount {1}")]
+    MatchExceedsOpenAmount(Decimal, Decimal),
+    #[error("Match {0} not found")]
+    UnknownMatch(u64),
+    #[error("Entry {0} has matched settlements; unmatch them first")]
+    ItemHasMatches(u64),
+    #[error("Invalid currency: {0}")]
+    InvalidCurrency(String),
+    #[error("Line on account {0} books {1} VND but its foreign amount converts to {2}")]
+    CurrencyAmountMismatch(String, Decimal, Decimal),
+    #[error("No exchange rate for {0} on or before {1}")]
+    MissingExchangeRate(String, NaiveDate),
+    #[error("Amount {1} has more decimal places than {2} allowed for {0}")]
+    PrecisionExceeded(String, Decimal, u32),
+    #[error("Invalid allocation: {0}")]
+    InvalidAllocation(String),
+    #[error("Invalid company: {0}")]
+    InvalidCompany(String),
+    #[error("Company {0} already exists")]
+    DuplicateCompany(String),
+    #[error("Company {0} is not in the registry")]
+    UnknownCompany(String),
+    #[error("Ledger belongs to company {1}, not {0}")]
+    CompanyMismatch(String, String),
+    #[error("Invalid user: {0}")]
+    InvalidUser(String),
+    #[error("User {0} is not in the user directory")]
+    UnknownUser(String),
+    #[error("User {0} may not {1}")]
+    PermissionDenied(String, Permission),
+    #[error("Draft {0} not found")]
+    DraftNotFound(u64),
+    #[error("Draft {0} is {1:?}, it must be {2:?}")]
+    InvalidDraftStatus(u64, DraftStatus, DraftStatus),
+    #[error("Draft {0} was prepared by {1}, who cannot also approve it")]
+    SelfApproval(u64, String),
+    #[error("Rejecting draft {0} needs a comment")]
+    MissingRejectionComment(u64),
+    #[error("Invalid recurring template: {0}")]
+    InvalidTemplate(String),
+    #[error("Invalid fixed asset: {0}")]
+    InvalidAsset(String),
+    #[error("Fixed asset {0} is already registered")]
+    DuplicateAsset(String),
+    #[error("Fixed asset {0} is not in the register")]
+    UnknownAsset(String),
+    #[error("Useful life of {0} is {1} years, outside the {2}-{3} years of its class (Circular 45/2013)")]
+    UsefulLifeOutOfRange(String, u32, u32, u32),
 }
 
 // === Account Code ===
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AccountCode(pub String);
 
 impl AccountCode {
     pub fn new(code: &str) -> Result<Self, LedgerError> {
-        if code.is_empty() || !code.chars().all(|c| c.is_digit(10)) {
+        if code.is_empty() || !code.chars().all(|c| c.is_ascii_digit()) {
             Err(LedgerError::InvalidAccountCode(code.to_string()))
         } else {
             Ok(AccountCode(code.to_string()))
@@ -39,10 +180,26 @@ impl AccountCode {
 
 // === Journal Line ===
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct JournalLine {
     pub account: AccountCode,
     pub debit: Decimal,
     pub credit: Decimal,
+    /// Customer or vendor code, for lines on subledger control accounts (131, 331).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub party: Option<String>,
+    /// When the amount falls due, for invoices on 131 and 331; aging uses
+    /// the entry date when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
+    /// Invoice the line concerns: its number on the invoice, the invoice
+    /// paid on a payment. Used to match open items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// Original currency amount and rate of a line not in VND; `debit` and
+    /// `credit` are always VND.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub foreign: Option<ForeignAmount>,
 }
 
 impl JournalLine {
@@ -53,16 +210,52 @@ impl JournalLine {
         if credit < Decimal::ZERO {
             return Err(LedgerError::NegativeAmount(credit));
         }
-        Ok(JournalLine { account, debit, credit })
+        Ok(JournalLine {
+            account,
+            debit,
+            credit,
+            party: None,
+            due_date: None,
+            reference: None,
+            foreign: None,
+        })
+    }
+
+    pub fn with_party(mut self, party: &str) -> Self {
+        self.party = Some(party.to_string());
+        self
+    }
+
+    pub fn with_due_date(mut self, due_date: NaiveDate) -> Self {
+        self.due_date = Some(due_date);
+        self
+    }
+
+    pub fn with_reference(mut self, reference: &str) -> Self {
+        self.reference = Some(reference.to_string());
+        self
+    }
+
+    /// Books the line in a foreign currency; the VND amount must match.
+    pub fn with_foreign(mut self, foreign: ForeignAmount) -> Result<Self, LedgerError> {
+        self.foreign = Some(foreign);
+        currency::check_line(&self)?;
+        Ok(self)
     }
 }
 
 // === Journal Entry ===
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct JournalEntry {
     pub date: NaiveDate,
     pub lines: Vec<JournalLine>,
     pub description: String,
+    /// Voucher type and number (chứng từ), checked to be the next of its
+    /// series on posting. An entry without one is posted without a number;
+    /// `Ledger::post_voucher` assigns the next number of a type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voucher: Option<Voucher>,
 }
 
 impl JournalEntry {
@@ -77,6 +270,9 @@ impl JournalEntry {
             return Err(LedgerError::InvalidDate);
         }
 
+        for line in &lines {
+            currency::check_line(line)?;
+        }
         let total_debit = lines.iter().map(|l| l.debit).sum::<Decimal>();
         let total_credit = lines.iter().map(|l| l.credit).sum::<Decimal>();
 
@@ -85,7 +281,12 @@ impl JournalEntry {
         }
 
         info!("Created balanced journal entry: {:?}", description);
-        Ok(JournalEntry { date, lines, description })
+        Ok(JournalEntry { date, lines, description, voucher: None })
+    }
+
+    pub fn with_voucher(mut self, voucher: Voucher) -> Self {
+        self.voucher = Some(voucher);
+        self
     }
 
     pub fn is_balanced(&self) -> bool {
@@ -97,7 +298,15 @@ impl JournalEntry {
 }
 
 // === Ledger ===
+/// A failing entry of a batch, identified by its position in the batch.
+#[derive(Debug)]
+pub struct BatchError {
+    pub index: usize,
+    pub error: LedgerError,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AccountBalance {
     pub account: AccountCode,
     pub debit_balance: Decimal,
@@ -105,26 +314,457 @@ pub struct AccountBalance {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct TrialBalance {
     pub balances: Vec<AccountBalance>,
     pub total_debit: Decimal,
     pub total_credit: Decimal,
 }
 
+/// Sequential identifier of a posted entry, starting at 1.
+pub type EntryId = u64;
+
+/// An entry as recorded in the ledger, with its reversal links.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PostedEntry {
+    pub id: EntryId,
+    pub entry: JournalEntry,
+    /// Set on a reversal entry: the entry it reverses.
+    pub reverses: Option<EntryId>,
+    /// Set on a reversed entry: the reversal that cancels it.
+    pub reversed_by: Option<EntryId>,
+    /// Accruals are reversed automatically on this date (first day of the next period).
+    pub auto_reverse_on: Option<NaiveDate>,
+}
+
+/// Actor recorded in the audit trail when none is set.
+pub const SYSTEM_ACTOR: &str = "system";
+
+/// Projection of the event stream (see the `events` module): only events,
+/// snapshots and the audit trail are serialized, balances and entries are
+/// rebuilt on load.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(try_from = "events::StoredLedger", into = "events::StoredLedger")]
 pub struct Ledger {
+    /// Company whose books these are; see the `company` module.
+    company: Option<String>,
     balances: Vec<AccountBalance>,
+    entries: Vec<PostedEntry>,
+    events: Vec<RecordedEvent>,
+    snapshots: Vec<Snapshot>,
+    vouchers: VoucherNumbering,
+    customers: CustomerMaster,
+    vendors: VendorMaster,
+    rates: RateTable,
+    precision: PrecisionPolicy,
+    vendor_invoices: Vec<VendorInvoice>,
+    item_matches: Vec<ItemMatch>,
+    /// Last match id handed out; ids are not reused after unmatching.
+    match_counter: MatchId,
+    /// Entries under review; see the `workflow` module.
+    drafts: Vec<DraftEntry>,
+    /// Templates of recurring entries; see the `recurring` module.
+    recurring: RecurringTemplates,
+    /// Fixed asset register; see the `asset` module.
+    assets: Vec<AssetRecord>,
+    audit: AuditLog,
+    actor: Option<String>,
+    /// Roles enforced on the actor; nobody is restricted when empty.
+    users: UserDirectory,
 }
 
 impl Ledger {
     pub fn new() -> Self {
-        Ledger { balances: vec![] }
+        Ledger {
+            company: None,
+            balances: vec![],
+            entries: vec![],
+            events: vec![],
+            snapshots: vec![],
+            vouchers: VoucherNumbering::default(),
+            customers: CustomerMaster::new(),
+            vendors: VendorMaster::new(),
+            rates: RateTable::new(),
+            precision: PrecisionPolicy::default(),
+            vendor_invoices: vec![],
+            item_matches: vec![],
+            match_counter: 0,
+            drafts: vec![],
+            recurring: RecurringTemplates::new(),
+            assets: vec![],
+            audit: AuditLog::new(),
+            actor: None,
+            users: UserDirectory::new(),
+        }
+    }
+
+    /// Tags the ledger as the books of company `id`; the books of one
+    /// company cannot be handed to another.
+    pub fn for_company(mut self, id: &str) -> Result<Self, LedgerError> {
+        if let Some(owner) = self.company.as_deref().filter(|owner| *owner != id) {
+            return Err(LedgerError::CompanyMismatch(id.to_string(), owner.to_string()));
+        }
+        self.company = Some(id.to_string());
+        Ok(self)
+    }
+
+    pub fn company(&self) -> Option<&str> {
+        self.company.as_deref()
+    }
+
+    /// Continues the ledger's audit trail, loaded from where it is kept. The
+    /// trail must end exactly at the head the ledger recorded, so a trail
+    /// cut short, replaced or missing is refused, and must record the
+    /// ledger's events as they are, so an edited ledger file is refused too.
+    pub fn with_audit_log(mut self, audit: AuditLog) -> Result<Self, LedgerError> {
+        audit.check_head(&self.audit.head())?;
+        self.check_trail(&audit)?;
+        self.audit = audit;
+        Ok(self)
+    }
+
+    /// Uses a voucher number format other than the default `PT2501-0001`.
+    pub fn with_voucher_format(mut self, format: VoucherFormat) -> Self {
+        self.vouchers.format = format;
+        self
+    }
+
+    pub fn voucher_numbering(&self) -> &VoucherNumbering {
+        &self.vouchers
+    }
+
+    /// Starts with a customer master; lines on 131 then need a known customer.
+    pub fn with_customers(mut self, customers: CustomerMaster) -> Self {
+        self.customers = customers;
+        self
+    }
+
+    pub fn customers(&self) -> &CustomerMaster {
+        &self.customers
+    }
+
+    /// Starts with a vendor master; lines on 331 then need a known vendor.
+    pub fn with_vendors(mut self, vendors: VendorMaster) -> Self {
+        self.vendors = vendors;
+        self
+    }
+
+    pub fn vendors(&self) -> &VendorMaster {
+        &self.vendors
     }
 
-    pub fn post_entry(&mut self, entry: JournalEntry) -> Result<(), LedgerError> {
+    /// Sets who is acting on the ledger, for the audit trail.
+    pub fn set_actor(&mut self, actor: &str) {
+        self.actor = Some(actor.to_string());
+    }
+
+    pub fn actor(&self) -> &str {
+        self.actor.as_deref().unwrap_or(SYSTEM_ACTOR)
+    }
+
+    pub fn audit_log(&self) -> &AuditLog {
+        &self.audit
+    }
+
+    fn audit<T: Serialize>(&mut self, action: AuditAction, payload: &T) {
+        let actor = self.actor().to_string();
+        self.audit.record(&actor, action, payload);
+    }
+
+    /// Posts an entry; a voucher number it carries must be the next of its series.
+    pub fn post_entry(&mut self, entry: JournalEntry) -> Result<EntryId, LedgerError> {
+        self.authorize(Permission::PostEntry)?;
+        self.post_approved(entry)
+    }
+
+    /// Posts an entry whose posting the caller has already authorized.
+    pub(crate) fn post_approved(&mut self, entry: JournalEntry) -> Result<EntryId, LedgerError> {
+        let posted = self.next_entry(entry, None, None)?;
+        let id = posted.id;
+        info!("Successfully posted entry: {}", posted.entry.description);
+        self.emit(LedgerEvent::EntryPosted { entry: posted });
+        Ok(id)
+    }
+
+    /// Posts an entry under the next number of a voucher type.
+    pub fn post_voucher(
+        &mut self,
+        entry: JournalEntry,
+        voucher_type: VoucherType,
+    ) -> Result<EntryId, LedgerError> {
+        let voucher = self.vouchers.next(voucher_type, entry.date);
+        self.post_entry(entry.with_voucher(voucher))
+    }
+
+    fn next_entry(
+        &self,
+        entry: JournalEntry,
+        reverses: Option<EntryId>,
+        auto_reverse_on: Option<NaiveDate>,
+    ) -> Result<PostedEntry, LedgerError> {
         debug!("Posting entry: {:?}", entry.description);
+        for line in &entry.lines {
+            currency::check_line(line)?;
+        }
+        self.precision.check_entry(&entry)?;
+        if let Some(voucher) = &entry.voucher {
+            self.vouchers.check(voucher, entry.date)?;
+        }
+        // Reversals only give back what was posted, so they skip credit limits
+        self.check_parties(&entry, &BTreeMap::new(), reverses.is_none())?;
+        Ok(PostedEntry {
+            id: self.entries.len() as EntryId + 1,
+            entry,
+            reverses,
+            reversed_by: None,
+            auto_reverse_on,
+        })
+    }
+
+    /// Checks the customer and vendor references of an entry; returns the
+    /// change of each customer's receivable (see `CustomerMaster::check_entry`).
+    fn check_parties(
+        &self,
+        entry: &JournalEntry,
+        pending: &BTreeMap<String, Decimal>,
+        check_limit: bool,
+    ) -> Result<BTreeMap<String, Decimal>, LedgerError> {
+        self.vendors.check_entry(entry)?;
+        let balance_of = |code: &str| self.party_balance(RECEIVABLE_ACCOUNT, code);
+        self.customers.check_entry(entry, balance_of, pending, check_limit)
+    }
+
+    /// Net debit of a party's lines on a control account.
+    fn party_balance(&self, account: &str, party: &str) -> Decimal {
+        self.entries
+            .iter()
+            .flat_map(|e| &e.entry.lines)
+            .filter(|l| l.account.as_str().starts_with(account) && l.party.as_deref() == Some(party))
+            .map(|l| l.debit - l.credit)
+            .sum()
+    }
+
+    pub fn get_entry(&self, id: EntryId) -> Option<&PostedEntry> {
+        self.entries.iter().find(|e| e.id == id)
+    }
+
+    /// Posts a mirror entry (debit and credit swapped on every line) that
+    /// cancels entry `id`, and links the two entries.
+    pub fn reverse_entry(
+        &mut self,
+        id: EntryId,
+        date: NaiveDate,
+        reason: &str,
+    ) -> Result<EntryId, LedgerError> {
+        self.authorize(Permission::ReverseEntry)?;
+        let original = self.get_entry(id).ok_or(LedgerError::EntryNotFound(id))?;
+        if original.reverses.is_some() {
+            return Err(LedgerError::ReversalOfReversal(id));
+        }
+        if original.reversed_by.is_some() {
+            return Err(LedgerError::AlreadyReversed(id));
+        }
+        if date < original.entry.date {
+            return Err(LedgerError::InvalidDate);
+        }
+        if let Some(invoice) = self.vendor_invoices.iter().find(|i| i.entry_id == id && !i.payments.is_empty()) {
+            return Err(LedgerError::InvalidVendorInvoice(format!(
+                "invoice {} of {} has payments; reverse them first",
+                invoice.number, invoice.vendor
+            )));
+        }
+        if self.item_matches.iter().any(|m| m.charge == id) {
+            return Err(LedgerError::ItemHasMatches(id));
+        }
+
+        let lines = original
+            .entry
+            .lines
+            .iter()
+            .map(|l| JournalLine { debit: l.credit, credit: l.debit, ..l.clone() })
+            .collect();
+        let description = format!("Đảo bút toán #{}: {}", id, reason);
+        let mirror = JournalEntry::new(date, lines, description)?;
+
+        let reversal = self.next_entry(mirror, Some(id), None)?;
+        let reversal_id = reversal.id;
+        self.emit(LedgerEvent::EntryReversed { original: id, reversal, reason: reason.to_string() });
+        info!("Entry {} reversed by entry {}", id, reversal_id);
+        Ok(reversal_id)
+    }
+
+    /// Posts an accrual that reverses itself on the first day of the period
+    /// following the one containing its date. Both days must be in an open
+    /// period.
+    pub fn post_accrual(
+        &mut self,
+        entry: JournalEntry,
+        periods: &PeriodManager,
+    ) -> Result<EntryId, LedgerError> {
+        self.authorize(Permission::PostEntry)?;
+        let period = periods
+            .periods
+            .iter()
+            .filter(|p| p.is_open() && p.contains_date(entry.date))
+            .max_by_key(|p| p.start_date)
+            .ok_or(LedgerError::DateNotInOpenPeriod(entry.date))?;
+        let reverse_on = period.end_date.succ_opt().ok_or(LedgerError::InvalidDate)?;
+        if !periods.is_date_in_open_period(reverse_on) {
+            return Err(LedgerError::DateNotInOpenPeriod(reverse_on));
+        }
+        let posted = self.next_entry(entry, None, Some(reverse_on))?;
+        let id = posted.id;
+        self.emit(LedgerEvent::EntryPosted { entry: posted });
+        Ok(id)
+    }
 
-        for line in entry.lines {
+    // === Audited period and CoA changes ===
+    pub fn open_period(&mut self, periods: &mut PeriodManager, period: Period) -> Result<(), LedgerError> {
+        self.authorize(Permission::ManagePeriods)?;
+        periods.add_period(period.clone());
+        self.emit(LedgerEvent::PeriodOpened { period });
+        Ok(())
+    }
+
+    pub fn close_current_period(&mut self, periods: &mut PeriodManager) -> Result<(), LedgerError> {
+        self.authorize(Permission::ManagePeriods)?;
+        periods.close_current_period();
+        if let Some(period) = periods.get_current_period().cloned() {
+            self.emit(LedgerEvent::PeriodClosed { period });
+        }
+        Ok(())
+    }
+
+    /// Closes the open period containing `date` and returns it.
+    pub fn close_period(&mut self, periods: &mut PeriodManager, date: NaiveDate) -> Result<Period, LedgerError> {
+        self.authorize(Permission::ManagePeriods)?;
+        let period = periods
+            .periods
+            .iter_mut()
+            .filter(|p| p.is_open() && p.contains_date(date))
+            .max_by_key(|p| p.start_date)
+            .ok_or(LedgerError::DateNotInOpenPeriod(date))?;
+        period.close();
+        let period = period.clone();
+        self.emit(LedgerEvent::PeriodClosed { period: period.clone() });
+        Ok(period)
+    }
+
+    /// The periods opened and closed on this ledger, rebuilt from its events.
+    pub fn period_manager(&self) -> PeriodManager {
+        let mut periods = PeriodManager::empty();
+        for recorded in &self.events {
+            match &recorded.event {
+                LedgerEvent::PeriodOpened { period } => periods.add_period(period.clone()),
+                LedgerEvent::PeriodClosed { period } => {
+                    let opened = periods
+                        .periods
+                        .iter_mut()
+                        .rfind(|p| p.start_date == period.start_date && p.end_date == period.end_date);
+                    if let Some(opened) = opened {
+                        opened.close();
+                    }
+                }
+                _ => {}
+            }
+        }
+        periods
+    }
+
+    pub fn add_account(&mut self, coa: &mut CoaConfig, account: Account) -> Result<(), LedgerError> {
+        self.authorize(Permission::EditCoa)?;
+        AccountCode::new(&account.code)?;
+        if coa.accounts.iter().any(|a| a.code == account.code) {
+            return Err(LedgerError::DuplicateAccount(account.code));
+        }
+        self.audit(AuditAction::AccountAdded, &account);
+        coa.accounts.push(account);
+        Ok(())
+    }
+
+    pub fn update_account(&mut self, coa: &mut CoaConfig, account: Account) -> Result<(), LedgerError> {
+        self.authorize(Permission::EditCoa)?;
+        let existing = coa
+            .accounts
+            .iter_mut()
+            .find(|a| a.code == account.code)
+            .ok_or_else(|| LedgerError::UnknownAccount(account.code.clone()))?;
+        let before = existing.clone();
+        *existing = account.clone();
+        self.audit(
+            AuditAction::AccountUpdated,
+            &serde_json::json!({ "before": before, "after": account }),
+        );
+        Ok(())
+    }
+
+    pub fn remove_account(&mut self, coa: &mut CoaConfig, code: &str) -> Result<Account, LedgerError> {
+        self.authorize(Permission::EditCoa)?;
+        let index = coa
+            .accounts
+            .iter()
+            .position(|a| a.code == code)
+            .ok_or_else(|| LedgerError::UnknownAccount(code.to_string()))?;
+        let account = coa.accounts.remove(index);
+        self.audit(AuditAction::AccountRemoved, &account);
+        Ok(account)
+    }
+
+    // === Audited customer master changes ===
+    pub fn add_customer(&mut self, customer: Customer) -> Result<(), LedgerError> {
+        self.authorize(Permission::ManageMasters)?;
+        self.customers.add(customer.clone())?;
+        self.audit(AuditAction::CustomerAdded, &customer);
+        Ok(())
+    }
+
+    pub fn update_customer(&mut self, customer: Customer) -> Result<(), LedgerError> {
+        self.authorize(Permission::ManageMasters)?;
+        let before = self.customers.update(customer.clone())?;
+        self.audit(
+            AuditAction::CustomerUpdated,
+            &serde_json::json!({ "before": before, "after": customer }),
+        );
+        Ok(())
+    }
+
+    // === Audited vendor master changes ===
+    pub fn add_vendor(&mut self, vendor: Vendor) -> Result<(), LedgerError> {
+        self.authorize(Permission::ManageMasters)?;
+        self.vendors.add(vendor.clone())?;
+        self.audit(AuditAction::VendorAdded, &vendor);
+        Ok(())
+    }
+
+    pub fn update_vendor(&mut self, vendor: Vendor) -> Result<(), LedgerError> {
+        self.authorize(Permission::ManageMasters)?;
+        let before = self.vendors.update(vendor.clone())?;
+        self.audit(
+            AuditAction::VendorUpdated,
+            &serde_json::json!({ "before": before, "after": vendor }),
+        );
+        Ok(())
+    }
+
+    /// Reverses every accrual due on or before `up_to`; returns the reversal ids.
+    pub fn run_auto_reversals(&mut self, up_to: NaiveDate) -> Result<Vec<EntryId>, LedgerError> {
+        let due: Vec<(EntryId, NaiveDate)> = self
+            .entries
+            .iter()
+            .filter_map(|e| match e.auto_reverse_on {
+                Some(date) if date <= up_to && e.reversed_by.is_none() => Some((e.id, date)),
+                _ => None,
+            })
+            .collect();
+        due.into_iter()
+            .map(|(id, date)| self.reverse_entry(id, date, "tự động đảo bút toán trích trước"))
+            .collect()
+    }
+
+    /// Adds the lines of an entry to the account balances.
+    fn apply(&mut self, entry: &JournalEntry) {
+        for line in &entry.lines {
             match self.balances.iter_mut().find(|b| b.account == line.account) {
                 Some(balance) => {
                     balance.debit_balance += line.debit;
@@ -139,11 +779,121 @@ impl Ledger {
                 }
             }
         }
+    }
 
-        info!("Successfully posted entry: {}", entry.description);
+    /// Checks an entry against the chart of accounts, open periods and
+    /// voucher numbering without posting it.
+    pub fn validate_entry(
+        &self,
+        entry: &JournalEntry,
+        coa: &CoaConfig,
+        periods: &PeriodManager,
+    ) -> Result<(), LedgerError> {
+        Self::check_entry(entry, coa, periods)?;
+        self.precision.check_entry(entry)?;
+        if let Some(voucher) = &entry.voucher {
+            self.vouchers.check(voucher, entry.date)?;
+        }
+        self.check_parties(entry, &BTreeMap::new(), true)?;
         Ok(())
     }
 
+    fn check_entry(entry: &JournalEntry, coa: &CoaConfig, periods: &PeriodManager) -> Result<(), LedgerError> {
+        if !periods.is_date_in_open_period(entry.date) {
+            return Err(LedgerError::DateNotInOpenPeriod(entry.date));
+        }
+        for line in &entry.lines {
+            if line.debit < Decimal::ZERO {
+                return Err(LedgerError::NegativeAmount(line.debit));
+            }
+            if line.credit < Decimal::ZERO {
+                return Err(LedgerError::NegativeAmount(line.credit));
+            }
+            if !coa.contains(line.account.as_str()) {
+                return Err(LedgerError::UnknownAccount(line.account.0.clone()));
+            }
+            currency::check_line(line)?;
+        }
+        if !entry.is_balanced() {
+            let total_debit = entry.lines.iter().map(|l| l.debit).sum::<Decimal>();
+            let total_credit = entry.lines.iter().map(|l| l.credit).sum::<Decimal>();
+            return Err(LedgerError::UnbalancedEntry(total_debit, total_credit));
+        }
+        Ok(())
+    }
+
+    /// Validates every entry of a batch and returns the trial balance the
+    /// batch would add, or every failing entry with its index.
+    pub fn validate_batch(
+        &self,
+        entries: &[JournalEntry],
+        coa: &CoaConfig,
+        periods: &PeriodManager,
+    ) -> Result<TrialBalance, Vec<BatchError>> {
+        // Vouchers of the batch follow each other in their series
+        // and credit limits count the earlier entries of the batch
+        let mut vouchers = self.vouchers.clone();
+        let mut pending: BTreeMap<String, Decimal> = BTreeMap::new();
+        let errors: Vec<BatchError> = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                let result = Self::check_entry(entry, coa, periods)
+                    .and_then(|_| self.precision.check_entry(entry))
+                    .and_then(|_| match &entry.voucher {
+                        Some(voucher) => {
+                            vouchers.check(voucher, entry.date)?;
+                            vouchers.register(voucher);
+                            Ok(())
+                        }
+                        None => Ok(()),
+                    })
+                    .and_then(|_| self.check_parties(entry, &pending, true))
+                    .map(|delta| {
+                        for (code, change) in delta {
+                            *pending.entry(code).or_insert(Decimal::ZERO) += change;
+                        }
+                    });
+                result.err().map(|error| BatchError { index, error })
+            })
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut delta = Ledger::new();
+        for entry in entries {
+            delta.apply(entry);
+        }
+        Ok(delta.trial_balance())
+    }
+
+    /// Posts all entries of a batch, or none of them if any entry is invalid.
+    pub fn post_batch(
+        &mut self,
+        entries: Vec<JournalEntry>,
+        coa: &CoaConfig,
+        periods: &PeriodManager,
+    ) -> Result<usize, Vec<BatchError>> {
+        self.authorize(Permission::PostEntry).map_err(|error| vec![BatchError { index: 0, error }])?;
+        self.validate_batch(&entries, coa, periods)?;
+        // Posted on a copy, so an entry failing after validation leaves no
+        // event of the earlier ones behind
+        let mut staged = self.clone();
+        let count = entries.len();
+        for (index, entry) in entries.into_iter().enumerate() {
+            staged.post_approved(entry).map_err(|error| vec![BatchError { index, error }])?;
+        }
+        *self = staged;
+        info!("Posted batch of {} entries", count);
+        Ok(count)
+    }
+
+    /// Posted entries in posting order.
+    pub fn entries(&self) -> &[PostedEntry] {
+        &self.entries
+    }
+
     pub fn trial_balance(&self) -> TrialBalance {
         let total_debit = self.balances.iter().map(|b| b.debit_balance).sum::<Decimal>();
         let total_credit = self.balances.iter().map(|b| b.credit_balance).sum::<Decimal>();
@@ -155,6 +905,16 @@ impl Ledger {
         }
     }
 
+    /// Trial balance by transaction date: entries dated on or before `date`,
+    /// including everything posted so far.
+    pub fn as_of_date(&self, date: NaiveDate) -> TrialBalance {
+        let mut state = Ledger::new();
+        for posted in self.entries.iter().filter(|e| e.entry.date <= date) {
+            state.apply(&posted.entry);
+        }
+        state.trial_balance()
+    }
+
     pub fn get_account_balance(&self, account_code: &AccountCode) -> Option<&AccountBalance> {
         self.balances.iter().find(|b| b.account == *account_code)
     }
@@ -174,12 +934,59 @@ pub fn create_vn_coa() -> Vec<AccountCode> {
     ]
 }
 
-pub use coa::{Account, CoaConfig, load_vn_coa};
+pub use asset::{
+    accumulated_depreciation, asset_class, asset_movements, depreciation_schedule, AssetClass, AssetDisposal,
+    AssetMovement, AssetRecord, DepreciationCharge, DepreciationMethod, FixedAsset, ScheduleLine, ASSET_CLASSES,
+    DEPRECIATION_ACCOUNT, DEPRECIATION_EXPENSE_ACCOUNT, DISPOSAL_EXPENSE_ACCOUNT, DISPOSAL_INCOME_ACCOUNT,
+    TANGIBLE_ASSET_ACCOUNT,
+};
+pub use audit::{AuditAction, AuditEvent, AuditHead, AuditLog, GENESIS_HASH};
+pub use auth::{hash_token, Permission, Role, User, UserDirectory};
+pub use events::{LedgerEvent, RecordedEvent, Snapshot};
+pub use subledger::{
+    ItemKind, ItemMatch, MatchId, OpenItem, payables_aging, provision_rate, receivables_aging, AgingBucket, AgingItem, AgingLine, AgingReport, ProvisionLine,
+    customer_balances, customer_statement, payments_due, reconcile_payables, reconcile_receivables,
+    vendor_balances, vendor_statement, Customer, CustomerMaster, InvoiceAllocation, InvoicePayment, PartyBalance,
+    PartyStatement, PaymentDue, PaymentSchedule, Reconciliation, StatementLine, Vendor, VendorInvoice,
+    VendorMaster, PAYABLE_ACCOUNT, RECEIVABLE_ACCOUNT,
+};
+pub use voucher::{Voucher, VoucherFormat, VoucherNumbering, VoucherType};
+pub use workflow::{DraftAction, DraftEntry, DraftId, DraftStatus, DraftStep};
+pub use coa::{Account, CoaConfig, load_coa, load_vn_coa};
+pub use company::{AccountingMethod, CoaRegime, Company, CompanyBooks, CompanyRegistry, Workspace};
+pub use currency::{
+    currency_balances, import_rates_csv, revaluation, Allocation, CurrencyBalance, ExchangeRate, ForeignAmount,
+    PrecisionPolicy, RateTable, RevaluationLine, RoundingMode, EXCHANGE_DIFFERENCE_ACCOUNT, FUNCTIONAL_CURRENCY, FX_GAIN_ACCOUNT, FX_LOSS_ACCOUNT,
+    REVALUED_ACCOUNTS,
+};
+pub use document::{amount_in_words, format_vnd, CashVoucher, CashVoucherKind, CompanyInfo, VoucherParty};
+pub use einvoice::{
+    load_einvoice, parse_einvoice, AccountMapping, EInvoice, EInvoiceImporter, EInvoiceItem,
+    EInvoiceItemKind, EInvoiceMapping, EInvoiceParty, InvoiceDirection,
+};
+pub use import::{
+    import_csv, import_rows, import_xlsx, parse_amount, parse_entry_batch, parse_date, ColumnMapping, ImportError, ImportMode,
+    ImportResult, ImportedVoucher, NumberFormat,
+};
+pub use recurring::{
+    GeneratedEntry, Occurrence, RecurringRun, RecurringTemplate, RecurringTemplates, Schedule, SkipReason,
+    TemplateAmount, TemplateLine,
+};
+pub use report::{
+    balance_sheet, income_statement, ledger_book, BalanceSheet, IncomeStatement, LedgerBook,
+    LedgerBookLine, ReportLine, ReportWorkbook,
+};
 
 pub struct PeriodManager {
     periods: Vec<Period>,
 }
 
+impl Default for PeriodManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PeriodManager {
     pub fn new() -> Self {
         // Default open period for testing: covers 2020-01-01 to 2030-12-31
@@ -190,6 +997,15 @@ impl PeriodManager {
         Self { periods: vec![default_period] }
     }
 
+    /// No periods at all: nothing can be posted until one is opened.
+    pub fn empty() -> Self {
+        Self { periods: vec![] }
+    }
+
+    pub fn periods(&self) -> &[Period] {
+        &self.periods
+    }
+
     pub fn add_period(&mut self, period: Period) {
         self.periods.push(period);
     }
@@ -204,6 +1020,14 @@ impl PeriodManager {
         }
     }
 
+    /// The most specific period containing `date` (the one starting latest).
+    pub fn period_for(&self, date: NaiveDate) -> Option<&Period> {
+        self.periods
+            .iter()
+            .filter(|p| p.contains_date(date))
+            .max_by_key(|p| p.start_date)
+    }
+
     pub fn is_date_in_open_period(&self, date: NaiveDate) -> bool {
         self.periods.iter().any(|p| p.is_open() && p.contains_date(date))
     }
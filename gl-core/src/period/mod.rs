@@ -1,9 +1,10 @@
 // gl-core/src/period/mod.rs
 
 use serde::{Deserialize, Serialize};
-use chrono::NaiveDate;
+use chrono::{Days, Months, NaiveDate};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Period {
     pub start_date: NaiveDate,
     pub end_date: NaiveDate,
@@ -11,6 +12,7 @@ pub struct Period {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum PeriodStatus {
     Open,
     Closed,
@@ -36,4 +38,22 @@ impl Period {
     pub fn contains_date(&self, date: NaiveDate) -> bool {
         date >= self.start_date && date <= self.end_date
     }
+
+    /// The calendar month starting on `start`, which should be a 1st.
+    pub fn month(start: NaiveDate) -> Option<Self> {
+        let end = start.checked_add_months(Months::new(1))?.pred_opt()?;
+        Some(Self::new(start, end))
+    }
+
+    /// The monthly periods of this one, e.g. the twelve of a fiscal year.
+    pub fn months(&self) -> Vec<Period> {
+        let mut months = Vec::new();
+        let mut start = self.start_date;
+        while start <= self.end_date {
+            let Some(month) = Period::month(start) else { break };
+            start = month.end_date + Days::new(1);
+            months.push(month);
+        }
+        months
+    }
 }
\ No newline at end of file
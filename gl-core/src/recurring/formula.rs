@@ -0,0 +1,210 @@
+// gl-core/src/recurring/formula.rs
+
+//! Amount formulas of recurring templates: numbers, `+ - * /`, parentheses
+//! and two functions of an account, sub-accounts included, evaluated on the
+//! date of the occurrence:
+//! - `balance(211)`: net debit (debit - credit) of every entry up to that date;
+//! - `movement(642)`: net debit of the entries of that month up to that date.
+//!
+//! A credit balance is negative, so `-balance(3331)` reads a liability.
+
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::Decimal;
+
+use crate::{AccountCode, Ledger, LedgerError};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(String),
+    Name(String),
+    Op(char),
+    Open,
+    Close,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Number(Decimal),
+    Balance(AccountCode),
+    Movement(AccountCode),
+    Neg(Box<Expr>),
+    Binary(Box<Expr>, char, Box<Expr>),
+}
+
+/// A parsed formula, checked when the templates are loaded.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Formula(Expr);
+
+fn invalid(formula: &str, reason: &str) -> LedgerError {
+    LedgerError::InvalidTemplate(format!("formula `{}`: {}", formula, reason))
+}
+
+fn tokenize(formula: &str) -> Result<Vec<Token>, LedgerError> {
+    let mut tokens = vec![];
+    let mut chars = formula.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' => {
+                chars.next();
+            }
+            '0'..='9' | '.' => {
+                let mut number = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+                    number.push(c);
+                }
+                tokens.push(Token::Number(number));
+            }
+            'a'..='z' | '_' => {
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_lowercase() || *c == '_') {
+                    name.push(c);
+                }
+                tokens.push(Token::Name(name));
+            }
+            '+' | '-' | '*' | '/' => {
+                chars.next();
+                tokens.push(Token::Op(c));
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            _ => return Err(invalid(formula, &format!("unexpected `{}`", c))),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over the tokens; `*` and `/` bind tighter than `+` and `-`.
+struct Parser<'a> {
+    formula: &'a str,
+    tokens: Vec<Token>,
+    at: usize,
+}
+
+impl Parser<'_> {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.at).cloned();
+        self.at += 1;
+        token
+    }
+
+    fn next_op(&mut self, ops: &[char]) -> Option<char> {
+        match self.tokens.get(self.at) {
+            Some(Token::Op(op)) if ops.contains(op) => {
+                self.at += 1;
+                Some(*op)
+            }
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, token: Token, what: &str) -> Result<(), LedgerError> {
+        if self.next() == Some(token) {
+            Ok(())
+        } else {
+            Err(invalid(self.formula, &format!("expected {}", what)))
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, LedgerError> {
+        let mut left = self.term()?;
+        while let Some(op) = self.next_op(&['+', '-']) {
+            left = Expr::Binary(Box::new(left), op, Box::new(self.term()?));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr, LedgerError> {
+        let mut left = self.factor()?;
+        while let Some(op) = self.next_op(&['*', '/']) {
+            left = Expr::Binary(Box::new(left), op, Box::new(self.factor()?));
+        }
+        Ok(left)
+    }
+
+    fn factor(&mut self) -> Result<Expr, LedgerError> {
+        if self.next_op(&['-']).is_some() {
+            return Ok(Expr::Neg(Box::new(self.factor()?)));
+        }
+        match self.next() {
+            Some(Token::Number(number)) => number
+                .parse()
+                .map(Expr::Number)
+                .map_err(|_| invalid(self.formula, &format!("`{}` is not a number", number))),
+            Some(Token::Open) => {
+                let inner = self.expr()?;
+                self.expect(Token::Close, "`)`")?;
+                Ok(inner)
+            }
+            Some(Token::Name(name)) => {
+                self.expect(Token::Open, &format!("`(` after `{}`", name))?;
+                let Some(Token::Number(code)) = self.next() else {
+                    return Err(invalid(self.formula, &format!("`{}` takes an account code", name)));
+                };
+                let account = AccountCode::new(&code)?;
+                self.expect(Token::Close, "`)`")?;
+                match name.as_str() {
+                    "balance" => Ok(Expr::Balance(account)),
+                    "movement" => Ok(Expr::Movement(account)),
+                    _ => Err(invalid(self.formula, &format!("unknown function `{}`", name))),
+                }
+            }
+            _ => Err(invalid(self.formula, "expected a number, `(` or a function")),
+        }
+    }
+}
+
+impl Formula {
+    pub(crate) fn parse(formula: &str) -> Result<Self, LedgerError> {
+        let mut parser = Parser { formula, tokens: tokenize(formula)?, at: 0 };
+        let expr = parser.expr()?;
+        if parser.at < parser.tokens.len() {
+            return Err(invalid(formula, "unexpected text at the end"));
+        }
+        Ok(Formula(expr))
+    }
+
+    /// The unrounded value of the formula on `date`.
+    pub(crate) fn eval(&self, ledger: &Ledger, date: NaiveDate) -> Result<Decimal, LedgerError> {
+        eval(&self.0, ledger, date)
+    }
+}
+
+fn eval(expr: &Expr, ledger: &Ledger, date: NaiveDate) -> Result<Decimal, LedgerError> {
+    Ok(match expr {
+        Expr::Number(value) => *value,
+        Expr::Balance(account) => net_debit(ledger, account, NaiveDate::MIN, date),
+        Expr::Movement(account) => {
+            let month_start = date.with_day(1).ok_or(LedgerError::InvalidDate)?;
+            net_debit(ledger, account, month_start, date)
+        }
+        Expr::Neg(inner) => -eval(inner, ledger, date)?,
+        Expr::Binary(left, op, right) => {
+            let (left, right) = (eval(left, ledger, date)?, eval(right, ledger, date)?);
+            match op {
+                '+' => left + right,
+                '-' => left - right,
+                '*' => left * right,
+                _ => left
+                    .checked_div(right)
+                    .ok_or_else(|| LedgerError::InvalidTemplate(format!("division by zero on {}", date)))?,
+            }
+        }
+    })
+}
+
+fn net_debit(ledger: &Ledger, account: &AccountCode, from: NaiveDate, to: NaiveDate) -> Decimal {
+    ledger
+        .entries()
+        .iter()
+        .filter(|e| e.entry.date >= from && e.entry.date <= to)
+        .flat_map(|e| &e.entry.lines)
+        .filter(|l| l.account.as_str().starts_with(account.as_str()))
+        .map(|l| l.debit - l.credit)
+        .sum()
+}
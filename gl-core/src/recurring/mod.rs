@@ -0,0 +1,355 @@
+// gl-core/src/recurring/mod.rs
+
+//! Recurring journal entries (bút toán định kỳ): templates such as monthly
+//! rent or depreciation, each on a schedule. `run_recurring` posts every
+//! occurrence due up to a date whose period is open. The generated entries
+//! are events of their own, so a template never posts the same date twice
+//! and running again only adds what has fallen due since.
+
+mod formula;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use chrono::{Datelike, Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{
+    AccountCode, CoaConfig, EntryId, JournalEntry, JournalLine, Ledger, LedgerError, LedgerEvent, PeriodManager,
+    Permission, FUNCTIONAL_CURRENCY,
+};
+use formula::Formula;
+
+/// When a template falls due. Days past the end of a month fall on its last day.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "every", rename_all = "snake_case")]
+pub enum Schedule {
+    /// Day `day` of every month.
+    Monthly { day: u32 },
+    /// Day `day` of every third month, counting from the template's start.
+    Quarterly { day: u32 },
+    /// The last day of every month.
+    EndOfMonth,
+}
+
+impl Schedule {
+    /// Dates of the schedule from `start` to `until`, both included.
+    pub fn dates(&self, start: NaiveDate, until: NaiveDate) -> Vec<NaiveDate> {
+        let (step, day) = match *self {
+            Schedule::Monthly { day } => (1, day),
+            Schedule::Quarterly { day } => (3, day),
+            Schedule::EndOfMonth => (1, 31),
+        };
+        let Some(first_month) = start.with_day(1) else { return vec![] };
+        let mut dates = vec![];
+        for n in 0.. {
+            let Some(month) = first_month.checked_add_months(Months::new(step * n)) else { break };
+            let Some(date) = (1..=day).rev().find_map(|d| month.with_day(d)) else { break };
+            if date > until {
+                break;
+            }
+            if date >= start {
+                dates.push(date);
+            }
+        }
+        dates
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schedule::Monthly { day } => write!(f, "monthly on day {}", day),
+            Schedule::Quarterly { day } => write!(f, "quarterly on day {}", day),
+            Schedule::EndOfMonth => write!(f, "end of month"),
+        }
+    }
+}
+
+/// Amount of a template line.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum TemplateAmount {
+    /// The same amount every time.
+    Fixed(Decimal),
+    /// An amount per month, keyed `YYYY-MM`, for bills that vary.
+    Variable(BTreeMap<String, Decimal>),
+    /// A formula such as `balance(211) / 60`; see the `formula` module.
+    Formula(String),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TemplateLine {
+    pub account: AccountCode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debit: Option<TemplateAmount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credit: Option<TemplateAmount>,
+    /// Customer or vendor code, for lines on 131 and 331.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub party: Option<String>,
+}
+
+impl TemplateLine {
+    /// A line with neither debit nor credit takes whatever balances the entry.
+    pub fn is_balancing(&self) -> bool {
+        self.debit.is_none() && self.credit.is_none()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RecurringTemplate {
+    pub code: String,
+    /// Description of the generated entries; `{period}` becomes the month, e.g. `03/2025`.
+    pub description: String,
+    pub schedule: Schedule,
+    /// No occurrence falls before this date.
+    pub start: NaiveDate,
+    /// Nor after this one; the template runs on when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<NaiveDate>,
+    pub lines: Vec<TemplateLine>,
+}
+
+impl RecurringTemplate {
+    fn check(&self) -> Result<(), LedgerError> {
+        let invalid = |reason: String| LedgerError::InvalidTemplate(format!("{}: {}", self.code, reason));
+        if self.code.trim().is_empty() {
+            return Err(LedgerError::InvalidTemplate("template code is empty".to_string()));
+        }
+        if let Schedule::Monthly { day } | Schedule::Quarterly { day } = self.schedule {
+            if !(1..=31).contains(&day) {
+                return Err(invalid(format!("day {} is not a day of the month", day)));
+            }
+        }
+        if self.end.is_some_and(|end| end < self.start) {
+            return Err(invalid("ends before it starts".to_string()));
+        }
+        if self.lines.len() < 2 {
+            return Err(invalid("needs at least two lines".to_string()));
+        }
+        if self.lines.iter().filter(|l| l.is_balancing()).count() > 1 {
+            return Err(invalid("only one line may be left without an amount".to_string()));
+        }
+        for line in &self.lines {
+            AccountCode::new(line.account.as_str())?;
+            match (&line.debit, &line.credit) {
+                (Some(_), Some(_)) => {
+                    return Err(invalid(format!("line on {} has both a debit and a credit", line.account.as_str())))
+                }
+                (Some(TemplateAmount::Formula(formula)), None) | (None, Some(TemplateAmount::Formula(formula))) => {
+                    Formula::parse(formula)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Dates this template falls due, up to `until`.
+    pub fn dates(&self, until: NaiveDate) -> Vec<NaiveDate> {
+        let until = self.end.map_or(until, |end| end.min(until));
+        self.schedule.dates(self.start, until)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct RecurringTemplates {
+    pub templates: Vec<RecurringTemplate>,
+}
+
+impl RecurringTemplates {
+    pub fn new() -> Self {
+        RecurringTemplates { templates: vec![] }
+    }
+
+    /// Reads and checks templates: unique codes, valid days, formulas that parse.
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let templates: RecurringTemplates = serde_yaml::from_str(contents)?;
+        let mut codes = BTreeSet::new();
+        for template in &templates.templates {
+            template.check()?;
+            if !codes.insert(template.code.as_str()) {
+                return Err(LedgerError::InvalidTemplate(format!("template {} is defined twice", template.code)).into());
+            }
+        }
+        Ok(templates)
+    }
+
+    pub fn get(&self, code: &str) -> Option<&RecurringTemplate> {
+        self.templates.iter().find(|t| t.code == code)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+}
+
+/// An entry posted from a template.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GeneratedEntry {
+    pub template: String,
+    pub date: NaiveDate,
+    pub entry_id: EntryId,
+}
+
+/// A date a template falls due.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Occurrence {
+    pub template: String,
+    pub date: NaiveDate,
+}
+
+/// Why an occurrence due was not posted, without it being an error.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Its period is closed or not opened yet; a later run posts it once it is open.
+    PeriodNotOpen,
+    /// Every amount came to zero, e.g. depreciation of a fully depreciated asset.
+    NothingToPost,
+}
+
+/// What `run_recurring` did with each occurrence due.
+#[derive(Debug, Default)]
+pub struct RecurringRun {
+    pub posted: Vec<GeneratedEntry>,
+    pub skipped: Vec<(Occurrence, SkipReason)>,
+    pub failed: Vec<(Occurrence, LedgerError)>,
+}
+
+impl Ledger {
+    /// Starts with recurring templates; they are stored with the ledger.
+    pub fn with_recurring(mut self, templates: RecurringTemplates) -> Self {
+        self.recurring = templates;
+        self
+    }
+
+    pub fn recurring(&self) -> &RecurringTemplates {
+        &self.recurring
+    }
+
+    /// Entries posted from templates, in posting order.
+    pub fn generated_entries(&self) -> Vec<GeneratedEntry> {
+        self.events
+            .iter()
+            .filter_map(|recorded| match &recorded.event {
+                LedgerEvent::RecurringEntryPosted { entry, template } => Some(GeneratedEntry {
+                    template: template.clone(),
+                    date: entry.entry.date,
+                    entry_id: entry.id,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The entry of `template` for `date`, with formulas evaluated against
+    /// the ledger as it is now; `None` when every amount is zero.
+    pub fn recurring_entry(
+        &self,
+        template: &RecurringTemplate,
+        date: NaiveDate,
+    ) -> Result<Option<JournalEntry>, LedgerError> {
+        let invalid = |reason: String| LedgerError::InvalidTemplate(format!("{}: {}", template.code, reason));
+        let amount = |amount: &TemplateAmount| -> Result<Decimal, LedgerError> {
+            match amount {
+                TemplateAmount::Fixed(value) => Ok(*value),
+                TemplateAmount::Variable(by_month) => {
+                    let month = date.format("%Y-%m").to_string();
+                    by_month.get(&month).copied().ok_or_else(|| invalid(format!("no amount for {}", month)))
+                }
+                TemplateAmount::Formula(formula) => {
+                    let value = Formula::parse(formula)?.eval(self, date)?;
+                    Ok(self.precision.round(FUNCTIONAL_CURRENCY, value))
+                }
+            }
+        };
+
+        let mut lines = vec![];
+        let mut net = Decimal::ZERO;
+        for line in template.lines.iter().filter(|l| !l.is_balancing()) {
+            let (debit, credit) = match (&line.debit, &line.credit) {
+                (Some(debit), _) => (amount(debit)?, Decimal::ZERO),
+                (_, Some(credit)) => (Decimal::ZERO, amount(credit)?),
+                (None, None) => unreachable!("balancing lines are filtered out"),
+            };
+            net += debit - credit;
+            lines.push((line, debit, credit));
+        }
+        if let Some(line) = template.lines.iter().find(|l| l.is_balancing()) {
+            let (debit, credit) = if net > Decimal::ZERO { (Decimal::ZERO, net) } else { (-net, Decimal::ZERO) };
+            lines.push((line, debit, credit));
+        }
+
+        let mut entry_lines = vec![];
+        for (line, debit, credit) in lines.into_iter().filter(|(_, d, c)| !d.is_zero() || !c.is_zero()) {
+            let mut journal_line = JournalLine::new(line.account.clone(), debit, credit)?;
+            if let Some(party) = &line.party {
+                journal_line = journal_line.with_party(party);
+            }
+            entry_lines.push(journal_line);
+        }
+        if entry_lines.is_empty() {
+            return Ok(None);
+        }
+        let description = template.description.replace("{period}", &date.format("%m/%Y").to_string());
+        JournalEntry::new(date, entry_lines, description).map(Some)
+    }
+
+    /// Posts every occurrence of the templates due up to `until` that is not
+    /// posted yet, oldest first. Occurrences outside open periods are left
+    /// for a later run; one that fails validation does not stop the others.
+    pub fn run_recurring(
+        &mut self,
+        coa: &CoaConfig,
+        periods: &PeriodManager,
+        until: NaiveDate,
+    ) -> Result<RecurringRun, LedgerError> {
+        self.authorize(Permission::PostEntry)?;
+        let generated: BTreeSet<(String, NaiveDate)> =
+            self.generated_entries().into_iter().map(|g| (g.template, g.date)).collect();
+        let mut due: Vec<(NaiveDate, usize)> = vec![];
+        for (index, template) in self.recurring.templates.iter().enumerate() {
+            for date in template.dates(until) {
+                if !generated.contains(&(template.code.clone(), date)) {
+                    due.push((date, index));
+                }
+            }
+        }
+        due.sort();
+
+        let mut run = RecurringRun::default();
+        for (date, index) in due {
+            let template = self.recurring.templates[index].clone();
+            let occurrence = Occurrence { template: template.code.clone(), date };
+            if !periods.is_date_in_open_period(date) {
+                run.skipped.push((occurrence, SkipReason::PeriodNotOpen));
+                continue;
+            }
+            match self.post_recurring(&template, date, coa, periods) {
+                Ok(Some(entry_id)) => run.posted.push(GeneratedEntry { template: template.code, date, entry_id }),
+                Ok(None) => run.skipped.push((occurrence, SkipReason::NothingToPost)),
+                Err(error) => run.failed.push((occurrence, error)),
+            }
+        }
+        info!("Recurring run up to {}: {} posted, {} skipped, {} failed", until, run.posted.len(), run.skipped.len(), run.failed.len());
+        Ok(run)
+    }
+
+    fn post_recurring(
+        &mut self,
+        template: &RecurringTemplate,
+        date: NaiveDate,
+        coa: &CoaConfig,
+        periods: &PeriodManager,
+    ) -> Result<Option<EntryId>, LedgerError> {
+        let Some(entry) = self.recurring_entry(template, date)? else { return Ok(None) };
+        self.validate_entry(&entry, coa, periods)?;
+        let posted = self.next_entry(entry, None, None)?;
+        let id = posted.id;
+        self.emit(LedgerEvent::RecurringEntryPosted { entry: posted, template: template.code.clone() });
+        Ok(Some(id))
+    }
+}
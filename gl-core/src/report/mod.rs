@@ -0,0 +1,312 @@
+// gl-core/src/report/mod.rs
+
+//! Statutory reports built from posted entries (Circular 133/2016/TT-BTC):
+//! Sổ cái (general ledger book per account), B01-DNN (balance sheet) and
+//! B02-DNN (income statement).
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::{AccountCode, EntryId, JournalEntry, Ledger, PostedEntry};
+
+mod xlsx;
+pub use xlsx::ReportWorkbook;
+
+/// Closing entries (kết chuyển) to 911 are left out of turnover-based reports.
+const PROFIT_ACCOUNT: &str = "911";
+
+fn is_closing_entry(entry: &JournalEntry) -> bool {
+    entry
+        .lines
+        .iter()
+        .any(|l| l.account.as_str().starts_with(PROFIT_ACCOUNT))
+}
+
+fn matches_prefix(account: &AccountCode, prefixes: &[&str]) -> bool {
+    prefixes.iter().any(|p| account.as_str().starts_with(p))
+}
+
+// === Sổ cái ===
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LedgerBookLine {
+    pub entry_id: EntryId,
+    pub date: NaiveDate,
+    pub description: String,
+    /// Accounts on the other side of the entry (TK đối ứng).
+    pub counter_accounts: Vec<AccountCode>,
+    pub debit: Decimal,
+    pub credit: Decimal,
+    /// Running balance, debit positive.
+    pub balance: Decimal,
+    /// Reversal links, so reversed and reversing entries can be traced.
+    pub reverses: Option<EntryId>,
+    pub reversed_by: Option<EntryId>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LedgerBook {
+    pub account: AccountCode,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Opening balance, debit positive.
+    pub opening_balance: Decimal,
+    pub lines: Vec<LedgerBookLine>,
+    pub total_debit: Decimal,
+    pub total_credit: Decimal,
+    pub closing_balance: Decimal,
+}
+
+/// Builds the Sổ cái of an account (and its sub-accounts) for a date range.
+pub fn ledger_book(ledger: &Ledger, account: &AccountCode, from: NaiveDate, to: NaiveDate) -> LedgerBook {
+    let prefix = [account.as_str()];
+    let mut opening_balance = Decimal::ZERO;
+    let mut lines = Vec::new();
+    let mut balance = Decimal::ZERO;
+
+    let mut entries: Vec<&PostedEntry> =
+        ledger.entries().iter().filter(|e| e.entry.date <= to).collect();
+    entries.sort_by_key(|e| e.entry.date);
+
+    for posted in entries {
+        let entry = &posted.entry;
+        let (own, others): (Vec<_>, Vec<_>) =
+            entry.lines.iter().partition(|l| matches_prefix(&l.account, &prefix));
+        if own.is_empty() {
+            continue;
+        }
+        let debit: Decimal = own.iter().map(|l| l.debit).sum();
+        let credit: Decimal = own.iter().map(|l| l.credit).sum();
+
+        if entry.date < from {
+            opening_balance += debit - credit;
+            balance = opening_balance;
+            continue;
+        }
+        balance += debit - credit;
+        let mut counter_accounts: Vec<AccountCode> = Vec::new();
+        for line in others {
+            if !counter_accounts.contains(&line.account) {
+                counter_accounts.push(line.account.clone());
+            }
+        }
+        lines.push(LedgerBookLine {
+            entry_id: posted.id,
+            date: entry.date,
+            description: entry.description.clone(),
+            counter_accounts,
+            debit,
+            credit,
+            balance,
+            reverses: posted.reverses,
+            reversed_by: posted.reversed_by,
+        });
+    }
+
+    let total_debit = lines.iter().map(|l| l.debit).sum();
+    let total_credit = lines.iter().map(|l| l.credit).sum();
+    LedgerBook {
+        account: account.clone(),
+        from,
+        to,
+        opening_balance,
+        lines,
+        total_debit,
+        total_credit,
+        closing_balance: opening_balance + total_debit - total_credit,
+    }
+}
+
+// === B01-DNN / B02-DNN ===
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReportLine {
+    /// Mã số on the statutory form.
+    pub code: String,
+    pub name: String,
+    pub amount: Decimal,
+    /// Subtotal and total lines are printed in bold.
+    pub is_total: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BalanceSheet {
+    pub as_of: NaiveDate,
+    pub lines: Vec<ReportLine>,
+    pub total_assets: Decimal,
+    pub total_sources: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IncomeStatement {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub lines: Vec<ReportLine>,
+    pub profit_after_tax: Decimal,
+}
+
+/// Net debit (debit - credit) per account over the selected entries.
+fn net_by_account<'a, I>(entries: I) -> BTreeMap<AccountCode, Decimal>
+where
+    I: IntoIterator<Item = &'a JournalEntry>,
+{
+    let mut net: BTreeMap<AccountCode, Decimal> = BTreeMap::new();
+    for entry in entries {
+        for line in &entry.lines {
+            *net.entry(line.account.clone()).or_insert(Decimal::ZERO) += line.debit - line.credit;
+        }
+    }
+    net
+}
+
+fn sum_prefixes(net: &BTreeMap<AccountCode, Decimal>, prefixes: &[&str]) -> Decimal {
+    net.iter()
+        .filter(|(a, _)| matches_prefix(a, prefixes))
+        .map(|(_, v)| *v)
+        .sum()
+}
+
+/// Credit balance (credit - debit); subtracting from zero avoids a negative zero.
+fn credit_prefixes(net: &BTreeMap<AccountCode, Decimal>, prefixes: &[&str]) -> Decimal {
+    Decimal::ZERO - sum_prefixes(net, prefixes)
+}
+
+fn line(code: &str, name: &str, amount: Decimal) -> ReportLine {
+    ReportLine { code: code.to_string(), name: name.to_string(), amount, is_total: false }
+}
+
+fn total(code: &str, name: &str, amount: Decimal) -> ReportLine {
+    ReportLine { code: code.to_string(), name: name.to_string(), amount, is_total: true }
+}
+
+/// Asset lines: (mã số, chỉ tiêu, account prefixes), measured as debit balance.
+const ASSET_LINES: &[(&str, &str, &[&str])] = &[
+    ("110", "Tiền và các khoản tương đương tiền", &["111", "112", "1281"]),
+    ("120", "Đầu tư tài chính", &["121", "1288", "228"]),
+    ("130", "Các khoản phải thu", &["131", "136", "138", "141"]),
+    ("140", "Hàng tồn kho", &["151", "152", "153", "154", "155", "156", "157"]),
+    ("150", "Tài sản cố định", &["211", "213", "214"]),
+    ("160", "Bất động sản đầu tư", &["217"]),
+    ("170", "Xây dựng cơ bản dở dang", &["241"]),
+    ("180", "Tài sản khác", &["133", "242"]),
+];
+
+/// Liability lines, measured as credit balance.
+const LIABILITY_LINES: &[(&str, &str, &[&str])] = &[
+    ("311", "Phải trả người bán", &["331"]),
+    ("313", "Thuế và các khoản phải nộp Nhà nước", &["333"]),
+    ("314", "Phải trả người lao động", &["334"]),
+    ("315", "Phải trả khác", &["335", "338"]),
+    ("316", "Vay và nợ thuê tài chính", &["341"]),
+];
+
+const EQUITY_LINES: &[(&str, &str, &[&str])] = &[
+    ("411", "Vốn góp của chủ sở hữu", &["411"]),
+    ("413", "Chênh lệch tỷ giá hối đoái", &["413"]),
+    ("414", "Các quỹ thuộc vốn chủ sở hữu", &["418"]),
+];
+
+/// Income and expense accounts not yet closed to 421 are shown in
+/// "Lợi nhuận sau thuế chưa phân phối" so the statement balances mid-year.
+const RESULT_PREFIXES: &[&str] = &["421", "5", "6", "7", "8", "9"];
+
+/// Builds the B01-DNN balance sheet as of a date.
+pub fn balance_sheet(ledger: &Ledger, as_of: NaiveDate) -> BalanceSheet {
+    let net = net_by_account(
+        ledger
+            .entries()
+            .iter()
+            .map(|e| &e.entry)
+            .filter(|e| e.date <= as_of),
+    );
+    let mut lines = Vec::new();
+
+    let mut total_assets = Decimal::ZERO;
+    for (code, name, prefixes) in ASSET_LINES {
+        let amount = sum_prefixes(&net, prefixes);
+        total_assets += amount;
+        lines.push(line(code, name, amount));
+    }
+    lines.push(total("200", "TỔNG CỘNG TÀI SẢN", total_assets));
+
+    let mut total_liabilities = Decimal::ZERO;
+    let mut liability_lines = Vec::new();
+    for (code, name, prefixes) in LIABILITY_LINES {
+        let amount = credit_prefixes(&net, prefixes);
+        total_liabilities += amount;
+        liability_lines.push(line(code, name, amount));
+    }
+    lines.push(total("300", "NỢ PHẢI TRẢ", total_liabilities));
+    lines.extend(liability_lines);
+
+    let mut total_equity = Decimal::ZERO;
+    let mut equity_lines = Vec::new();
+    for (code, name, prefixes) in EQUITY_LINES {
+        let amount = credit_prefixes(&net, prefixes);
+        total_equity += amount;
+        equity_lines.push(line(code, name, amount));
+    }
+    let retained = credit_prefixes(&net, RESULT_PREFIXES);
+    total_equity += retained;
+    equity_lines.push(line("417", "Lợi nhuận sau thuế chưa phân phối", retained));
+    lines.push(total("400", "VỐN CHỦ SỞ HỮU", total_equity));
+    lines.extend(equity_lines);
+
+    let total_sources = total_liabilities + total_equity;
+    lines.push(total("500", "TỔNG CỘNG NGUỒN VỐN", total_sources));
+
+    BalanceSheet { as_of, lines, total_assets, total_sources }
+}
+
+/// Builds the B02-DNN income statement for a date range.
+pub fn income_statement(ledger: &Ledger, from: NaiveDate, to: NaiveDate) -> IncomeStatement {
+    let net = net_by_account(
+        ledger
+            .entries()
+            .iter()
+            .map(|e| &e.entry)
+            .filter(|e| e.date >= from && e.date <= to && !is_closing_entry(e)),
+    );
+    let credit = |prefixes: &[&str]| credit_prefixes(&net, prefixes);
+    let debit = |prefixes: &[&str]| sum_prefixes(&net, prefixes);
+
+    let revenue = credit(&["511"]);
+    let deductions = debit(&["521"]);
+    let net_revenue = revenue - deductions;
+    let cogs = debit(&["632"]);
+    let gross_profit = net_revenue - cogs;
+    let financial_income = credit(&["515"]);
+    let financial_expense = debit(&["635"]);
+    let admin_expense = debit(&["642"]);
+    let operating_profit = gross_profit + financial_income - financial_expense - admin_expense;
+    let other_income = credit(&["711"]);
+    let other_expense = debit(&["811"]);
+    let other_profit = other_income - other_expense;
+    let profit_before_tax = operating_profit + other_profit;
+    let income_tax = debit(&["821"]);
+    let profit_after_tax = profit_before_tax - income_tax;
+
+    let lines = vec![
+        line("01", "Doanh thu bán hàng và cung cấp dịch vụ", revenue),
+        line("02", "Các khoản giảm trừ doanh thu", deductions),
+        total("10", "Doanh thu thuần về bán hàng và cung cấp dịch vụ", net_revenue),
+        line("11", "Giá vốn hàng bán", cogs),
+        total("20", "Lợi nhuận gộp về bán hàng và cung cấp dịch vụ", gross_profit),
+        line("21", "Doanh thu hoạt động tài chính", financial_income),
+        line("22", "Chi phí tài chính", financial_expense),
+        line("24", "Chi phí quản lý kinh doanh", admin_expense),
+        total("30", "Lợi nhuận thuần từ hoạt động kinh doanh", operating_profit),
+        line("31", "Thu nhập khác", other_income),
+        line("32", "Chi phí khác", other_expense),
+        total("40", "Lợi nhuận khác", other_profit),
+        total("50", "Tổng lợi nhuận kế toán trước thuế", profit_before_tax),
+        line("51", "Chi phí thuế TNDN", income_tax),
+        total("60", "Lợi nhuận sau thuế thu nhập doanh nghiệp", profit_after_tax),
+    ];
+
+    IncomeStatement { from, to, lines, profit_after_tax }
+}
@@ -0,0 +1,240 @@
+// gl-core/src/report/xlsx.rs
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_xlsxwriter::{Format, FormatAlign, Workbook, Worksheet, XlsxError};
+
+use super::{BalanceSheet, IncomeStatement, LedgerBook, ReportLine};
+use crate::{LedgerError, TrialBalance};
+
+const AMOUNT_FORMAT: &str = "#,##0;(#,##0);-";
+const DATE_FORMAT: &str = "dd/mm/yyyy";
+
+fn spreadsheet_error(e: XlsxError) -> LedgerError {
+    LedgerError::Spreadsheet(e.to_string())
+}
+
+fn number(amount: Decimal) -> f64 {
+    amount.to_f64().unwrap_or_default()
+}
+
+/// Excel workbook with one worksheet per report.
+pub struct ReportWorkbook {
+    workbook: Workbook,
+    title: Format,
+    header: Format,
+    amount: Format,
+    total_label: Format,
+    total_amount: Format,
+    date: Format,
+}
+
+impl Default for ReportWorkbook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReportWorkbook {
+    pub fn new() -> Self {
+        ReportWorkbook {
+            workbook: Workbook::new(),
+            title: Format::new().set_bold().set_font_size(14),
+            header: Format::new()
+                .set_bold()
+                .set_align(FormatAlign::Center)
+                .set_background_color(0xD9E1F2),
+            amount: Format::new().set_num_format(AMOUNT_FORMAT),
+            total_label: Format::new().set_bold(),
+            total_amount: Format::new().set_bold().set_num_format(AMOUNT_FORMAT),
+            date: Format::new().set_num_format(DATE_FORMAT),
+        }
+    }
+
+    fn sheet(&mut self, name: &str, title: &str, headers: &[&str]) -> Result<&mut Worksheet, LedgerError> {
+        let title_format = self.title.clone();
+        let header_format = self.header.clone();
+        let sheet = self.workbook.add_worksheet();
+        sheet.set_name(name).map_err(spreadsheet_error)?;
+        sheet
+            .write_string_with_format(0, 0, title, &title_format)
+            .map_err(spreadsheet_error)?;
+        for (col, h) in headers.iter().enumerate() {
+            sheet
+                .write_string_with_format(2, col as u16, *h, &header_format)
+                .map_err(spreadsheet_error)?;
+        }
+        Ok(sheet)
+    }
+
+    /// Bảng cân đối tài khoản.
+    pub fn add_trial_balance(&mut self, tb: &TrialBalance) -> Result<(), LedgerError> {
+        let (amount, total_label, total_amount) =
+            (self.amount.clone(), self.total_label.clone(), self.total_amount.clone());
+        let sheet = self.sheet(
+            "Bang can doi TK",
+            "BẢNG CÂN ĐỐI TÀI KHOẢN",
+            &["Số hiệu tài khoản", "Phát sinh Nợ", "Phát sinh Có"],
+        )?;
+        sheet.set_column_width(0, 20).map_err(spreadsheet_error)?;
+        sheet.set_column_width(1, 20).map_err(spreadsheet_error)?;
+        sheet.set_column_width(2, 20).map_err(spreadsheet_error)?;
+
+        let mut row = 3;
+        for b in &tb.balances {
+            sheet.write_string(row, 0, b.account.as_str()).map_err(spreadsheet_error)?;
+            sheet
+                .write_number_with_format(row, 1, number(b.debit_balance), &amount)
+                .map_err(spreadsheet_error)?;
+            sheet
+                .write_number_with_format(row, 2, number(b.credit_balance), &amount)
+                .map_err(spreadsheet_error)?;
+            row += 1;
+        }
+        sheet
+            .write_string_with_format(row, 0, "Tổng cộng", &total_label)
+            .map_err(spreadsheet_error)?;
+        sheet
+            .write_number_with_format(row, 1, number(tb.total_debit), &total_amount)
+            .map_err(spreadsheet_error)?;
+        sheet
+            .write_number_with_format(row, 2, number(tb.total_credit), &total_amount)
+            .map_err(spreadsheet_error)?;
+        Ok(())
+    }
+
+    /// Sổ cái of one account.
+    pub fn add_ledger_book(&mut self, book: &LedgerBook) -> Result<(), LedgerError> {
+        let (amount, total_label, total_amount, date) = (
+            self.amount.clone(),
+            self.total_label.clone(),
+            self.total_amount.clone(),
+            self.date.clone(),
+        );
+        let sheet = self.sheet(
+            &format!("So cai {}", book.account.as_str()),
+            &format!(
+                "SỔ CÁI TÀI KHOẢN {} (từ {} đến {})",
+                book.account.as_str(),
+                book.from.format("%d/%m/%Y"),
+                book.to.format("%d/%m/%Y")
+            ),
+            &["Ngày", "Diễn giải", "TK đối ứng", "Nợ", "Có", "Số dư", "Đảo bút toán"],
+        )?;
+        sheet.set_column_width(0, 12).map_err(spreadsheet_error)?;
+        sheet.set_column_width(1, 40).map_err(spreadsheet_error)?;
+        sheet.set_column_width(2, 14).map_err(spreadsheet_error)?;
+        for col in 3..6 {
+            sheet.set_column_width(col, 18).map_err(spreadsheet_error)?;
+        }
+        sheet.set_column_width(6, 22).map_err(spreadsheet_error)?;
+
+        sheet
+            .write_string_with_format(3, 1, "Số dư đầu kỳ", &total_label)
+            .map_err(spreadsheet_error)?;
+        sheet
+            .write_number_with_format(3, 5, number(book.opening_balance), &total_amount)
+            .map_err(spreadsheet_error)?;
+
+        let mut row = 4;
+        for l in &book.lines {
+            sheet
+                .write_datetime_with_format(row, 0, l.date, &date)
+                .map_err(spreadsheet_error)?;
+            sheet.write_string(row, 1, &l.description).map_err(spreadsheet_error)?;
+            let counter: Vec<&str> = l.counter_accounts.iter().map(|a| a.as_str()).collect();
+            sheet.write_string(row, 2, counter.join(", ")).map_err(spreadsheet_error)?;
+            sheet
+                .write_number_with_format(row, 3, number(l.debit), &amount)
+                .map_err(spreadsheet_error)?;
+            sheet
+                .write_number_with_format(row, 4, number(l.credit), &amount)
+                .map_err(spreadsheet_error)?;
+            sheet
+                .write_number_with_format(row, 5, number(l.balance), &amount)
+                .map_err(spreadsheet_error)?;
+            let link = match (l.reverses, l.reversed_by) {
+                (Some(id), _) => format!("Đảo bút toán #{}", id),
+                (_, Some(id)) => format!("Đã đảo bởi #{}", id),
+                _ => String::new(),
+            };
+            if !link.is_empty() {
+                sheet.write_string(row, 6, link).map_err(spreadsheet_error)?;
+            }
+            row += 1;
+        }
+
+        sheet
+            .write_string_with_format(row, 1, "Cộng phát sinh", &total_label)
+            .map_err(spreadsheet_error)?;
+        sheet
+            .write_number_with_format(row, 3, number(book.total_debit), &total_amount)
+            .map_err(spreadsheet_error)?;
+        sheet
+            .write_number_with_format(row, 4, number(book.total_credit), &total_amount)
+            .map_err(spreadsheet_error)?;
+        sheet
+            .write_string_with_format(row + 1, 1, "Số dư cuối kỳ", &total_label)
+            .map_err(spreadsheet_error)?;
+        sheet
+            .write_number_with_format(row + 1, 5, number(book.closing_balance), &total_amount)
+            .map_err(spreadsheet_error)?;
+        Ok(())
+    }
+
+    pub fn add_balance_sheet(&mut self, bs: &BalanceSheet) -> Result<(), LedgerError> {
+        let title = format!("BẢNG CÂN ĐỐI KẾ TOÁN (B01-DNN) tại ngày {}", bs.as_of.format("%d/%m/%Y"));
+        self.add_statement("B01-DNN", &title, "Số cuối kỳ", &bs.lines)
+    }
+
+    pub fn add_income_statement(&mut self, is: &IncomeStatement) -> Result<(), LedgerError> {
+        let title = format!(
+            "BÁO CÁO KẾT QUẢ HOẠT ĐỘNG KINH DOANH (B02-DNN) từ {} đến {}",
+            is.from.format("%d/%m/%Y"),
+            is.to.format("%d/%m/%Y")
+        );
+        self.add_statement("B02-DNN", &title, "Kỳ này", &is.lines)
+    }
+
+    fn add_statement(
+        &mut self,
+        name: &str,
+        title: &str,
+        amount_header: &str,
+        lines: &[ReportLine],
+    ) -> Result<(), LedgerError> {
+        let (amount, total_label, total_amount) =
+            (self.amount.clone(), self.total_label.clone(), self.total_amount.clone());
+        let sheet = self.sheet(name, title, &["Chỉ tiêu", "Mã số", amount_header])?;
+        sheet.set_column_width(0, 55).map_err(spreadsheet_error)?;
+        sheet.set_column_width(1, 10).map_err(spreadsheet_error)?;
+        sheet.set_column_width(2, 20).map_err(spreadsheet_error)?;
+
+        for (i, l) in lines.iter().enumerate() {
+            let row = 3 + i as u32;
+            let (label_format, amount_format) = if l.is_total {
+                (&total_label, &total_amount)
+            } else {
+                (&Format::new(), &amount)
+            };
+            sheet
+                .write_string_with_format(row, 0, &l.name, label_format)
+                .map_err(spreadsheet_error)?;
+            sheet
+                .write_string_with_format(row, 1, &l.code, label_format)
+                .map_err(spreadsheet_error)?;
+            sheet
+                .write_number_with_format(row, 2, number(l.amount), amount_format)
+                .map_err(spreadsheet_error)?;
+        }
+        Ok(())
+    }
+
+    pub fn save(&mut self, path: &str) -> Result<(), LedgerError> {
+        self.workbook.save(path).map_err(spreadsheet_error)
+    }
+
+    pub fn to_bytes(&mut self) -> Result<Vec<u8>, LedgerError> {
+        self.workbook.save_to_buffer().map_err(spreadsheet_error)
+    }
+}
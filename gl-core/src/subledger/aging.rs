@@ -0,0 +1,289 @@
+// gl-core/src/subledger/aging.rs
+
+//! Aging of receivables (131) and payables (331) by days past due, with the
+//! doubtful-debt provision suggested by Circular 48/2019/TT-BTC.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::matching::collect_items;
+use super::{ItemKind, NormalSide, PAYABLE_ACCOUNT, RECEIVABLE_ACCOUNT};
+use crate::{EntryId, Ledger, LedgerError};
+
+/// Columns of the aging report, by days past due.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AgingBucket {
+    /// Not yet due.
+    Current,
+    Days1To30,
+    Days31To60,
+    Days61To90,
+    Over90,
+}
+
+impl AgingBucket {
+    pub const ALL: [AgingBucket; 5] = [
+        AgingBucket::Current,
+        AgingBucket::Days1To30,
+        AgingBucket::Days31To60,
+        AgingBucket::Days61To90,
+        AgingBucket::Over90,
+    ];
+
+    pub fn for_days_overdue(days: i64) -> Self {
+        match days {
+            i64::MIN..=0 => AgingBucket::Current,
+            1..=30 => AgingBucket::Days1To30,
+            31..=60 => AgingBucket::Days31To60,
+            61..=90 => AgingBucket::Days61To90,
+            _ => AgingBucket::Over90,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AgingBucket::Current => "current",
+            AgingBucket::Days1To30 => "1-30",
+            AgingBucket::Days31To60 => "31-60",
+            AgingBucket::Days61To90 => "61-90",
+            AgingBucket::Over90 => ">90",
+        }
+    }
+}
+
+/// The unsettled part of a charge to a party (an invoice line).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AgingItem {
+    pub entry_id: EntryId,
+    pub date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub voucher: Option<String>,
+    pub description: String,
+    pub open_amount: Decimal,
+    pub days_overdue: i64,
+    pub bucket: AgingBucket,
+}
+
+/// Open amounts of one party by bucket. Payments not yet matched to a
+/// charge (advances) count as a negative amount in `current`, so `total`
+/// is the party's balance.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct AgingLine {
+    pub party: String,
+    pub name: String,
+    pub current: Decimal,
+    pub days_1_30: Decimal,
+    pub days_31_60: Decimal,
+    pub days_61_90: Decimal,
+    pub over_90: Decimal,
+    pub total: Decimal,
+    #[serde(default)]
+    pub items: Vec<AgingItem>,
+}
+
+impl AgingLine {
+    pub fn bucket(&self, bucket: AgingBucket) -> Decimal {
+        match bucket {
+            AgingBucket::Current => self.current,
+            AgingBucket::Days1To30 => self.days_1_30,
+            AgingBucket::Days31To60 => self.days_31_60,
+            AgingBucket::Days61To90 => self.days_61_90,
+            AgingBucket::Over90 => self.over_90,
+        }
+    }
+
+    fn add(&mut self, bucket: AgingBucket, amount: Decimal) {
+        let cell = match bucket {
+            AgingBucket::Current => &mut self.current,
+            AgingBucket::Days1To30 => &mut self.days_1_30,
+            AgingBucket::Days31To60 => &mut self.days_31_60,
+            AgingBucket::Days61To90 => &mut self.days_61_90,
+            AgingBucket::Over90 => &mut self.over_90,
+        };
+        *cell += amount;
+        self.total += amount;
+    }
+}
+
+/// Suggested provision for one overdue receivable.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProvisionLine {
+    pub party: String,
+    pub name: String,
+    pub entry_id: EntryId,
+    pub due_date: NaiveDate,
+    pub days_overdue: i64,
+    pub open_amount: Decimal,
+    /// 0.3, 0.5, 0.7 or 1.
+    pub rate: Decimal,
+    pub provision: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AgingReport {
+    pub account: String,
+    pub as_of: NaiveDate,
+    pub lines: Vec<AgingLine>,
+    /// Column totals; `party` and `name` are empty.
+    pub totals: AgingLine,
+    /// Doubtful-debt provisions (dự phòng phải thu khó đòi), receivables only.
+    pub provisions: Vec<ProvisionLine>,
+    pub total_provision: Decimal,
+}
+
+impl AgingReport {
+    pub fn to_json(&self) -> Result<String, LedgerError> {
+        serde_json::to_string_pretty(self).map_err(|e| LedgerError::Export(e.to_string()))
+    }
+
+    /// One row per party plus a total row, without the item detail.
+    pub fn to_csv(&self) -> Result<String, LedgerError> {
+        let export_error = |e: csv::Error| LedgerError::Export(e.to_string());
+        let mut writer = csv::Writer::from_writer(vec![]);
+        let mut header = vec!["party", "name"];
+        header.extend(AgingBucket::ALL.iter().map(|b| b.label()));
+        header.push("total");
+        writer.write_record(&header).map_err(export_error)?;
+        let total_row = AgingLine { party: "total".to_string(), ..self.totals.clone() };
+        for line in self.lines.iter().chain(std::iter::once(&total_row)) {
+            let mut record = vec![line.party.clone(), line.name.clone()];
+            record.extend(AgingBucket::ALL.iter().map(|b| line.bucket(*b).to_string()));
+            record.push(line.total.to_string());
+            writer.write_record(&record).map_err(export_error)?;
+        }
+        let bytes = writer.into_inner().map_err(|e| LedgerError::Export(e.to_string()))?;
+        String::from_utf8(bytes).map_err(|e| LedgerError::Export(e.to_string()))
+    }
+}
+
+/// Provision rate for a receivable overdue since `due_date` (Circular
+/// 48/2019, art. 6): 30% from 6 months, 50% from 1 year, 70% from 2 years
+/// and 100% from 3 years past due; zero before 6 months.
+pub fn provision_rate(due_date: NaiveDate, as_of: NaiveDate) -> Decimal {
+    let overdue_for = |months: u32| due_date.checked_add_months(Months::new(months)).is_some_and(|d| d <= as_of);
+    if overdue_for(36) {
+        Decimal::ONE
+    } else if overdue_for(24) {
+        Decimal::new(7, 1)
+    } else if overdue_for(12) {
+        Decimal::new(5, 1)
+    } else if overdue_for(6) {
+        Decimal::new(3, 1)
+    } else {
+        Decimal::ZERO
+    }
+}
+
+/// Aging of customer balances on 131 as of a date, with provisions.
+pub fn receivables_aging(ledger: &Ledger, as_of: NaiveDate) -> AgingReport {
+    let customers = ledger.customers();
+    let mut report = aging(ledger, RECEIVABLE_ACCOUNT, NormalSide::Debit, as_of, |code| {
+        customers.get(code).map(|c| c.name.clone()).unwrap_or_default()
+    });
+    for line in &report.lines {
+        for item in &line.items {
+            let rate = provision_rate(item.due_date, as_of);
+            if rate.is_zero() {
+                continue;
+            }
+            report.provisions.push(ProvisionLine {
+                party: line.party.clone(),
+                name: line.name.clone(),
+                entry_id: item.entry_id,
+                due_date: item.due_date,
+                days_overdue: item.days_overdue,
+                open_amount: item.open_amount,
+                rate,
+                provision: (item.open_amount * rate).round_dp(0),
+            });
+        }
+    }
+    report.total_provision = report.provisions.iter().map(|p| p.provision).sum();
+    report
+}
+
+/// Aging of vendor balances on 331 as of a date.
+pub fn payables_aging(ledger: &Ledger, as_of: NaiveDate) -> AgingReport {
+    let vendors = ledger.vendors();
+    aging(ledger, PAYABLE_ACCOUNT, NormalSide::Credit, as_of, |code| {
+        vendors.get(code).map(|v| v.name.clone()).unwrap_or_default()
+    })
+}
+
+/// Buckets each party's open charges. Explicit matches are honoured first;
+/// the rest of each party's settlements then goes to its earliest due
+/// charges. Entries reversed on or before `as_of` are left out together
+/// with their reversals.
+fn aging<F>(ledger: &Ledger, account: &str, side: NormalSide, as_of: NaiveDate, name_of: F) -> AgingReport
+where
+    F: Fn(&str) -> String,
+{
+    let mut charges: BTreeMap<String, Vec<AgingItem>> = BTreeMap::new();
+    let mut settled: BTreeMap<String, Decimal> = BTreeMap::new();
+    for item in collect_items(ledger, account, side, as_of) {
+        match item.kind {
+            ItemKind::Charge => {
+                let days_overdue = (as_of - item.due_date).num_days();
+                charges.entry(item.party.clone()).or_default().push(AgingItem {
+                    entry_id: item.entry_id,
+                    date: item.date,
+                    due_date: item.due_date,
+                    voucher: ledger
+                        .get_entry(item.entry_id)
+                        .and_then(|e| e.entry.voucher.as_ref())
+                        .map(|v| v.number.clone()),
+                    description: item.description.clone(),
+                    open_amount: item.open_amount(),
+                    days_overdue,
+                    bucket: AgingBucket::for_days_overdue(days_overdue),
+                });
+            }
+            ItemKind::Settlement => {
+                *settled.entry(item.party.clone()).or_insert(Decimal::ZERO) += item.open_amount();
+            }
+        }
+    }
+
+    let parties: BTreeSet<String> = charges.keys().chain(settled.keys()).cloned().collect();
+    let mut totals = AgingLine::default();
+    let mut lines = Vec::new();
+    for party in parties {
+        let mut items = charges.remove(&party).unwrap_or_default();
+        items.sort_by_key(|i| (i.due_date, i.entry_id));
+        let mut unapplied = settled.get(&party).copied().unwrap_or_default();
+        for item in items.iter_mut() {
+            let applied = unapplied.min(item.open_amount);
+            item.open_amount -= applied;
+            unapplied -= applied;
+        }
+        items.retain(|i| !i.open_amount.is_zero());
+
+        let mut line = AgingLine { name: name_of(&party), party, ..AgingLine::default() };
+        for item in &items {
+            line.add(item.bucket, item.open_amount);
+        }
+        if !unapplied.is_zero() {
+            line.add(AgingBucket::Current, Decimal::ZERO - unapplied);
+        }
+        if line.total.is_zero() && items.is_empty() {
+            continue;
+        }
+        for bucket in AgingBucket::ALL {
+            totals.add(bucket, line.bucket(bucket));
+        }
+        line.items = items;
+        lines.push(line);
+    }
+
+    AgingReport {
+        account: account.to_string(),
+        as_of,
+        lines,
+        totals,
+        provisions: vec![],
+        total_provision: Decimal::ZERO,
+    }
+}
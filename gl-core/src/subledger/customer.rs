@@ -0,0 +1,141 @@
+// gl-core/src/subledger/customer.rs
+
+//! Customer master and the accounts receivable subledger on 131.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::{party_balances, party_statement, reconcile, NormalSide, PartyBalance, PartyStatement, Reconciliation};
+use crate::{JournalEntry, Ledger, LedgerError};
+
+/// Phải thu của khách hàng, the AR control account.
+pub const RECEIVABLE_ACCOUNT: &str = "131";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Customer {
+    pub code: String,
+    pub name: String,
+    /// Mã số thuế.
+    #[serde(default)]
+    pub tax_code: String,
+    #[serde(default)]
+    pub address: String,
+    /// Maximum receivable balance; no limit when absent.
+    #[serde(default)]
+    pub credit_limit: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct CustomerMaster {
+    pub customers: Vec<Customer>,
+}
+
+impl CustomerMaster {
+    pub fn new() -> Self {
+        CustomerMaster { customers: vec![] }
+    }
+
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(serde_yaml::from_str(contents)?)
+    }
+
+    pub fn get(&self, code: &str) -> Option<&Customer> {
+        self.customers.iter().find(|c| c.code == code)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.customers.is_empty()
+    }
+
+    pub(crate) fn add(&mut self, customer: Customer) -> Result<(), LedgerError> {
+        if customer.code.trim().is_empty() {
+            return Err(LedgerError::InvalidCustomer("customer code is empty".to_string()));
+        }
+        if self.get(&customer.code).is_some() {
+            return Err(LedgerError::DuplicateCustomer(customer.code));
+        }
+        self.customers.push(customer);
+        Ok(())
+    }
+
+    pub(crate) fn update(&mut self, customer: Customer) -> Result<Customer, LedgerError> {
+        let existing = self
+            .customers
+            .iter_mut()
+            .find(|c| c.code == customer.code)
+            .ok_or_else(|| LedgerError::UnknownCustomer(customer.code.clone()))?;
+        Ok(std::mem::replace(existing, customer))
+    }
+
+    fn name_of(&self, code: &str) -> String {
+        self.get(code).map(|c| c.name.clone()).unwrap_or_default()
+    }
+
+    /// Checks the 131 lines of an entry: once the master has customers, each
+    /// line needs a known customer, and debits must stay within its credit
+    /// limit given its current balance plus `pending` (earlier, unposted
+    /// entries of the same batch). Returns the entry's net debit per customer.
+    pub(crate) fn check_entry(
+        &self,
+        entry: &JournalEntry,
+        balance_of: impl Fn(&str) -> Decimal,
+        pending: &BTreeMap<String, Decimal>,
+        check_limit: bool,
+    ) -> Result<BTreeMap<String, Decimal>, LedgerError> {
+        let mut delta: BTreeMap<String, Decimal> = BTreeMap::new();
+        if self.is_empty() {
+            return Ok(delta);
+        }
+        for line in entry.lines.iter().filter(|l| l.account.as_str().starts_with(RECEIVABLE_ACCOUNT)) {
+            let code = line
+                .party
+                .as_deref()
+                .ok_or_else(|| LedgerError::MissingParty(line.account.as_str().to_string()))?;
+            if self.get(code).is_none() {
+                return Err(LedgerError::UnknownCustomer(code.to_string()));
+            }
+            *delta.entry(code.to_string()).or_insert(Decimal::ZERO) += line.debit - line.credit;
+        }
+        if check_limit {
+            for (code, change) in &delta {
+                let Some(limit) = self.get(code).and_then(|c| c.credit_limit) else {
+                    continue;
+                };
+                let balance = balance_of(code) + pending.get(code).copied().unwrap_or_default() + change;
+                if *change > Decimal::ZERO && balance > limit {
+                    return Err(LedgerError::CreditLimitExceeded(code.clone(), limit, balance));
+                }
+            }
+        }
+        Ok(delta)
+    }
+}
+
+/// Receivable balance of every customer as of a date.
+pub fn customer_balances(ledger: &Ledger, as_of: NaiveDate) -> Vec<PartyBalance> {
+    let customers = ledger.customers();
+    party_balances(ledger, RECEIVABLE_ACCOUNT, NormalSide::Debit, as_of, |code| customers.name_of(code))
+}
+
+/// Statement of account of a customer for a date range.
+pub fn customer_statement(
+    ledger: &Ledger,
+    code: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<PartyStatement, LedgerError> {
+    let customer = ledger
+        .customers()
+        .get(code)
+        .ok_or_else(|| LedgerError::UnknownCustomer(code.to_string()))?;
+    Ok(party_statement(ledger, RECEIVABLE_ACCOUNT, NormalSide::Debit, code, &customer.name, from, to))
+}
+
+/// Checks that the AR subledger adds up to the 131 balance as of a date.
+pub fn reconcile_receivables(ledger: &Ledger, as_of: NaiveDate) -> Result<Reconciliation, LedgerError> {
+    let customers = ledger.customers();
+    reconcile(ledger, RECEIVABLE_ACCOUNT, NormalSide::Debit, as_of, |code| customers.name_of(code))
+}
@@ -0,0 +1,287 @@
+// gl-core/src/subledger/matching.rs
+
+//! Open-item management on the control accounts 131 and 331.
+//!
+//! Each entry's net amount for a party on a control account is an item: a
+//! charge when it is on the account's normal side (an invoice), otherwise a
+//! settlement (a payment or credit note). Settlements are matched to
+//! charges, fully or in part, so the open amount of every invoice is known.
+//! Entries that are reversed, and their reversals, are not items.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::{NormalSide, PAYABLE_ACCOUNT, RECEIVABLE_ACCOUNT};
+use crate::{EntryId, InvoicePayment, Ledger, LedgerError, LedgerEvent, Permission};
+
+pub type MatchId = u64;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ItemKind {
+    /// An invoice: the party owes (131) or is owed (331) the amount.
+    Charge,
+    /// A payment or credit note reducing what is owed.
+    Settlement,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OpenItem {
+    pub account: String,
+    pub party: String,
+    pub entry_id: EntryId,
+    pub kind: ItemKind,
+    pub date: NaiveDate,
+    pub due_date: NaiveDate,
+    /// Invoice reference of the lines, or the voucher number of a charge.
+    pub reference: Option<String>,
+    pub description: String,
+    /// Amount of the item, always positive.
+    pub amount: Decimal,
+    pub matched: Decimal,
+}
+
+impl OpenItem {
+    pub fn open_amount(&self) -> Decimal {
+        self.amount - self.matched
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.open_amount().is_zero()
+    }
+}
+
+/// Part of a settlement applied to a charge of the same party.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ItemMatch {
+    pub id: MatchId,
+    pub account: String,
+    pub party: String,
+    pub charge: EntryId,
+    pub settlement: EntryId,
+    pub amount: Decimal,
+}
+
+/// Open items are kept on the AR and AP control accounts only.
+pub(crate) fn control_side(account: &str) -> Result<NormalSide, LedgerError> {
+    match account {
+        RECEIVABLE_ACCOUNT => Ok(NormalSide::Debit),
+        PAYABLE_ACCOUNT => Ok(NormalSide::Credit),
+        _ => Err(LedgerError::NotAControlAccount(account.to_string())),
+    }
+}
+
+/// Items of `account` from entries dated on or before `as_of`, with the
+/// matches between them. Entries reversed by then are left out together
+/// with their reversals.
+pub(crate) fn collect_items(ledger: &Ledger, account: &str, side: NormalSide, as_of: NaiveDate) -> Vec<OpenItem> {
+    let visible: Vec<_> = ledger.entries().iter().filter(|e| e.entry.date <= as_of).collect();
+    let cancelled: BTreeSet<EntryId> = visible
+        .iter()
+        .filter_map(|e| e.reverses.map(|original| [e.id, original]))
+        .flatten()
+        .collect();
+
+    let mut items = Vec::new();
+    for posted in visible.iter().filter(|e| !cancelled.contains(&e.id)) {
+        let mut parties: BTreeMap<&str, (Decimal, Option<NaiveDate>, Option<String>)> = BTreeMap::new();
+        for line in posted.entry.lines.iter().filter(|l| l.account.as_str().starts_with(account)) {
+            let Some(party) = line.party.as_deref() else {
+                continue;
+            };
+            let (net, due_date, reference) = parties.entry(party).or_insert((Decimal::ZERO, None, None));
+            *net += side.signed(line.debit - line.credit);
+            *due_date = due_date.or(line.due_date);
+            if reference.is_none() {
+                reference.clone_from(&line.reference);
+            }
+        }
+        for (party, (net, due_date, reference)) in parties {
+            if net.is_zero() {
+                continue;
+            }
+            let kind = if net > Decimal::ZERO { ItemKind::Charge } else { ItemKind::Settlement };
+            let voucher = posted.entry.voucher.as_ref().map(|v| v.number.clone());
+            items.push(OpenItem {
+                account: account.to_string(),
+                party: party.to_string(),
+                entry_id: posted.id,
+                kind,
+                date: posted.entry.date,
+                due_date: due_date.unwrap_or(posted.entry.date),
+                reference: match kind {
+                    ItemKind::Charge => reference.or(voucher),
+                    ItemKind::Settlement => reference,
+                },
+                description: posted.entry.description.clone(),
+                amount: net.abs(),
+                matched: Decimal::ZERO,
+            });
+        }
+    }
+
+    for m in ledger.item_matches().iter().filter(|m| m.account == account) {
+        let charge = items.iter().position(|i| i.entry_id == m.charge && i.party == m.party);
+        let settlement = items.iter().position(|i| i.entry_id == m.settlement && i.party == m.party);
+        if let (Some(charge), Some(settlement)) = (charge, settlement) {
+            items[charge].matched += m.amount;
+            items[settlement].matched += m.amount;
+        }
+    }
+    items
+}
+
+impl Ledger {
+    /// Every item of a control account (131 or 331) with its matched amount.
+    pub fn open_items(&self, account: &str) -> Result<Vec<OpenItem>, LedgerError> {
+        let side = control_side(account)?;
+        Ok(collect_items(self, account, side, NaiveDate::MAX))
+    }
+
+    /// Items that are not, or only partly, matched.
+    pub fn unmatched_items(&self, account: &str) -> Result<Vec<OpenItem>, LedgerError> {
+        Ok(self.open_items(account)?.into_iter().filter(|i| i.is_open()).collect())
+    }
+
+    pub fn item_matches(&self) -> &[ItemMatch] {
+        &self.item_matches
+    }
+
+    /// Applies `amount` of a settlement to a charge of the same party; by
+    /// default as much as both still have open.
+    pub fn match_items(
+        &mut self,
+        account: &str,
+        party: &str,
+        charge: EntryId,
+        settlement: EntryId,
+        amount: Option<Decimal>,
+    ) -> Result<MatchId, LedgerError> {
+        self.authorize(Permission::PostEntry)?;
+        let items = self.open_items(account)?;
+        let find = |id: EntryId, kind: ItemKind| {
+            items
+                .iter()
+                .find(|i| i.entry_id == id && i.party == party && i.kind == kind)
+                .ok_or_else(|| LedgerError::UnknownOpenItem(id, party.to_string()))
+        };
+        let available = find(charge, ItemKind::Charge)?
+            .open_amount()
+            .min(find(settlement, ItemKind::Settlement)?.open_amount());
+        let amount = amount.unwrap_or(available);
+        if amount <= Decimal::ZERO {
+            return Err(LedgerError::InvalidMatch(format!(
+                "nothing to match between entries {} and {}",
+                charge, settlement
+            )));
+        }
+        if amount > available {
+            return Err(LedgerError::MatchExceedsOpenAmount(amount, available));
+        }
+
+        let id = self.match_counter + 1;
+        self.emit(LedgerEvent::ItemsMatched {
+            matched: ItemMatch {
+                id,
+                account: account.to_string(),
+                party: party.to_string(),
+                charge,
+                settlement,
+                amount,
+            },
+        });
+        Ok(id)
+    }
+
+    pub fn unmatch_items(&mut self, id: MatchId) -> Result<(), LedgerError> {
+        self.authorize(Permission::PostEntry)?;
+        if !self.item_matches.iter().any(|m| m.id == id) {
+            return Err(LedgerError::UnknownMatch(id));
+        }
+        self.emit(LedgerEvent::ItemsUnmatched { id });
+        Ok(())
+    }
+
+    /// Matches open settlements to open charges of the same party: first by
+    /// reference, then a settlement of exactly the open amount of a charge
+    /// to the earliest due such charge. Returns the matches made.
+    pub fn auto_match(&mut self, account: &str) -> Result<Vec<ItemMatch>, LedgerError> {
+        self.authorize(Permission::PostEntry)?;
+        let mut items = self.unmatched_items(account)?;
+        items.sort_by_key(|i| (i.due_date, i.entry_id));
+        let mut open: Vec<Decimal> = items.iter().map(|i| i.open_amount()).collect();
+        let settlements: Vec<usize> = (0..items.len()).filter(|&s| items[s].kind == ItemKind::Settlement).collect();
+        let charges: Vec<usize> = (0..items.len()).filter(|&c| items[c].kind == ItemKind::Charge).collect();
+
+        let mut proposals: Vec<(usize, usize, Decimal)> = Vec::new();
+        for &s in &settlements {
+            let Some(reference) = &items[s].reference else {
+                continue;
+            };
+            for &c in &charges {
+                if open[s].is_zero() {
+                    break;
+                }
+                if items[c].party == items[s].party && items[c].reference.as_ref() == Some(reference) && !open[c].is_zero() {
+                    let amount = open[s].min(open[c]);
+                    open[s] -= amount;
+                    open[c] -= amount;
+                    proposals.push((c, s, amount));
+                }
+            }
+        }
+        for &s in &settlements {
+            if open[s].is_zero() {
+                continue;
+            }
+            if let Some(&c) = charges.iter().find(|&&c| items[c].party == items[s].party && open[c] == open[s]) {
+                proposals.push((c, s, open[s]));
+                open[c] = Decimal::ZERO;
+                open[s] = Decimal::ZERO;
+            }
+        }
+
+        let mut made = Vec::new();
+        for (c, s, amount) in proposals {
+            let id = self.match_items(account, &items[c].party, items[c].entry_id, items[s].entry_id, Some(amount))?;
+            made.extend(self.item_matches.iter().find(|m| m.id == id).cloned());
+        }
+        Ok(made)
+    }
+
+    /// Records a match in the projection; a match on a vendor invoice is
+    /// also a payment of that invoice.
+    pub(crate) fn apply_match(&mut self, matched: ItemMatch) {
+        self.match_counter = self.match_counter.max(matched.id);
+        if matched.account == PAYABLE_ACCOUNT {
+            let date = self.get_entry(matched.settlement).map(|e| e.entry.date);
+            if let (Some(invoice), Some(date)) = (
+                self.vendor_invoices
+                    .iter_mut()
+                    .find(|i| i.entry_id == matched.charge && i.vendor == matched.party),
+                date,
+            ) {
+                invoice.payments.push(InvoicePayment { entry_id: matched.settlement, date, amount: matched.amount });
+            }
+        }
+        self.item_matches.push(matched);
+    }
+
+    pub(crate) fn apply_unmatch(&mut self, id: MatchId) {
+        let Some(index) = self.item_matches.iter().position(|m| m.id == id) else {
+            return;
+        };
+        let matched = self.item_matches.remove(index);
+        if let Some(invoice) = self.vendor_invoices.iter_mut().find(|i| i.entry_id == matched.charge) {
+            if let Some(p) = invoice
+                .payments
+                .iter()
+                .position(|p| p.entry_id == matched.settlement && p.amount == matched.amount)
+            {
+                invoice.payments.remove(p);
+            }
+        }
+    }
+}
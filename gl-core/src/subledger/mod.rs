@@ -0,0 +1,218 @@
+// gl-core/src/subledger/mod.rs
+
+//! Subledgers (sổ chi tiết công nợ) kept per party on a control account.
+//!
+//! Lines on a control account carry the code of the party they concern;
+//! the subledger is the per-party breakdown of those lines and must add up
+//! to the control account balance in the general ledger.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::{EntryId, Ledger, LedgerError};
+
+mod aging;
+mod customer;
+mod matching;
+mod vendor;
+pub use aging::{
+    payables_aging, provision_rate, receivables_aging, AgingBucket, AgingItem, AgingLine, AgingReport, ProvisionLine,
+};
+pub use matching::{ItemKind, ItemMatch, MatchId, OpenItem};
+pub use customer::{
+    customer_balances, customer_statement, reconcile_receivables, Customer, CustomerMaster, RECEIVABLE_ACCOUNT,
+};
+pub use vendor::{
+    payments_due, reconcile_payables, vendor_balances, vendor_statement, InvoiceAllocation, InvoicePayment,
+    PaymentDue, PaymentSchedule, Vendor, VendorInvoice, VendorMaster, PAYABLE_ACCOUNT,
+};
+
+/// Which side a control account's balance is normally on: debit for
+/// receivables (131), credit for payables (331). Balances, statements and
+/// reconciliations are reported positive on that side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum NormalSide {
+    Debit,
+    Credit,
+}
+
+impl NormalSide {
+    fn signed(&self, net_debit: Decimal) -> Decimal {
+        match self {
+            NormalSide::Debit => net_debit,
+            NormalSide::Credit => Decimal::ZERO - net_debit,
+        }
+    }
+}
+
+/// Balance of one party, positive on the normal side of its control account.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PartyBalance {
+    pub party: String,
+    pub name: String,
+    pub balance: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StatementLine {
+    pub entry_id: EntryId,
+    pub date: NaiveDate,
+    pub voucher: Option<String>,
+    pub description: String,
+    pub debit: Decimal,
+    pub credit: Decimal,
+    /// Running balance, positive on the normal side of the account.
+    pub balance: Decimal,
+}
+
+/// Statement of account of one party (bản đối chiếu công nợ).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PartyStatement {
+    pub account: String,
+    pub party: String,
+    pub name: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub opening_balance: Decimal,
+    pub lines: Vec<StatementLine>,
+    pub total_debit: Decimal,
+    pub total_credit: Decimal,
+    pub closing_balance: Decimal,
+}
+
+/// Outcome of a successful reconciliation of a subledger to its control account.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Reconciliation {
+    pub account: String,
+    pub as_of: NaiveDate,
+    pub subledger_total: Decimal,
+    pub ledger_balance: Decimal,
+    pub parties: Vec<PartyBalance>,
+}
+
+/// Net debit per party of the lines on `account` dated on or before `as_of`,
+/// plus the control account balance including lines without a party.
+fn party_totals(ledger: &Ledger, account: &str, as_of: NaiveDate) -> (BTreeMap<String, Decimal>, Decimal) {
+    let mut parties = BTreeMap::new();
+    let mut control = Decimal::ZERO;
+    for posted in ledger.entries().iter().filter(|e| e.entry.date <= as_of) {
+        for line in posted.entry.lines.iter().filter(|l| l.account.as_str().starts_with(account)) {
+            let net = line.debit - line.credit;
+            control += net;
+            if let Some(party) = &line.party {
+                *parties.entry(party.clone()).or_insert(Decimal::ZERO) += net;
+            }
+        }
+    }
+    (parties, control)
+}
+
+/// Balance of every party on a control account as of a date.
+pub(crate) fn party_balances<F>(
+    ledger: &Ledger,
+    account: &str,
+    side: NormalSide,
+    as_of: NaiveDate,
+    name_of: F,
+) -> Vec<PartyBalance>
+where
+    F: Fn(&str) -> String,
+{
+    party_totals(ledger, account, as_of)
+        .0
+        .into_iter()
+        .map(|(party, balance)| PartyBalance { name: name_of(&party), party, balance: side.signed(balance) })
+        .collect()
+}
+
+/// Checks that the subledger adds up to the control account balance.
+pub(crate) fn reconcile<F>(
+    ledger: &Ledger,
+    account: &str,
+    side: NormalSide,
+    as_of: NaiveDate,
+    name_of: F,
+) -> Result<Reconciliation, LedgerError>
+where
+    F: Fn(&str) -> String,
+{
+    let (parties, ledger_balance) = party_totals(ledger, account, as_of);
+    let ledger_balance = side.signed(ledger_balance);
+    let subledger_total: Decimal = parties.values().map(|b| side.signed(*b)).sum();
+    if subledger_total != ledger_balance {
+        return Err(LedgerError::SubledgerMismatch(account.to_string(), subledger_total, ledger_balance));
+    }
+    Ok(Reconciliation {
+        account: account.to_string(),
+        as_of,
+        subledger_total,
+        ledger_balance,
+        parties: parties
+            .into_iter()
+            .map(|(party, balance)| PartyBalance { name: name_of(&party), party, balance: side.signed(balance) })
+            .collect(),
+    })
+}
+
+/// Statement of one party's lines on a control account for a date range.
+pub(crate) fn party_statement(
+    ledger: &Ledger,
+    account: &str,
+    side: NormalSide,
+    party: &str,
+    name: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> PartyStatement {
+    let mut entries: Vec<_> = ledger.entries().iter().filter(|e| e.entry.date <= to).collect();
+    entries.sort_by_key(|e| e.entry.date);
+
+    let mut opening_balance = Decimal::ZERO;
+    let mut balance = Decimal::ZERO;
+    let mut lines = Vec::new();
+    for posted in entries {
+        let own = posted
+            .entry
+            .lines
+            .iter()
+            .filter(|l| l.account.as_str().starts_with(account) && l.party.as_deref() == Some(party));
+        let (debit, credit) = own.fold((Decimal::ZERO, Decimal::ZERO), |(d, c), l| (d + l.debit, c + l.credit));
+        if debit.is_zero() && credit.is_zero() {
+            continue;
+        }
+        let change = side.signed(debit - credit);
+        if posted.entry.date < from {
+            opening_balance += change;
+            balance = opening_balance;
+            continue;
+        }
+        balance += change;
+        lines.push(StatementLine {
+            entry_id: posted.id,
+            date: posted.entry.date,
+            voucher: posted.entry.voucher.as_ref().map(|v| v.number.clone()),
+            description: posted.entry.description.clone(),
+            debit,
+            credit,
+            balance,
+        });
+    }
+
+    let total_debit = lines.iter().map(|l| l.debit).sum();
+    let total_credit = lines.iter().map(|l| l.credit).sum();
+    PartyStatement {
+        account: account.to_string(),
+        party: party.to_string(),
+        name: name.to_string(),
+        from,
+        to,
+        opening_balance,
+        lines,
+        total_debit,
+        total_credit,
+        closing_balance: opening_balance + side.signed(total_debit - total_credit),
+    }
+}
@@ -0,0 +1,402 @@
+// gl-core/src/subledger/vendor.rs
+
+//! Vendor master, vendor invoices with due dates, and the accounts payable
+//! subledger on 331. Payments are matched to invoices, fully or partly.
+
+use chrono::{Days, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::{party_balances, party_statement, reconcile, NormalSide, PartyBalance, PartyStatement, Reconciliation};
+use super::ItemMatch;
+use crate::{EntryId, JournalEntry, Ledger, LedgerError, LedgerEvent, Permission};
+
+/// Phải trả cho người bán, the AP control account.
+pub const PAYABLE_ACCOUNT: &str = "331";
+
+fn default_payment_terms() -> u32 {
+    30
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Vendor {
+    pub code: String,
+    pub name: String,
+    /// Mã số thuế.
+    #[serde(default)]
+    pub tax_code: String,
+    #[serde(default)]
+    pub address: String,
+    /// Days from invoice date to due date when an invoice gives none.
+    #[serde(default = "default_payment_terms")]
+    pub payment_terms_days: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct VendorMaster {
+    pub vendors: Vec<Vendor>,
+}
+
+impl VendorMaster {
+    pub fn new() -> Self {
+        VendorMaster { vendors: vec![] }
+    }
+
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(serde_yaml::from_str(contents)?)
+    }
+
+    pub fn get(&self, code: &str) -> Option<&Vendor> {
+        self.vendors.iter().find(|v| v.code == code)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vendors.is_empty()
+    }
+
+    pub(crate) fn add(&mut self, vendor: Vendor) -> Result<(), LedgerError> {
+        if vendor.code.trim().is_empty() {
+            return Err(LedgerError::InvalidVendor("vendor code is empty".to_string()));
+        }
+        if self.get(&vendor.code).is_some() {
+            return Err(LedgerError::DuplicateVendor(vendor.code));
+        }
+        self.vendors.push(vendor);
+        Ok(())
+    }
+
+    pub(crate) fn update(&mut self, vendor: Vendor) -> Result<Vendor, LedgerError> {
+        let existing = self
+            .vendors
+            .iter_mut()
+            .find(|v| v.code == vendor.code)
+            .ok_or_else(|| LedgerError::UnknownVendor(vendor.code.clone()))?;
+        Ok(std::mem::replace(existing, vendor))
+    }
+
+    fn name_of(&self, code: &str) -> String {
+        self.get(code).map(|v| v.name.clone()).unwrap_or_default()
+    }
+
+    /// Once the master has vendors, every 331 line needs a known vendor.
+    pub(crate) fn check_entry(&self, entry: &JournalEntry) -> Result<(), LedgerError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        for line in entry.lines.iter().filter(|l| l.account.as_str().starts_with(PAYABLE_ACCOUNT)) {
+            let code = line
+                .party
+                .as_deref()
+                .ok_or_else(|| LedgerError::MissingParty(line.account.as_str().to_string()))?;
+            if self.get(code).is_none() {
+                return Err(LedgerError::UnknownVendor(code.to_string()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Part of a payment entry applied to an invoice.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InvoicePayment {
+    pub entry_id: EntryId,
+    pub date: NaiveDate,
+    pub amount: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct VendorInvoice {
+    pub vendor: String,
+    /// The vendor's invoice number, unique per vendor.
+    pub number: String,
+    pub invoice_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub amount: Decimal,
+    /// The entry that recorded the invoice on 331.
+    pub entry_id: EntryId,
+    #[serde(default)]
+    pub payments: Vec<InvoicePayment>,
+}
+
+impl VendorInvoice {
+    pub fn paid(&self) -> Decimal {
+        self.payments.iter().map(|p| p.amount).sum()
+    }
+
+    pub fn outstanding(&self) -> Decimal {
+        self.amount - self.paid()
+    }
+
+    pub fn is_paid(&self) -> bool {
+        self.outstanding() <= Decimal::ZERO
+    }
+}
+
+/// How much of a payment goes to which invoice.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InvoiceAllocation {
+    pub invoice: String,
+    pub amount: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PaymentDue {
+    pub due_date: NaiveDate,
+    pub vendor: String,
+    pub vendor_name: String,
+    pub invoice: String,
+    pub invoice_date: NaiveDate,
+    pub amount: Decimal,
+    pub outstanding: Decimal,
+    /// Days past due at the report date; zero or negative when not yet due.
+    pub days_overdue: i64,
+}
+
+/// Unpaid invoices due on or before a date, earliest first.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PaymentSchedule {
+    pub until: NaiveDate,
+    pub lines: Vec<PaymentDue>,
+    pub total: Decimal,
+}
+
+/// Net credit of a vendor's 331 lines in an entry.
+fn payable_credit(entry: &JournalEntry, vendor: &str) -> Decimal {
+    entry
+        .lines
+        .iter()
+        .filter(|l| l.account.as_str().starts_with(PAYABLE_ACCOUNT) && l.party.as_deref() == Some(vendor))
+        .map(|l| l.credit - l.debit)
+        .sum()
+}
+
+/// Tags the 331 lines that have no party with the vendor.
+fn tag_vendor(mut entry: JournalEntry, vendor: &str) -> JournalEntry {
+    for line in entry.lines.iter_mut() {
+        if line.account.as_str().starts_with(PAYABLE_ACCOUNT) && line.party.is_none() {
+            line.party = Some(vendor.to_string());
+        }
+    }
+    entry
+}
+
+impl Ledger {
+    /// Recorded vendor invoices, in recording order.
+    pub fn vendor_invoices(&self) -> &[VendorInvoice] {
+        &self.vendor_invoices
+    }
+
+    pub fn vendor_invoice(&self, vendor: &str, number: &str) -> Option<&VendorInvoice> {
+        self.vendor_invoices.iter().find(|i| i.vendor == vendor && i.number == number)
+    }
+
+    /// Posts a vendor invoice: the entry's net credit to 331 is the invoice
+    /// amount. 331 lines without a party are assigned to the vendor. The due
+    /// date defaults to the invoice date plus the vendor's payment terms.
+    pub fn record_vendor_invoice(
+        &mut self,
+        vendor: &str,
+        number: &str,
+        entry: JournalEntry,
+        due_date: Option<NaiveDate>,
+    ) -> Result<EntryId, LedgerError> {
+        self.authorize(Permission::PostEntry)?;
+        let terms = self
+            .vendors
+            .get(vendor)
+            .ok_or_else(|| LedgerError::UnknownVendor(vendor.to_string()))?
+            .payment_terms_days;
+        if self.vendor_invoice(vendor, number).is_some() {
+            return Err(LedgerError::DuplicateVendorInvoice(vendor.to_string(), number.to_string()));
+        }
+        let mut entry = tag_vendor(entry, vendor);
+        let amount = payable_credit(&entry, vendor);
+        if amount <= Decimal::ZERO {
+            return Err(LedgerError::InvalidVendorInvoice(format!(
+                "invoice {} does not credit {} for {}",
+                number, PAYABLE_ACCOUNT, vendor
+            )));
+        }
+        let due_date = match due_date {
+            Some(date) => date,
+            None => entry
+                .date
+                .checked_add_days(Days::new(terms as u64))
+                .ok_or(LedgerError::InvalidDate)?,
+        };
+        if due_date < entry.date {
+            return Err(LedgerError::InvalidVendorInvoice(format!(
+                "invoice {} is due before its date",
+                number
+            )));
+        }
+        for line in entry.lines.iter_mut() {
+            if line.account.as_str().starts_with(PAYABLE_ACCOUNT) {
+                line.due_date.get_or_insert(due_date);
+                line.reference.get_or_insert_with(|| number.to_string());
+            }
+        }
+
+        let posted = self.next_entry(entry, None, None)?;
+        let id = posted.id;
+        let invoice = VendorInvoice {
+            vendor: vendor.to_string(),
+            number: number.to_string(),
+            invoice_date: posted.entry.date,
+            due_date,
+            amount,
+            entry_id: id,
+            payments: vec![],
+        };
+        self.emit(LedgerEvent::VendorInvoiceRecorded { entry: posted, invoice });
+        Ok(id)
+    }
+
+    /// Posts a payment to a vendor (net debit to 331) and applies it to
+    /// invoices: as allocated, or to the oldest due invoices first when no
+    /// allocation is given. Any unallocated rest stays as an advance.
+    pub fn pay_vendor(
+        &mut self,
+        vendor: &str,
+        entry: JournalEntry,
+        allocations: Vec<InvoiceAllocation>,
+    ) -> Result<EntryId, LedgerError> {
+        self.authorize(Permission::PostEntry)?;
+        if self.vendors.get(vendor).is_none() {
+            return Err(LedgerError::UnknownVendor(vendor.to_string()));
+        }
+        let entry = tag_vendor(entry, vendor);
+        let amount = Decimal::ZERO - payable_credit(&entry, vendor);
+        if amount <= Decimal::ZERO {
+            return Err(LedgerError::InvalidVendorInvoice(format!(
+                "payment does not debit {} for {}",
+                PAYABLE_ACCOUNT, vendor
+            )));
+        }
+
+        let allocations = if allocations.is_empty() {
+            self.allocate_oldest_first(vendor, amount)
+        } else {
+            allocations
+        };
+        let allocated: Decimal = allocations.iter().map(|a| a.amount).sum();
+        if allocated > amount {
+            return Err(LedgerError::AllocationExceedsPayment(allocated, amount));
+        }
+        for allocation in &allocations {
+            let invoice = self
+                .vendor_invoice(vendor, &allocation.invoice)
+                .ok_or_else(|| LedgerError::UnknownVendorInvoice(vendor.to_string(), allocation.invoice.clone()))?;
+            if allocation.amount <= Decimal::ZERO {
+                return Err(LedgerError::InvalidVendorInvoice(format!(
+                    "allocation to invoice {} must be positive",
+                    allocation.invoice
+                )));
+            }
+            let applied: Decimal = allocations
+                .iter()
+                .filter(|a| a.invoice == allocation.invoice)
+                .map(|a| a.amount)
+                .sum();
+            if applied > invoice.outstanding() {
+                return Err(LedgerError::PaymentExceedsInvoice(
+                    allocation.invoice.clone(),
+                    applied,
+                    invoice.outstanding(),
+                ));
+            }
+        }
+
+        let posted = self.next_entry(entry, None, None)?;
+        let id = posted.id;
+        self.emit(LedgerEvent::VendorPaymentRecorded { entry: posted, vendor: vendor.to_string(), allocations });
+        Ok(id)
+    }
+
+    fn allocate_oldest_first(&self, vendor: &str, amount: Decimal) -> Vec<InvoiceAllocation> {
+        let mut open: Vec<&VendorInvoice> = self
+            .vendor_invoices
+            .iter()
+            .filter(|i| i.vendor == vendor && !i.is_paid())
+            .collect();
+        open.sort_by_key(|i| (i.due_date, i.entry_id));
+
+        let mut remaining = amount;
+        let mut allocations = Vec::new();
+        for invoice in open {
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+            let applied = remaining.min(invoice.outstanding());
+            allocations.push(InvoiceAllocation { invoice: invoice.number.clone(), amount: applied });
+            remaining -= applied;
+        }
+        allocations
+    }
+
+    /// Applies the allocations of a payment event to the invoices, as
+    /// matches of the payment to each invoice's entry.
+    pub(crate) fn apply_vendor_payment(&mut self, vendor: &str, entry_id: EntryId, allocations: &[InvoiceAllocation]) {
+        for allocation in allocations {
+            let Some(charge) = self.vendor_invoice(vendor, &allocation.invoice).map(|i| i.entry_id) else {
+                continue;
+            };
+            self.apply_match(ItemMatch {
+                id: self.match_counter + 1,
+                account: PAYABLE_ACCOUNT.to_string(),
+                party: vendor.to_string(),
+                charge,
+                settlement: entry_id,
+                amount: allocation.amount,
+            });
+        }
+    }
+}
+
+/// Payable balance of every vendor as of a date, credit positive.
+pub fn vendor_balances(ledger: &Ledger, as_of: NaiveDate) -> Vec<PartyBalance> {
+    let vendors = ledger.vendors();
+    party_balances(ledger, PAYABLE_ACCOUNT, NormalSide::Credit, as_of, |code| vendors.name_of(code))
+}
+
+/// Statement of account of a vendor for a date range, credit positive.
+pub fn vendor_statement(
+    ledger: &Ledger,
+    code: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<PartyStatement, LedgerError> {
+    let vendor = ledger
+        .vendors()
+        .get(code)
+        .ok_or_else(|| LedgerError::UnknownVendor(code.to_string()))?;
+    Ok(party_statement(ledger, PAYABLE_ACCOUNT, NormalSide::Credit, code, &vendor.name, from, to))
+}
+
+/// Checks that the AP subledger adds up to the 331 balance as of a date.
+pub fn reconcile_payables(ledger: &Ledger, as_of: NaiveDate) -> Result<Reconciliation, LedgerError> {
+    let vendors = ledger.vendors();
+    reconcile(ledger, PAYABLE_ACCOUNT, NormalSide::Credit, as_of, |code| vendors.name_of(code))
+}
+
+/// Unpaid vendor invoices due on or before `until`, by due date.
+pub fn payments_due(ledger: &Ledger, until: NaiveDate) -> PaymentSchedule {
+    let mut lines: Vec<PaymentDue> = ledger
+        .vendor_invoices()
+        .iter()
+        .filter(|i| !i.is_paid() && i.due_date <= until)
+        .map(|i| PaymentDue {
+            due_date: i.due_date,
+            vendor: i.vendor.clone(),
+            vendor_name: ledger.vendors().name_of(&i.vendor),
+            invoice: i.number.clone(),
+            invoice_date: i.invoice_date,
+            amount: i.amount,
+            outstanding: i.outstanding(),
+            days_overdue: (until - i.due_date).num_days(),
+        })
+        .collect();
+    lines.sort_by(|a, b| (a.due_date, &a.vendor, &a.invoice).cmp(&(b.due_date, &b.vendor, &b.invoice)));
+    let total = lines.iter().map(|l| l.outstanding).sum();
+    PaymentSchedule { until, lines, total }
+}
@@ -0,0 +1,216 @@
+// gl-core/src/voucher/mod.rs
+
+//! Voucher types (chứng từ) and gapless voucher numbering.
+//!
+//! Each voucher type is numbered per month, e.g. `PT2501-0001` is the first
+//! Phiếu thu of January 2025. Numbers are only issued in sequence: the
+//! ledger rejects a voucher that skips or repeats a number of its series.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::LedgerError;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum VoucherType {
+    /// Phiếu thu (01-TT): cash receipt.
+    PhieuThu,
+    /// Phiếu chi (02-TT): cash payment.
+    PhieuChi,
+    /// Giấy báo Nợ: bank debit advice (money leaving the account).
+    BaoNo,
+    /// Giấy báo Có: bank credit advice (money received).
+    BaoCo,
+    /// Phiếu kế toán: general journal voucher.
+    PhieuKeToan,
+}
+
+impl VoucherType {
+    pub const ALL: [VoucherType; 5] = [
+        VoucherType::PhieuThu,
+        VoucherType::PhieuChi,
+        VoucherType::BaoNo,
+        VoucherType::BaoCo,
+        VoucherType::PhieuKeToan,
+    ];
+
+    pub fn default_prefix(&self) -> &'static str {
+        match self {
+            VoucherType::PhieuThu => "PT",
+            VoucherType::PhieuChi => "PC",
+            VoucherType::BaoNo => "BN",
+            VoucherType::BaoCo => "BC",
+            VoucherType::PhieuKeToan => "PKT",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            VoucherType::PhieuThu => "Phiếu thu",
+            VoucherType::PhieuChi => "Phiếu chi",
+            VoucherType::BaoNo => "Giấy báo Nợ",
+            VoucherType::BaoCo => "Giấy báo Có",
+            VoucherType::PhieuKeToan => "Phiếu kế toán",
+        }
+    }
+}
+
+/// A voucher number, kept both as printed and as its parts.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Voucher {
+    pub voucher_type: VoucherType,
+    pub year: i32,
+    pub month: u32,
+    /// Position in the series of this type and month, starting at 1.
+    pub sequence: u32,
+    pub number: String,
+}
+
+impl Voucher {
+    fn series(&self) -> (VoucherType, i32, u32) {
+        (self.voucher_type, self.year, self.month)
+    }
+
+    /// Whether `date` falls in the month the voucher is numbered in.
+    pub fn matches_date(&self, date: NaiveDate) -> bool {
+        date.year() == self.year && date.month() == self.month
+    }
+}
+
+impl fmt::Display for Voucher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number)
+    }
+}
+
+/// How voucher numbers are printed: `{prefix}{yy}{mm}-{sequence}`, the
+/// sequence zero-padded to `digits`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct VoucherFormat {
+    #[serde(default)]
+    pub prefixes: BTreeMap<VoucherType, String>,
+    #[serde(default = "default_digits")]
+    pub digits: usize,
+}
+
+fn default_digits() -> usize {
+    4
+}
+
+impl Default for VoucherFormat {
+    fn default() -> Self {
+        VoucherFormat { prefixes: BTreeMap::new(), digits: default_digits() }
+    }
+}
+
+impl VoucherFormat {
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(serde_yaml::from_str(contents)?)
+    }
+
+    pub fn with_prefix(mut self, voucher_type: VoucherType, prefix: &str) -> Self {
+        self.prefixes.insert(voucher_type, prefix.to_string());
+        self
+    }
+
+    /// Configured prefix of a type, or its default.
+    pub fn prefix(&self, voucher_type: VoucherType) -> &str {
+        self.prefixes
+            .get(&voucher_type)
+            .map(String::as_str)
+            .unwrap_or_else(|| voucher_type.default_prefix())
+    }
+
+    pub fn voucher(&self, voucher_type: VoucherType, date: NaiveDate, sequence: u32) -> Voucher {
+        let number = format!(
+            "{}{:02}{:02}-{:0width$}",
+            self.prefix(voucher_type),
+            date.year() % 100,
+            date.month(),
+            sequence,
+            width = self.digits
+        );
+        Voucher { voucher_type, year: date.year(), month: date.month(), sequence, number }
+    }
+
+    /// Parses a printed number; the type is the one with the longest
+    /// matching prefix, the century is taken from `date`.
+    pub fn parse(&self, number: &str, date: NaiveDate) -> Result<Voucher, LedgerError> {
+        let invalid = || LedgerError::InvalidVoucherNumber(number.to_string());
+        let (voucher_type, rest) = VoucherType::ALL
+            .iter()
+            .filter_map(|t| number.strip_prefix(self.prefix(*t)).map(|rest| (*t, rest)))
+            .min_by_key(|(_, rest)| rest.len())
+            .ok_or_else(invalid)?;
+        let (period, sequence) = rest.split_once('-').ok_or_else(invalid)?;
+        if period.len() != 4
+            || sequence.len() != self.digits
+            || !period.chars().chain(sequence.chars()).all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let yy: i32 = period[..2].parse().map_err(|_| invalid())?;
+        let month: u32 = period[2..].parse().map_err(|_| invalid())?;
+        let sequence: u32 = sequence.parse().map_err(|_| invalid())?;
+        if !(1..=12).contains(&month) || sequence == 0 {
+            return Err(invalid());
+        }
+        let year = date.year() - date.year().rem_euclid(100) + yy;
+        Ok(Voucher { voucher_type, year, month, sequence, number: number.to_string() })
+    }
+}
+
+/// Issues voucher numbers and tracks the last number of every series.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct VoucherNumbering {
+    pub format: VoucherFormat,
+    #[serde(skip)]
+    last: BTreeMap<(VoucherType, i32, u32), u32>,
+}
+
+impl VoucherNumbering {
+    pub fn new(format: VoucherFormat) -> Self {
+        VoucherNumbering { format, last: BTreeMap::new() }
+    }
+
+    /// The next number of the series for `date`; it is only used up once
+    /// an entry carrying it is posted.
+    pub fn next(&self, voucher_type: VoucherType, date: NaiveDate) -> Voucher {
+        let last = self
+            .last
+            .get(&(voucher_type, date.year(), date.month()))
+            .copied()
+            .unwrap_or(0);
+        self.format.voucher(voucher_type, date, last + 1)
+    }
+
+    /// Checks that `voucher` is the next number of its series, belongs to
+    /// the month of `date` and is printed as the format prints its parts.
+    pub fn check(&self, voucher: &Voucher, date: NaiveDate) -> Result<(), LedgerError> {
+        if !voucher.matches_date(date) {
+            return Err(LedgerError::VoucherPeriodMismatch(voucher.number.clone(), date));
+        }
+        if voucher.number != self.format.voucher(voucher.voucher_type, date, voucher.sequence).number {
+            return Err(LedgerError::InvalidVoucherNumber(voucher.number.clone()));
+        }
+        let expected = self.next(voucher.voucher_type, date);
+        if voucher.sequence < expected.sequence {
+            return Err(LedgerError::DuplicateVoucher(voucher.number.clone()));
+        }
+        if voucher.sequence > expected.sequence {
+            return Err(LedgerError::VoucherOutOfSequence(voucher.number.clone(), expected.number));
+        }
+        Ok(())
+    }
+
+    /// Records a posted voucher as the last of its series.
+    pub fn register(&mut self, voucher: &Voucher) {
+        let last = self.last.entry(voucher.series()).or_insert(0);
+        *last = (*last).max(voucher.sequence);
+    }
+}
@@ -0,0 +1,211 @@
+// gl-core/src/workflow/mod.rs
+
+//! Review of entries before they reach the books (duyệt chứng từ).
+//!
+//! A draft moves Draft → Submitted → Approved → Posted. Only a Draft can be
+//! edited, and it may be unbalanced while it is; submitting requires a
+//! balanced entry. Preparing drafts needs `Permission::PrepareEntry`, the
+//! only permission of a clerk, who cannot post directly. Approval needs
+//! `Permission::ApproveEntry` and a user who neither saved nor submitted
+//! the draft. A rejection sends the draft back to Draft with the
+//! reviewer's comment. Balances change only when an approved draft is
+//! posted.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{currency, CoaConfig, EntryId, JournalEntry, Ledger, LedgerError, LedgerEvent, PeriodManager, Permission};
+
+/// Sequential identifier of a draft, starting at 1.
+pub type DraftId = u64;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum DraftStatus {
+    Draft,
+    Submitted,
+    Approved,
+    Posted,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum DraftAction {
+    Saved,
+    Submitted,
+    Approved,
+    Rejected,
+    Posted,
+}
+
+/// One step of a draft's history.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DraftStep {
+    pub action: DraftAction,
+    pub actor: String,
+    pub at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DraftEntry {
+    pub id: DraftId,
+    /// Not checked for balance until the draft is submitted.
+    pub entry: JournalEntry,
+    pub status: DraftStatus,
+    /// Who first saved the draft.
+    pub preparer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approver: Option<String>,
+    /// The posted entry, once the draft is posted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_id: Option<EntryId>,
+    pub history: Vec<DraftStep>,
+}
+
+impl DraftEntry {
+    /// Comments of every rejection, oldest first.
+    pub fn rejection_comments(&self) -> impl Iterator<Item = &str> {
+        self.history
+            .iter()
+            .filter(|s| s.action == DraftAction::Rejected)
+            .filter_map(|s| s.comment.as_deref())
+    }
+
+    /// Whether `user` saved or submitted the draft, and so may not approve it.
+    pub fn prepared_by(&self, user: &str) -> bool {
+        self.history
+            .iter()
+            .any(|s| s.actor == user && matches!(s.action, DraftAction::Saved | DraftAction::Submitted))
+    }
+
+    pub(crate) fn last_action(&self) -> DraftAction {
+        self.history.last().map_or(DraftAction::Saved, |s| s.action)
+    }
+}
+
+impl Ledger {
+    pub fn drafts(&self) -> &[DraftEntry] {
+        &self.drafts
+    }
+
+    pub fn draft(&self, id: DraftId) -> Result<&DraftEntry, LedgerError> {
+        self.drafts.iter().find(|d| d.id == id).ok_or(LedgerError::DraftNotFound(id))
+    }
+
+    /// Saves a new draft; it does not touch the balances.
+    pub fn save_draft(&mut self, entry: JournalEntry) -> Result<DraftId, LedgerError> {
+        self.authorize(Permission::PrepareEntry)?;
+        let id = self.drafts.len() as DraftId + 1;
+        let draft = DraftEntry {
+            id,
+            entry,
+            status: DraftStatus::Draft,
+            preparer: self.actor().to_string(),
+            approver: None,
+            entry_id: None,
+            history: vec![],
+        };
+        self.record_draft(draft, DraftAction::Saved, None);
+        Ok(id)
+    }
+
+    /// Replaces the entry of a draft that is not yet submitted.
+    pub fn update_draft(&mut self, id: DraftId, entry: JournalEntry) -> Result<(), LedgerError> {
+        self.authorize(Permission::PrepareEntry)?;
+        let mut draft = self.draft_in(id, DraftStatus::Draft)?;
+        draft.entry = entry;
+        self.record_draft(draft, DraftAction::Saved, None);
+        Ok(())
+    }
+
+    /// Sends a draft for review; it must be balanced by now.
+    pub fn submit_draft(&mut self, id: DraftId) -> Result<(), LedgerError> {
+        self.authorize(Permission::PrepareEntry)?;
+        let mut draft = self.draft_in(id, DraftStatus::Draft)?;
+        let entry = &draft.entry;
+        if !entry.is_balanced() {
+            let debit = entry.lines.iter().map(|l| l.debit).sum();
+            let credit = entry.lines.iter().map(|l| l.credit).sum();
+            return Err(LedgerError::UnbalancedEntry(debit, credit));
+        }
+        for line in &entry.lines {
+            currency::check_line(line)?;
+        }
+        self.precision.check_entry(entry)?;
+        draft.status = DraftStatus::Submitted;
+        self.record_draft(draft, DraftAction::Submitted, None);
+        Ok(())
+    }
+
+    pub fn approve_draft(&mut self, id: DraftId, comment: Option<&str>) -> Result<(), LedgerError> {
+        self.authorize(Permission::ApproveEntry)?;
+        let mut draft = self.draft_in(id, DraftStatus::Submitted)?;
+        let actor = self.actor().to_string();
+        if draft.prepared_by(&actor) {
+            return Err(LedgerError::SelfApproval(id, actor));
+        }
+        draft.status = DraftStatus::Approved;
+        draft.approver = Some(actor);
+        self.record_draft(draft, DraftAction::Approved, comment);
+        Ok(())
+    }
+
+    /// Sends a submitted or approved draft back to Draft with a comment.
+    pub fn reject_draft(&mut self, id: DraftId, comment: &str) -> Result<(), LedgerError> {
+        self.authorize(Permission::ApproveEntry)?;
+        if comment.trim().is_empty() {
+            return Err(LedgerError::MissingRejectionComment(id));
+        }
+        let mut draft = self.draft(id)?.clone();
+        if !matches!(draft.status, DraftStatus::Submitted | DraftStatus::Approved) {
+            return Err(LedgerError::InvalidDraftStatus(id, draft.status, DraftStatus::Submitted));
+        }
+        draft.status = DraftStatus::Draft;
+        draft.approver = None;
+        self.record_draft(draft, DraftAction::Rejected, Some(comment));
+        Ok(())
+    }
+
+    /// Posts an approved draft after checking it like `validate_entry`; only
+    /// now do the balances change. The approval stands in for the
+    /// permission to post directly.
+    pub fn post_draft(&mut self, id: DraftId, coa: &CoaConfig, periods: &PeriodManager) -> Result<EntryId, LedgerError> {
+        self.authorize(Permission::PrepareEntry)?;
+        let mut draft = self.draft_in(id, DraftStatus::Approved)?;
+        self.validate_entry(&draft.entry, coa, periods)?;
+        let entry_id = self.post_approved(draft.entry.clone())?;
+        draft.status = DraftStatus::Posted;
+        draft.entry_id = Some(entry_id);
+        self.record_draft(draft, DraftAction::Posted, None);
+        Ok(entry_id)
+    }
+
+    fn draft_in(&self, id: DraftId, status: DraftStatus) -> Result<DraftEntry, LedgerError> {
+        let draft = self.draft(id)?;
+        if draft.status != status {
+            return Err(LedgerError::InvalidDraftStatus(id, draft.status, status));
+        }
+        Ok(draft.clone())
+    }
+
+    fn record_draft(&mut self, mut draft: DraftEntry, action: DraftAction, comment: Option<&str>) {
+        draft.history.push(DraftStep {
+            action,
+            actor: self.actor().to_string(),
+            at: Utc::now(),
+            comment: comment.map(str::to_string),
+        });
+        self.emit(LedgerEvent::DraftRecorded { draft });
+    }
+
+    pub(crate) fn apply_draft(&mut self, draft: &DraftEntry) {
+        match self.drafts.iter_mut().find(|d| d.id == draft.id) {
+            Some(existing) => *existing = draft.clone(),
+            None => self.drafts.push(draft.clone()),
+        }
+    }
+}
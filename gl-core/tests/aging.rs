@@ -0,0 +1,157 @@
+mod common;
+
+use chrono::NaiveDate;
+use gl_core::{
+    import_csv, payables_aging, provision_rate, receivables_aging, AgingBucket, ColumnMapping, ImportMode,
+    JournalEntry, Ledger, Vendor,
+};
+use rust_decimal::Decimal;
+
+use common::{line, ymd};
+
+fn credit_sale(date: NaiveDate, customer: &str, amount: i64, due_date: Option<NaiveDate>) -> JournalEntry {
+    let mut receivable = line("131", amount, 0).with_party(customer);
+    if let Some(due) = due_date {
+        receivable = receivable.with_due_date(due);
+    }
+    JournalEntry::new(date, vec![receivable, line("5111", 0, amount)], "Bán hàng".to_string()).unwrap()
+}
+
+fn receipt(date: NaiveDate, customer: &str, amount: i64) -> JournalEntry {
+    JournalEntry::new(
+        date,
+        vec![line("1111", amount, 0), line("131", 0, amount).with_party(customer)],
+        "Thu tiền".to_string(),
+    )
+    .unwrap()
+}
+
+// --- Aging Bucket Tests ---
+#[test]
+fn test_bucket_boundaries() {
+    assert_eq!(AgingBucket::for_days_overdue(-5), AgingBucket::Current);
+    assert_eq!(AgingBucket::for_days_overdue(0), AgingBucket::Current);
+    assert_eq!(AgingBucket::for_days_overdue(1), AgingBucket::Days1To30);
+    assert_eq!(AgingBucket::for_days_overdue(30), AgingBucket::Days1To30);
+    assert_eq!(AgingBucket::for_days_overdue(31), AgingBucket::Days31To60);
+    assert_eq!(AgingBucket::for_days_overdue(90), AgingBucket::Days61To90);
+    assert_eq!(AgingBucket::for_days_overdue(91), AgingBucket::Over90);
+}
+
+#[test]
+fn test_provision_rates_follow_circular_48() {
+    let due = ymd(2022, 1, 31);
+    assert_eq!(provision_rate(due, ymd(2022, 7, 30)), Decimal::ZERO);
+    assert_eq!(provision_rate(due, ymd(2022, 7, 31)), Decimal::new(3, 1));
+    assert_eq!(provision_rate(due, ymd(2023, 1, 31)), Decimal::new(5, 1));
+    assert_eq!(provision_rate(due, ymd(2024, 2, 1)), Decimal::new(7, 1));
+    assert_eq!(provision_rate(due, ymd(2025, 1, 31)), Decimal::ONE);
+}
+
+// --- Aging Report Tests ---
+#[test]
+fn test_receivables_aging_settles_oldest_first() {
+    let mut ledger = Ledger::new();
+    ledger.post_entry(credit_sale(ymd(2025, 1, 5), "KH001", 1_000, Some(ymd(2025, 2, 4)))).unwrap();
+    ledger.post_entry(credit_sale(ymd(2025, 3, 15), "KH001", 500, None)).unwrap();
+    ledger.post_entry(credit_sale(ymd(2025, 4, 20), "KH002", 300, Some(ymd(2025, 5, 20)))).unwrap();
+    ledger.post_entry(receipt(ymd(2025, 4, 1), "KH001", 400)).unwrap();
+
+    let report = receivables_aging(&ledger, ymd(2025, 5, 1));
+    assert_eq!(report.lines.len(), 2);
+    let kh001 = &report.lines[0];
+    assert_eq!(kh001.over_90, Decimal::ZERO);
+    assert_eq!(kh001.days_61_90, Decimal::from(600));
+    assert_eq!(kh001.days_31_60, Decimal::from(500));
+    assert_eq!(kh001.total, Decimal::from(1_100));
+    assert_eq!(report.lines[1].current, Decimal::from(300));
+    assert_eq!(report.totals.total, Decimal::from(1_400));
+    assert!(report.provisions.is_empty());
+
+    // An advance larger than the charges shows as a negative current amount
+    ledger.post_entry(receipt(ymd(2025, 4, 25), "KH002", 500)).unwrap();
+    let report = receivables_aging(&ledger, ymd(2025, 5, 1));
+    assert_eq!(report.lines[1].current, Decimal::from(-200));
+    assert!(report.lines[1].items.is_empty());
+}
+
+#[test]
+fn test_receivables_aging_suggests_provisions() {
+    let mut ledger = Ledger::new();
+    ledger.post_entry(credit_sale(ymd(2023, 1, 10), "KH001", 2_000, Some(ymd(2023, 2, 10)))).unwrap();
+    ledger.post_entry(credit_sale(ymd(2024, 6, 1), "KH001", 1_000, Some(ymd(2024, 7, 1)))).unwrap();
+    ledger.post_entry(credit_sale(ymd(2025, 1, 1), "KH002", 900, Some(ymd(2025, 2, 1)))).unwrap();
+
+    let report = receivables_aging(&ledger, ymd(2025, 3, 1));
+    let rates: Vec<Decimal> = report.provisions.iter().map(|p| p.rate).collect();
+    assert_eq!(rates, vec![Decimal::new(7, 1), Decimal::new(3, 1)]);
+    assert_eq!(report.total_provision, Decimal::from(1_400 + 300));
+}
+
+#[test]
+fn test_reversed_invoices_drop_out_of_aging() {
+    let mut ledger = Ledger::new();
+    let id = ledger.post_entry(credit_sale(ymd(2025, 1, 5), "KH001", 1_000, None)).unwrap();
+    ledger.post_entry(credit_sale(ymd(2025, 1, 6), "KH001", 300, None)).unwrap();
+    ledger.reverse_entry(id, ymd(2025, 1, 20), "Sai giá").unwrap();
+
+    let before = receivables_aging(&ledger, ymd(2025, 1, 10));
+    assert_eq!(before.lines[0].total, Decimal::from(1_300));
+    let after = receivables_aging(&ledger, ymd(2025, 1, 31));
+    assert_eq!(after.lines[0].items.len(), 1);
+    assert_eq!(after.lines[0].items[0].open_amount, Decimal::from(300));
+}
+
+#[test]
+fn test_payables_aging_uses_invoice_due_dates() {
+    let mut ledger = Ledger::new();
+    ledger
+        .add_vendor(Vendor {
+            code: "NCC001".to_string(),
+            name: "Phú Thành".to_string(),
+            tax_code: String::new(),
+            address: String::new(),
+            payment_terms_days: 30,
+        })
+        .unwrap();
+    let purchase = JournalEntry::new(
+        ymd(2025, 1, 1),
+        vec![line("152", 800, 0), line("331", 0, 800)],
+        "Mua hàng".to_string(),
+    )
+    .unwrap();
+    ledger.record_vendor_invoice("NCC001", "HD01", purchase, None).unwrap();
+
+    let report = payables_aging(&ledger, ymd(2025, 3, 15));
+    assert_eq!(report.lines[0].items[0].due_date, ymd(2025, 1, 31));
+    assert_eq!(report.lines[0].days_31_60, Decimal::from(800));
+    assert!(report.provisions.is_empty());
+}
+
+#[test]
+fn test_aging_csv_and_json_output() {
+    let mut ledger = Ledger::new();
+    ledger.post_entry(credit_sale(ymd(2025, 1, 5), "KH001", 1_000, None)).unwrap();
+    let report = receivables_aging(&ledger, ymd(2025, 1, 20));
+
+    let csv = report.to_csv().unwrap();
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows[0], "party,name,current,1-30,31-60,61-90,>90,total");
+    assert_eq!(rows[1], "KH001,,0,1000,0,0,0,1000");
+    assert!(rows[2].starts_with("total,"));
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!(json["lines"][0]["items"][0]["bucket"], "Days1To30");
+}
+
+// --- Import Tests ---
+#[test]
+fn test_import_reads_due_date_column() {
+    let csv = "voucher,date,account,debit,credit,description,party,due_date\n\
+               V1,05/01/2025,131,1.000,,Bán hàng,KH001,04/02/2025\n\
+               V1,05/01/2025,5111,,1.000,Bán hàng,,\n";
+    let result = import_csv(csv.as_bytes(), b',', &ColumnMapping::default(), &ImportMode::AllOrNothing).unwrap();
+    let lines = &result.vouchers[0].entry.lines;
+    assert_eq!(lines[0].due_date, Some(ymd(2025, 2, 4)));
+    assert_eq!(lines[1].due_date, None);
+}
@@ -0,0 +1,204 @@
+mod common;
+
+use gl_core::{
+    accumulated_depreciation, asset_movements, depreciation_schedule, AuditAction, CoaConfig, DepreciationMethod,
+    FixedAsset, JournalEntry, JournalLine, Ledger, LedgerError, LedgerEvent, PeriodManager, PrecisionPolicy,
+};
+use rust_decimal::Decimal;
+
+use common::{balance, code, dec, ymd};
+
+fn coa() -> CoaConfig {
+    common::coa(&["1121", "211", "214", "331", "627", "642", "711", "811"])
+}
+
+/// A laptop for the office: 36.000.000 over 3 years, from mid-January.
+fn laptop() -> FixedAsset {
+    serde_yaml::from_str(
+        "code: TS001\nname: Máy tính xách tay\nclass: E2\ncost: 36000000\nacquired: 2025-01-16\nuseful_life_years: 3\ncost_center: VP\n",
+    )
+    .unwrap()
+}
+
+/// A lathe for the workshop: 120.000.000 over 10 years, charged to 627.
+fn lathe() -> FixedAsset {
+    FixedAsset {
+        code: "TS002".to_string(),
+        name: "Máy tiện CNC".to_string(),
+        class: "B1".to_string(),
+        cost: dec(120_000_000),
+        acquired: ymd(2025, 1, 1),
+        useful_life_years: 10,
+        method: DepreciationMethod::StraightLine,
+        cost_center: "PX1".to_string(),
+        expense_account: code("627"),
+        ..laptop()
+    }
+}
+
+fn purchase(asset: &FixedAsset) -> JournalEntry {
+    JournalEntry::new(
+        asset.acquired,
+        vec![
+            JournalLine::new(code("211"), asset.cost, Decimal::ZERO).unwrap(),
+            JournalLine::new(code("331"), Decimal::ZERO, asset.cost).unwrap(),
+        ],
+        format!("Mua {}", asset.name),
+    )
+    .unwrap()
+}
+
+fn ledger() -> Ledger {
+    let mut ledger = Ledger::new();
+    for asset in [laptop(), lathe()] {
+        ledger.post_entry(purchase(&asset)).unwrap();
+        ledger.register_asset(asset).unwrap();
+    }
+    ledger
+}
+
+// --- Fixed Asset Tests ---
+#[test]
+fn test_register_checks_circular_45_ranges() {
+    let mut ledger = ledger();
+    assert!(matches!(ledger.register_asset(laptop()), Err(LedgerError::DuplicateAsset(_))));
+    let too_long = FixedAsset { code: "TS003".to_string(), useful_life_years: 9, ..laptop() };
+    assert!(matches!(ledger.register_asset(too_long), Err(LedgerError::UsefulLifeOutOfRange(_, 9, 3, 8))));
+    let unknown = FixedAsset { code: "TS004".to_string(), class: "Z9".to_string(), ..laptop() };
+    assert!(matches!(ledger.register_asset(unknown), Err(LedgerError::InvalidAsset(_))));
+    assert_eq!(ledger.assets().len(), 2);
+    assert_eq!(ledger.audit_log().events().last().unwrap().action, AuditAction::AssetRegistered);
+}
+
+#[test]
+fn test_straight_line_schedule_runs_by_day() {
+    let schedule = depreciation_schedule(&laptop(), &PrecisionPolicy::default());
+    // 16 of 31 days in January, then 1.000.000 a month, the rest in January 2028
+    assert_eq!(schedule.len(), 37);
+    assert_eq!(schedule[0].charge, dec(516_129));
+    assert_eq!(schedule[1].charge, dec(1_000_000));
+    assert_eq!(schedule[36].month, ymd(2028, 1, 31));
+    assert_eq!(schedule[36].charge, dec(483_871));
+    assert_eq!(schedule.iter().map(|l| l.charge).sum::<Decimal>(), dec(36_000_000));
+    assert_eq!(schedule[36].net_book_value, Decimal::ZERO);
+}
+
+#[test]
+fn test_declining_balance_switches_to_straight_line() {
+    let asset = FixedAsset {
+        class: "E1".to_string(),
+        cost: dec(100_000_000),
+        acquired: ymd(2025, 1, 1),
+        useful_life_years: 5,
+        method: DepreciationMethod::DecliningBalance,
+        ..laptop()
+    };
+    let precision = PrecisionPolicy::default();
+    let at_year_end = |year: i32| accumulated_depreciation(&asset, ymd(year, 12, 31), &precision);
+    // Rate 20% × 2 = 40%: 40, 24 and 14,4 million, then 21,6 million over the last two years
+    assert_eq!(at_year_end(2025), dec(40_000_000));
+    assert_eq!(at_year_end(2026), dec(64_000_000));
+    assert_eq!(at_year_end(2027), dec(78_400_000));
+    assert_eq!(at_year_end(2028) - at_year_end(2027), dec(10_800_000));
+    assert_eq!(at_year_end(2029), dec(100_000_000));
+    assert_eq!(depreciation_schedule(&asset, &precision)[0].charge, dec(3_333_333));
+}
+
+#[test]
+fn test_monthly_depreciation_posting() {
+    let mut ledger = ledger();
+    let (coa, periods) = (coa(), PeriodManager::new());
+    let id = ledger.post_depreciation(&coa, &periods, ymd(2025, 1, 1)).unwrap().unwrap();
+    let entry = &ledger.get_entry(id).unwrap().entry;
+    assert_eq!(entry.date, ymd(2025, 1, 31));
+    assert_eq!(entry.description, "Khấu hao TSCĐ tháng 01/2025");
+    assert_eq!(balance(&ledger, "627"), dec(1_000_000));
+    assert_eq!(balance(&ledger, "642"), dec(516_129));
+    assert_eq!(balance(&ledger, "214"), dec(-1_516_129));
+    assert!(ledger.post_depreciation(&coa, &periods, ymd(2025, 1, 20)).unwrap().is_none());
+
+    // February was skipped: March catches up
+    ledger.post_depreciation(&coa, &periods, ymd(2025, 3, 1)).unwrap().unwrap();
+    assert_eq!(balance(&ledger, "642"), dec(2_516_129));
+    assert_eq!(ledger.asset("TS001").unwrap().depreciated_through(), Some(ymd(2025, 3, 31)));
+    assert!(matches!(ledger.events().last().unwrap().event, LedgerEvent::DepreciationPosted { .. }));
+}
+
+#[test]
+fn test_disposal_books_gain_and_loss() {
+    let mut ledger = ledger();
+    let (coa, periods) = (coa(), PeriodManager::new());
+    ledger.post_depreciation(&coa, &periods, ymd(2025, 1, 1)).unwrap();
+    let id = ledger.dispose_asset("TS002", ymd(2025, 2, 15), dec(110_000_000), &code("1121"), &coa, &periods).unwrap();
+
+    // 14 days of February: 1.000.000 × 14 / 28
+    let record = ledger.asset("TS002").unwrap();
+    let disposal = record.disposal().unwrap();
+    assert_eq!(disposal.accumulated_depreciation, dec(1_500_000));
+    assert_eq!(disposal.net_book_value, dec(118_500_000));
+    assert_eq!(ledger.get_entry(id).unwrap().entry.lines.len(), 7);
+    assert_eq!(balance(&ledger, "627"), dec(1_500_000));
+    assert_eq!(balance(&ledger, "811"), dec(118_500_000));
+    assert_eq!(balance(&ledger, "711"), dec(-110_000_000));
+    assert_eq!(balance(&ledger, "211"), dec(36_000_000));
+    assert_eq!(balance(&ledger, "214"), dec(-516_129));
+
+    assert!(matches!(
+        ledger.dispose_asset("TS002", ymd(2025, 3, 1), Decimal::ZERO, &code("1121"), &coa, &periods),
+        Err(LedgerError::InvalidAsset(_))
+    ));
+    // A disposed asset is no longer depreciated
+    ledger.post_depreciation(&coa, &periods, ymd(2025, 2, 1)).unwrap();
+    assert_eq!(balance(&ledger, "627"), dec(1_500_000));
+}
+
+#[test]
+fn test_disposal_needs_later_depreciation_reversed() {
+    let mut ledger = ledger();
+    let (coa, periods) = (coa(), PeriodManager::new());
+    let january = ledger.post_depreciation(&coa, &periods, ymd(2025, 1, 1)).unwrap().unwrap();
+    assert!(matches!(
+        ledger.dispose_asset("TS002", ymd(2025, 1, 20), Decimal::ZERO, &code("1121"), &coa, &periods),
+        Err(LedgerError::InvalidAsset(_))
+    ));
+
+    ledger.reverse_entry(january, ymd(2025, 1, 31), "Khấu hao sai").unwrap();
+    assert_eq!(ledger.asset("TS002").unwrap().accumulated_depreciation(), Decimal::ZERO);
+    let disposal =
+        ledger.dispose_asset("TS002", ymd(2025, 1, 20), Decimal::ZERO, &code("1121"), &coa, &periods).unwrap();
+    // 19 days of January
+    assert_eq!(ledger.asset("TS002").unwrap().accumulated_depreciation(), dec(612_903));
+
+    ledger.reverse_entry(disposal, ymd(2025, 1, 31), "Chưa thanh lý").unwrap();
+    let record = ledger.asset("TS002").unwrap();
+    assert!(record.disposal().is_none());
+    assert_eq!(record.accumulated_depreciation(), Decimal::ZERO);
+}
+
+#[test]
+fn test_movement_report() {
+    let mut ledger = ledger();
+    let (coa, periods) = (coa(), PeriodManager::new());
+    for month in 1..=3 {
+        ledger.post_depreciation(&coa, &periods, ymd(2025, month, 1)).unwrap();
+    }
+    ledger.dispose_asset("TS002", ymd(2025, 4, 1), dec(100_000_000), &code("1121"), &coa, &periods).unwrap();
+    ledger.post_depreciation(&coa, &periods, ymd(2025, 4, 1)).unwrap();
+
+    let reloaded: Ledger = serde_json::from_str(&serde_json::to_string(&ledger).unwrap()).unwrap();
+    assert_eq!(reloaded.assets(), ledger.assets());
+
+    let q1 = asset_movements(&reloaded, ymd(2025, 1, 1), ymd(2025, 3, 31));
+    assert_eq!(q1.len(), 2);
+    assert!(q1.iter().all(|m| m.opening_cost.is_zero() && m.additions == m.closing_cost));
+    assert_eq!(q1[1].closing_depreciation, dec(3_000_000));
+
+    let april = asset_movements(&reloaded, ymd(2025, 4, 1), ymd(2025, 4, 30));
+    let lathe = &april[1];
+    assert_eq!((lathe.opening_cost, lathe.disposals, lathe.closing_cost), (dec(120_000_000), dec(120_000_000), Decimal::ZERO));
+    assert_eq!((lathe.depreciation_disposed, lathe.closing_depreciation), (dec(3_000_000), Decimal::ZERO));
+    let laptop = &april[0];
+    assert_eq!(laptop.depreciation, dec(1_000_000));
+    assert_eq!(laptop.net_book_value, dec(36_000_000) - laptop.closing_depreciation);
+    assert!(asset_movements(&reloaded, ymd(2025, 5, 1), ymd(2025, 5, 31)).iter().all(|m| m.code == "TS001"));
+}
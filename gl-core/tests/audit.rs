@@ -0,0 +1,179 @@
+mod common;
+
+use gl_core::{Account, AuditAction, AuditLog, CoaConfig, Ledger, LedgerError, Period, PeriodManager, GENESIS_HASH};
+
+use common::{date, sale};
+
+fn account(code: &str, name: &str) -> Account {
+    Account {
+        code: code.to_string(),
+        name: name.to_string(),
+        classification: "Chi phí".to_string(),
+        default_balance: "debit".to_string(),
+        description: String::new(),
+    }
+}
+
+fn audited_ledger() -> Ledger {
+    let mut ledger = Ledger::new();
+    ledger.set_actor("ketoan1");
+    let id = ledger.post_entry(sale(1, 100)).unwrap();
+    ledger.reverse_entry(id, date(2), "Sai").unwrap();
+
+    let mut periods = PeriodManager::new();
+    let january = Period::new(date(1), date(31));
+    ledger.open_period(&mut periods, january).unwrap();
+    ledger.close_current_period(&mut periods).unwrap();
+
+    let mut coa = CoaConfig { accounts: vec![] };
+    ledger.add_account(&mut coa, account("6421", "Chi phí bán hàng")).unwrap();
+    ledger.update_account(&mut coa, account("6421", "Chi phí bán hàng (sửa)")).unwrap();
+    ledger.remove_account(&mut coa, "6421").unwrap();
+    ledger
+}
+
+// --- Recording Tests ---
+#[test]
+fn test_ledger_records_every_change() {
+    let ledger = audited_ledger();
+    let actions: Vec<AuditAction> = ledger.audit_log().events().iter().map(|e| e.action.clone()).collect();
+
+    assert_eq!(
+        actions,
+        vec![
+            AuditAction::EntryPosted,
+            AuditAction::EntryReversed,
+            AuditAction::PeriodOpened,
+            AuditAction::PeriodClosed,
+            AuditAction::AccountAdded,
+            AuditAction::AccountUpdated,
+            AuditAction::AccountRemoved,
+        ]
+    );
+    assert!(ledger.audit_log().events().iter().all(|e| e.actor == "ketoan1"));
+    assert_eq!(ledger.audit_log().events()[0].prev_hash, GENESIS_HASH);
+}
+
+#[test]
+fn test_duplicate_account_is_rejected() {
+    let mut ledger = Ledger::new();
+    let mut coa = CoaConfig { accounts: vec![account("642", "Chi phí quản lý kinh doanh")] };
+    let result = ledger.add_account(&mut coa, account("642", "Trùng"));
+    assert!(matches!(result, Err(LedgerError::DuplicateAccount(_))));
+    assert!(ledger.audit_log().events().is_empty());
+}
+
+// --- Verification Tests ---
+#[test]
+fn test_verify_intact_chain_returns_head_hash() {
+    let ledger = audited_ledger();
+    let head = ledger.audit_log().verify().unwrap();
+    assert_eq!(head, ledger.audit_log().head_hash());
+}
+
+#[test]
+fn test_verify_detects_edited_record() {
+    let ledger = audited_ledger();
+    let mut json = serde_json::to_value(ledger.audit_log()).unwrap();
+    json["events"][0]["payload"]["entry"]["lines"][0]["debit"] = serde_json::json!("999");
+    let tampered: AuditLog = serde_json::from_value(json).unwrap();
+
+    assert!(matches!(tampered.verify(), Err(LedgerError::AuditChainBroken(1, _))));
+}
+
+#[test]
+fn test_verify_detects_deleted_record() {
+    let ledger = audited_ledger();
+    let mut json = serde_json::to_value(ledger.audit_log()).unwrap();
+    json["events"].as_array_mut().unwrap().remove(2);
+    let tampered: AuditLog = serde_json::from_value(json).unwrap();
+
+    assert!(matches!(tampered.verify(), Err(LedgerError::AuditChainBroken(3, _))));
+}
+
+// --- File Tests ---
+#[test]
+fn test_append_and_load_continues_chain() {
+    let path = std::env::temp_dir().join(format!("gl-audit-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut ledger = Ledger::new();
+    ledger.post_entry(sale(1, 100)).unwrap();
+    ledger.audit_log().append_to(&path, 0).unwrap();
+    let json = serde_json::to_string(&ledger).unwrap();
+
+    let loaded = AuditLog::load(&path).unwrap();
+    let written = loaded.events().len();
+    let stored: Ledger = serde_json::from_str(&json).unwrap();
+    let mut ledger = stored.with_audit_log(loaded).unwrap();
+    ledger.post_entry(sale(1, 100)).unwrap();
+    ledger.audit_log().append_to(&path, written).unwrap();
+
+    let reloaded = AuditLog::load(&path).unwrap();
+    assert_eq!(reloaded.events().len(), 2);
+    assert!(reloaded.verify().is_ok());
+    std::fs::remove_file(&path).unwrap();
+}
+
+// --- Head Tests ---
+#[test]
+fn test_ledger_keeps_the_head_of_its_trail() {
+    let ledger = audited_ledger();
+    let stored: Ledger = serde_json::from_str(&serde_json::to_string(&ledger).unwrap()).unwrap();
+    assert_eq!(stored.audit_log().head(), ledger.audit_log().head());
+    assert!(stored.audit_log().events().is_empty(), "the trail itself is kept beside the ledger");
+
+    // A ledger read without its trail continues the chain, not a new one
+    let mut continued = stored;
+    continued.post_entry(sale(3, 100)).unwrap();
+    let event = &continued.audit_log().events()[0];
+    assert_eq!(event.sequence, 8);
+    assert_eq!(event.prev_hash, ledger.audit_log().head_hash());
+}
+
+#[test]
+fn test_trail_must_end_at_the_ledger_head() {
+    let ledger = audited_ledger();
+    let json = serde_json::to_string(&ledger).unwrap();
+    let stored = || serde_json::from_str::<Ledger>(&json).unwrap();
+    let trail = |events: &[gl_core::AuditEvent]| {
+        let value = serde_json::json!({ "events": events });
+        serde_json::from_value::<AuditLog>(value).unwrap()
+    };
+    let events = ledger.audit_log().events();
+    assert!(stored().with_audit_log(trail(events)).is_ok());
+
+    // The last records deleted: still a valid chain, but shorter
+    assert!(matches!(stored().with_audit_log(trail(&events[..5])), Err(LedgerError::AuditChainBroken(6, _))));
+    // The whole trail deleted
+    assert!(matches!(stored().with_audit_log(AuditLog::new()), Err(LedgerError::AuditChainBroken(1, _))));
+    // A new ledger next to an existing trail
+    assert!(matches!(Ledger::new().with_audit_log(trail(events)), Err(LedgerError::AuditChainBroken(1, _))));
+}
+
+#[test]
+fn test_trail_must_record_the_ledger_events() {
+    let ledger = audited_ledger();
+    let trail = || {
+        let value = serde_json::json!({ "events": ledger.audit_log().events() });
+        serde_json::from_value::<AuditLog>(value).unwrap()
+    };
+    let mut json = serde_json::to_value(&ledger).unwrap();
+    assert_eq!(ledger.events()[0].recorded_at, ledger.audit_log().events()[0].timestamp);
+
+    // The amount of the first sale edited in the ledger file, and the
+    // snapshot dropped: it still replays
+    json["snapshots"] = serde_json::json!([]);
+    json["events"][0]["event"]["EntryPosted"]["entry"]["entry"]["lines"][0]["debit"] = serde_json::json!("900");
+    json["events"][0]["event"]["EntryPosted"]["entry"]["entry"]["lines"][1]["credit"] = serde_json::json!("900");
+    let edited: Ledger = serde_json::from_value(json.clone()).unwrap();
+    assert!(matches!(edited.with_audit_log(trail()), Err(LedgerError::AuditChainBroken(1, _))));
+
+    // An event dropped from the ledger file
+    let mut json = serde_json::to_value(&ledger).unwrap();
+    let events = json["events"].as_array_mut().unwrap();
+    events.pop();
+    json["snapshots"] = serde_json::json!([]);
+    let shortened: Ledger = serde_json::from_value(json).unwrap();
+    assert!(shortened.with_audit_log(trail()).is_err());
+}
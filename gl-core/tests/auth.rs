@@ -0,0 +1,200 @@
+mod common;
+
+use gl_core::{
+    Account, AuditAction, CoaConfig, Customer, ExchangeRate, JournalEntry, Ledger, LedgerError, Period, Permission,
+    PeriodManager, Role, UserDirectory, Vendor,
+};
+
+use common::{date, dec, ledger_as, line, sale, users};
+
+// --- Role Tests ---
+#[test]
+fn test_role_permissions() {
+    assert!(Role::Clerk.allows(Permission::PrepareEntry));
+    assert!(!Role::Clerk.allows(Permission::PostEntry));
+    assert!(!Role::Clerk.allows(Permission::ReverseEntry));
+    assert!(Role::ChiefAccountant.allows(Permission::PostEntry));
+    assert!(Role::ChiefAccountant.allows(Permission::ManagePeriods));
+    assert!(Role::ChiefAccountant.allows(Permission::EditCoa));
+    assert!(Role::Auditor.permissions().is_empty());
+}
+
+#[test]
+fn test_directory_from_yaml() {
+    let yaml = "users:\n  - username: ketoan1\n    roles: [Clerk, Director]\n    token_sha256: abc\n";
+    let directory = UserDirectory::from_yaml(yaml).unwrap();
+    let ketoan1 = directory.get("ketoan1").unwrap();
+    assert!(ketoan1.can(Permission::PrepareEntry));
+    assert!(!ketoan1.can(Permission::EditCoa));
+
+    let duplicate = format!("{}{}", yaml, &yaml["users:\n".len()..]);
+    assert!(UserDirectory::from_yaml(&duplicate).is_err());
+    assert!(UserDirectory::from_yaml("users:\n  - username: ke toan\n    roles: []\n").is_err());
+
+    assert_eq!(users().authenticate("ketoantruong-token").unwrap().username, "ketoantruong");
+    assert!(users().authenticate("wrong").is_none());
+}
+
+// --- Enforcement Tests ---
+#[test]
+fn test_clerk_prepares_drafts_but_cannot_post_or_reverse() {
+    let mut ledger = ledger_as("ketoanvien");
+    assert!(matches!(ledger.post_entry(sale(1, 100)), Err(LedgerError::PermissionDenied(_, Permission::PostEntry))));
+    assert!(!ledger.may(Permission::PostEntry));
+    ledger.save_draft(sale(1, 100)).unwrap();
+    assert!(ledger.entries().is_empty());
+
+    ledger.set_actor("ketoantruong");
+    let id = ledger.post_entry(sale(1, 100)).unwrap();
+    ledger.set_actor("ketoanvien");
+    let result = ledger.reverse_entry(id, date(2), "Sai");
+    assert!(matches!(result, Err(LedgerError::PermissionDenied(_, Permission::ReverseEntry))));
+
+    let mut periods = PeriodManager::empty();
+    let january = Period::new(date(1), date(31));
+    assert!(ledger.open_period(&mut periods, january).is_err());
+    assert!(periods.periods().is_empty());
+
+    let mut coa = CoaConfig { accounts: vec![] };
+    let account = Account {
+        code: "6421".to_string(),
+        name: "Chi phí bán hàng".to_string(),
+        classification: String::new(),
+        default_balance: String::new(),
+        description: String::new(),
+    };
+    assert!(matches!(ledger.add_account(&mut coa, account), Err(LedgerError::PermissionDenied(..))));
+    assert!(coa.accounts.is_empty());
+}
+
+#[test]
+fn test_auditor_is_read_only_and_refusals_are_audited() {
+    let mut ledger = ledger_as("kiemtoan");
+    assert!(matches!(ledger.post_entry(sale(1, 100)), Err(LedgerError::PermissionDenied(_, Permission::PostEntry))));
+    assert!(ledger.entries().is_empty());
+    let denied = ledger.audit_log().events().last().unwrap();
+    assert_eq!(denied.action, AuditAction::PermissionDenied);
+    assert_eq!(denied.actor, "kiemtoan");
+    assert_eq!(denied.payload["permission"], "PostEntry");
+
+    let coa = common::coa(&["1111", "5111"]);
+    let errors = ledger.post_batch(vec![sale(1, 100), sale(2, 50)], &coa, &PeriodManager::new()).unwrap_err();
+    assert!(matches!(errors[0].error, LedgerError::PermissionDenied(_, Permission::PostEntry)));
+    assert!(ledger.entries().is_empty());
+    assert_eq!(ledger.audit_log().events().last().unwrap().action, AuditAction::PermissionDenied);
+
+    let mut stranger = ledger_as("khach");
+    assert!(matches!(stranger.post_entry(sale(1, 100)), Err(LedgerError::UnknownUser(_))));
+}
+
+#[test]
+fn test_auditor_cannot_match_items_or_set_rates() {
+    let mut ledger = ledger_as("ketoantruong");
+    let invoice = JournalEntry::new(
+        date(5),
+        vec![line("131", 1_000, 0).with_party("KH001"), line("5111", 0, 1_000)],
+        "Hóa đơn".to_string(),
+    )
+    .unwrap();
+    let receipt = JournalEntry::new(
+        date(10),
+        vec![line("1111", 1_000, 0), line("131", 0, 1_000).with_party("KH001")],
+        "Thu tiền".to_string(),
+    )
+    .unwrap();
+    let charge = ledger.post_entry(invoice).unwrap();
+    let payment = ledger.post_entry(receipt).unwrap();
+    let matched = ledger.match_items("131", "KH001", charge, payment, None).unwrap();
+
+    ledger.set_actor("kiemtoan");
+    let refused = |result: Result<(), LedgerError>| {
+        matches!(result, Err(LedgerError::PermissionDenied(_, Permission::PostEntry)))
+    };
+    assert!(refused(ledger.unmatch_items(matched)));
+    assert!(refused(ledger.match_items("131", "KH001", charge, payment, None).map(|_| ())));
+    assert!(refused(ledger.auto_match("131").map(|_| ())));
+    let rate = ExchangeRate {
+        currency: "USD".to_string(),
+        date: date(10),
+        buying: dec(25_000),
+        selling: dec(25_400),
+        source: "VCB".to_string(),
+    };
+    assert!(refused(ledger.set_exchange_rate(rate)));
+    assert_eq!(ledger.item_matches().len(), 1);
+    assert!(ledger.rates().rates.is_empty());
+}
+
+#[test]
+fn test_only_chief_accountant_manages_customers_and_vendors() {
+    let customer = Customer {
+        code: "KH001".to_string(),
+        name: "Công ty ABC".to_string(),
+        tax_code: String::new(),
+        address: String::new(),
+        credit_limit: None,
+    };
+    let vendor = Vendor {
+        code: "NCC001".to_string(),
+        name: "Công ty XYZ".to_string(),
+        tax_code: String::new(),
+        address: String::new(),
+        payment_terms_days: 30,
+    };
+    let mut ledger = ledger_as("ketoanvien");
+    let refused = |result: Result<(), LedgerError>| {
+        matches!(result, Err(LedgerError::PermissionDenied(_, Permission::ManageMasters)))
+    };
+    assert!(refused(ledger.add_customer(customer.clone())));
+    assert!(refused(ledger.add_vendor(vendor.clone())));
+    assert!(ledger.customers().is_empty() && ledger.vendors().is_empty());
+    let denied = ledger.audit_log().events().last().unwrap();
+    assert_eq!(denied.action, AuditAction::PermissionDenied);
+    assert_eq!(denied.payload["permission"], "ManageMasters");
+
+    ledger.set_actor("ketoantruong");
+    ledger.add_customer(customer.clone()).unwrap();
+    ledger.add_vendor(vendor.clone()).unwrap();
+    ledger.set_actor("giamdoc");
+    assert!(refused(ledger.update_customer(Customer { credit_limit: Some(dec(1_000)), ..customer })));
+    assert!(refused(ledger.update_vendor(Vendor { payment_terms_days: 60, ..vendor })));
+    assert_eq!(ledger.vendors().get("NCC001").unwrap().payment_terms_days, 30);
+}
+
+#[test]
+fn test_chief_accountant_corrects_and_closes() {
+    let mut ledger = ledger_as("ketoantruong");
+    let id = ledger.post_entry(sale(1, 100)).unwrap();
+    ledger.reverse_entry(id, date(2), "Sai").unwrap();
+    let mut periods = PeriodManager::empty();
+    let january = Period::new(date(1), date(31));
+    ledger.open_period(&mut periods, january).unwrap();
+    ledger.close_current_period(&mut periods).unwrap();
+    assert!(ledger.audit_log().events().iter().all(|e| e.actor == "ketoantruong"));
+
+    // Without a directory nobody is restricted
+    let mut open = Ledger::new();
+    open.set_actor("kiemtoan");
+    assert!(open.post_entry(sale(1, 100)).is_ok());
+}
+
+#[test]
+fn test_users_are_stored_with_the_ledger() {
+    let mut ledger = ledger_as("ketoantruong");
+    ledger.post_entry(sale(1, 100)).unwrap();
+    let json = serde_json::to_string(&ledger).unwrap();
+    let mut stored: Ledger = serde_json::from_str(&json).unwrap();
+    assert_eq!(stored.users(), &users());
+
+    // Reloaded books are still protected, and only a chief accountant replaces the users
+    stored.set_actor("kiemtoan");
+    assert!(matches!(stored.post_entry(sale(2, 100)), Err(LedgerError::PermissionDenied(..))));
+    let everyone_chief = UserDirectory { users: vec![common::user("kiemtoan", vec![Role::ChiefAccountant])] };
+    assert!(matches!(
+        stored.set_users(everyone_chief.clone()),
+        Err(LedgerError::PermissionDenied(_, Permission::ManageUsers))
+    ));
+    stored.set_actor("ketoantruong");
+    stored.set_users(everyone_chief).unwrap();
+    assert_eq!(stored.audit_log().events().last().unwrap().action, AuditAction::UsersChanged);
+}
@@ -0,0 +1,87 @@
+// gl-core/tests/common/mod.rs
+
+//! Fixtures shared by the integration tests. Dates default to January 2025;
+//! users sign in with the token `<username>-token`.
+
+#![allow(dead_code)]
+
+use chrono::NaiveDate;
+use gl_core::{
+    hash_token, Account, AccountCode, CoaConfig, JournalEntry, JournalLine, Ledger, Role, User, UserDirectory,
+};
+use rust_decimal::Decimal;
+
+pub fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+/// A day of January 2025.
+pub fn date(day: u32) -> NaiveDate {
+    ymd(2025, 1, day)
+}
+
+pub fn dec(amount: i64) -> Decimal {
+    Decimal::from(amount)
+}
+
+pub fn code(code: &str) -> AccountCode {
+    AccountCode::new(code).unwrap()
+}
+
+pub fn line(account: &str, debit: i64, credit: i64) -> JournalLine {
+    JournalLine::new(code(account), dec(debit), dec(credit)).unwrap()
+}
+
+/// A cash sale: Nợ 1111 / Có 5111.
+pub fn sale(day: u32, amount: i64) -> JournalEntry {
+    JournalEntry::new(date(day), vec![line("1111", amount, 0), line("5111", 0, amount)], "Bán hàng".to_string())
+        .unwrap()
+}
+
+/// A chart with just these accounts.
+pub fn coa(codes: &[&str]) -> CoaConfig {
+    let account = |code: &str| Account {
+        code: code.to_string(),
+        name: String::new(),
+        classification: String::new(),
+        default_balance: String::new(),
+        description: String::new(),
+    };
+    CoaConfig { accounts: codes.iter().map(|c| account(c)).collect() }
+}
+
+/// Net debit (debit - credit) of an account.
+pub fn balance(ledger: &Ledger, account: &str) -> Decimal {
+    ledger
+        .get_account_balance(&code(account))
+        .map(|b| b.debit_balance - b.credit_balance)
+        .unwrap_or(Decimal::ZERO)
+}
+
+pub fn user(username: &str, roles: Vec<Role>) -> User {
+    User {
+        username: username.to_string(),
+        name: String::new(),
+        roles,
+        token_sha256: Some(hash_token(&format!("{}-token", username))),
+    }
+}
+
+/// One user of each role.
+pub fn users() -> UserDirectory {
+    UserDirectory {
+        users: vec![
+            user("ketoanvien", vec![Role::Clerk]),
+            user("ketoantruong", vec![Role::ChiefAccountant]),
+            user("giamdoc", vec![Role::Director]),
+            user("kiemtoan", vec![Role::Auditor]),
+        ],
+    }
+}
+
+/// A ledger enforcing `users()`, acted on by `actor`.
+pub fn ledger_as(actor: &str) -> Ledger {
+    let mut ledger = Ledger::new().with_users(users());
+    ledger.set_actor(actor);
+    ledger
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use chrono::NaiveDate;
+use gl_core::{
+    AccountingMethod, CoaConfig, CoaRegime, Company, CompanyBooks, CompanyRegistry, Ledger, LedgerError, Workspace,
+};
+
+use common::sale;
+
+fn company(id: &str, tax_code: &str) -> Company {
+    Company {
+        id: id.to_string(),
+        name: format!("Công ty {}", id),
+        tax_code: tax_code.to_string(),
+        address: String::new(),
+        fiscal_year_start: 1,
+        coa_regime: CoaRegime::Circular133,
+        accounting_method: AccountingMethod::Perpetual,
+        coa_file: None,
+    }
+}
+
+fn coa() -> CoaConfig {
+    common::coa(&["1111", "5111"])
+}
+
+// --- Company Tests ---
+#[test]
+fn test_company_validation() {
+    assert!(company("phuthanh", "0312345678").check().is_ok());
+    assert!(company("chi-nhanh_1", "0312345678-001").check().is_ok());
+    assert!(matches!(company("phu thanh", "0312345678").check(), Err(LedgerError::InvalidCompany(_))));
+    assert!(matches!(company("phuthanh", "031234567").check(), Err(LedgerError::InvalidCompany(_))));
+    let mut bad_year = company("phuthanh", "0312345678");
+    bad_year.fiscal_year_start = 13;
+    assert!(bad_year.check().is_err());
+}
+
+#[test]
+fn test_fiscal_year_start() {
+    let mut anphat = company("anphat", "0109876543");
+    anphat.fiscal_year_start = 4;
+    let year = anphat.fiscal_year_of(NaiveDate::from_ymd_opt(2025, 2, 15).unwrap()).unwrap();
+    assert_eq!(year.start_date, NaiveDate::from_ymd_opt(2024, 4, 1).unwrap());
+    assert_eq!(year.end_date, NaiveDate::from_ymd_opt(2025, 3, 31).unwrap());
+
+    let mut books = CompanyBooks::new(anphat, coa()).unwrap();
+    books.open_fiscal_year(2024).unwrap();
+    assert!(books.periods().is_date_in_open_period(NaiveDate::from_ymd_opt(2025, 3, 31).unwrap()));
+    assert!(!books.periods().is_date_in_open_period(NaiveDate::from_ymd_opt(2025, 4, 1).unwrap()));
+}
+
+#[test]
+fn test_registry_from_yaml() {
+    let yaml = "companies:\n  - id: phuthanh\n    name: Phú Thành\n    tax_code: \"0312345678\"\n    accounting_method: Periodic\n";
+    let registry = CompanyRegistry::from_yaml(yaml).unwrap();
+    let phuthanh = registry.get("phuthanh").unwrap();
+    assert_eq!(phuthanh.fiscal_year_start, 1);
+    assert_eq!(phuthanh.coa_regime, CoaRegime::Circular133);
+    assert_eq!(phuthanh.accounting_method, AccountingMethod::Periodic);
+    assert!(matches!(registry.get("anphat"), Err(LedgerError::UnknownCompany(_))));
+
+    let duplicate = format!("{}{}", yaml, &yaml["companies:\n".len()..]);
+    assert!(CompanyRegistry::from_yaml(&duplicate).is_err());
+}
+
+// --- Workspace Isolation Tests ---
+#[test]
+fn test_companies_keep_separate_books() {
+    let mut workspace = Workspace::new();
+    workspace.add_company(company("phuthanh", "0312345678"), coa()).unwrap();
+    workspace.add_company(company("anphat", "0109876543"), coa()).unwrap();
+    assert!(matches!(
+        workspace.add_company(company("anphat", "0109876543"), coa()),
+        Err(LedgerError::DuplicateCompany(_))
+    ));
+    workspace.books_mut("phuthanh").unwrap().open_fiscal_year(2025).unwrap();
+
+    workspace.post_entry("phuthanh", sale(5, 1_000)).unwrap();
+    // No open period in An Phát's books
+    assert!(matches!(workspace.post_entry("anphat", sale(5, 1_000)), Err(LedgerError::DateNotInOpenPeriod(_))));
+    assert!(matches!(workspace.post_entry("khac", sale(5, 1_000)), Err(LedgerError::UnknownCompany(_))));
+
+    assert_eq!(workspace.books("phuthanh").unwrap().ledger().entries().len(), 1);
+    assert!(workspace.books("anphat").unwrap().ledger().entries().is_empty());
+    assert_eq!(workspace.books("anphat").unwrap().ledger().company(), Some("anphat"));
+    assert_eq!(workspace.companies().count(), 2);
+}
+
+#[test]
+fn test_ledger_cannot_move_between_companies() {
+    let mut books = CompanyBooks::new(company("phuthanh", "0312345678"), coa()).unwrap();
+    books.open_fiscal_year(2025).unwrap();
+    books.post_entry(sale(5, 1_000)).unwrap();
+
+    let json = serde_json::to_string(books.ledger()).unwrap();
+    let stored: Ledger = serde_json::from_str(&json).unwrap();
+    assert_eq!(stored.company(), Some("phuthanh"));
+    assert!(matches!(
+        CompanyBooks::with_ledger(company("anphat", "0109876543"), coa(), stored.clone()),
+        Err(LedgerError::CompanyMismatch(_, _))
+    ));
+    assert!(stored.clone().for_company("anphat").is_err());
+    assert_eq!(stored.state_at(0).unwrap().company(), Some("phuthanh"));
+
+    let reopened = CompanyBooks::with_ledger(company("phuthanh", "0312345678"), coa(), stored).unwrap();
+    assert_eq!(reopened.into_ledger().entries().len(), 1);
+}
+
+#[test]
+fn test_reopened_books_keep_their_periods() {
+    let mut books = CompanyBooks::new(company("phuthanh", "0312345678"), coa()).unwrap();
+    books.open_fiscal_year(2025).unwrap();
+    books.close_current_period().unwrap();
+    books.post_entry(sale(5, 1_000)).unwrap();
+
+    let json = serde_json::to_string(books.ledger()).unwrap();
+    let stored: Ledger = serde_json::from_str(&json).unwrap();
+    let mut reopened = CompanyBooks::with_ledger(company("phuthanh", "0312345678"), coa(), stored).unwrap();
+    assert_eq!(reopened.periods().periods().len(), 12);
+    // December was closed, January is still open
+    assert!(!reopened.periods().periods()[11].is_open());
+    reopened.post_entry(sale(6, 2_000)).unwrap();
+    assert_eq!(reopened.ledger().entries().len(), 2);
+}
@@ -0,0 +1,247 @@
+mod common;
+
+use gl_core::{
+    currency_balances, import_csv, import_rates_csv, revaluation, AccountCode, AuditAction, ColumnMapping,
+    ExchangeRate, ForeignAmount, ImportMode, JournalEntry, Ledger, LedgerError, Period, PeriodManager,
+    PrecisionPolicy,
+};
+use rust_decimal::Decimal;
+
+use common::{date, line, ymd};
+
+fn usd(amount: i64, rate: i64) -> ForeignAmount {
+    ForeignAmount::new("USD", Decimal::from(amount), Decimal::from(rate)).unwrap()
+}
+
+// --- Foreign Amount Tests ---
+#[test]
+fn test_foreign_amount_validation() {
+    assert!(matches!(
+        ForeignAmount::new("usd", Decimal::ONE, Decimal::ONE),
+        Err(LedgerError::InvalidCurrency(_))
+    ));
+    assert!(matches!(
+        ForeignAmount::new("VND", Decimal::ONE, Decimal::ONE),
+        Err(LedgerError::InvalidCurrency(_))
+    ));
+    assert!(matches!(
+        ForeignAmount::new("USD", Decimal::ONE, Decimal::ZERO),
+        Err(LedgerError::InvalidCurrency(_))
+    ));
+    assert!(matches!(
+        ForeignAmount::new("USD", Decimal::NEGATIVE_ONE, Decimal::ONE),
+        Err(LedgerError::NegativeAmount(_))
+    ));
+
+    let cents = ForeignAmount::new("USD", Decimal::new(1050, 2), Decimal::from(25_455)).unwrap();
+    assert_eq!(cents.functional_amount(&PrecisionPolicy::default()), Decimal::from(267_278));
+}
+
+#[test]
+fn test_line_amount_must_agree_with_rate() {
+    let wrong = line("1122", 250_000, 0).with_foreign(usd(10, 25_400));
+    assert!(matches!(wrong, Err(LedgerError::CurrencyAmountMismatch(_, _, _))));
+
+    // Rounding to the đồng is tolerated
+    let cents = ForeignAmount::new("USD", Decimal::new(1050, 2), Decimal::from(25_455)).unwrap();
+    assert!(line("1122", 267_278, 0).with_foreign(cents).is_ok());
+}
+
+// --- Ledger Tests ---
+#[test]
+fn test_entries_balance_in_vnd_and_report_currency_balances() {
+    let mut ledger = Ledger::new();
+    let deposit = JournalEntry::new(
+        date(6),
+        vec![line("1122", 254_000, 0).with_foreign(usd(10, 25_400)).unwrap(), line("411", 0, 254_000)],
+        "Nhận vốn góp".to_string(),
+    )
+    .unwrap();
+    ledger.post_entry(deposit).unwrap();
+    let payment = JournalEntry::new(
+        date(15),
+        vec![
+            line("331", 101_800, 0).with_foreign(usd(4, 25_450)).unwrap(),
+            line("1122", 0, 101_800).with_foreign(usd(4, 25_450)).unwrap(),
+        ],
+        "Trả tiền".to_string(),
+    )
+    .unwrap();
+    ledger.post_entry(payment).unwrap();
+
+    let balances = currency_balances(&ledger, date(31));
+    assert_eq!(balances.len(), 2);
+    assert_eq!(balances[0].account.as_str(), "1122");
+    assert_eq!(balances[0].foreign_balance, Decimal::from(6));
+    assert_eq!(balances[0].balance, Decimal::from(152_200));
+    assert_eq!(balances[1].foreign_balance, Decimal::from(4));
+    assert_eq!(currency_balances(&ledger, date(10)).len(), 1);
+
+    let json = serde_json::to_string(&ledger).unwrap();
+    let restored: Ledger = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.entries()[0].entry.lines[0].foreign, Some(usd(10, 25_400)));
+    assert!(!json.contains("\"foreign\":null"));
+}
+
+// --- Import Tests ---
+#[test]
+fn test_import_reads_currency_columns() {
+    let csv = "voucher,date,account,debit,credit,description,party,currency,foreign_amount,rate\n\
+               V1,06/01/2025,1122,254.000,,Nhận vốn,,USD,10,25.400\n\
+               V1,06/01/2025,411,,254.000,Nhận vốn,,,,\n";
+    let result = import_csv(csv.as_bytes(), b',', &ColumnMapping::default(), &ImportMode::AllOrNothing).unwrap();
+    let lines = &result.vouchers[0].entry.lines;
+    assert_eq!(lines[0].foreign, Some(usd(10, 25_400)));
+    assert_eq!(lines[1].foreign, None);
+
+    let mismatch = "voucher,date,account,debit,credit,description,party,currency,foreign_amount,rate\n\
+                    V1,06/01/2025,1122,250.000,,Nhận vốn,,USD,10,25.400\n\
+                    V1,06/01/2025,411,,250.000,Nhận vốn,,,,\n";
+    let result = import_csv(mismatch.as_bytes(), b',', &ColumnMapping::default(), &ImportMode::AllOrNothing).unwrap();
+    assert!(!result.is_ok());
+    assert!(result.vouchers.is_empty());
+}
+
+// --- Exchange Rate Tests ---
+fn rate(day: u32, buying: i64, selling: i64) -> ExchangeRate {
+    ExchangeRate::new("USD", date(day), Decimal::from(buying), Decimal::from(selling)).unwrap()
+}
+
+#[test]
+fn test_rate_table_lookup_and_audit() {
+    let mut ledger = Ledger::new();
+    ledger.set_exchange_rate(rate(10, 25_300, 25_600)).unwrap();
+    ledger.set_exchange_rate(rate(20, 25_400, 25_700)).unwrap();
+    ledger.set_exchange_rate(rate(20, 25_410, 25_710)).unwrap();
+
+    assert!(ledger.rates().get("USD", date(9)).is_none());
+    assert_eq!(ledger.rates().get("USD", date(15)).unwrap().buying, Decimal::from(25_300));
+    assert_eq!(ledger.rates().get("USD", date(31)).unwrap().selling, Decimal::from(25_710));
+    assert_eq!(ledger.rates().rates.len(), 2);
+    assert_eq!(ledger.audit_log().events().last().unwrap().action, AuditAction::ExchangeRateSet);
+
+    assert!(matches!(
+        ExchangeRate::new("VND", date(1), Decimal::ONE, Decimal::ONE),
+        Err(LedgerError::InvalidCurrency(_))
+    ));
+
+    let json = serde_json::to_string(&ledger).unwrap();
+    let restored: Ledger = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.rates().rates, ledger.rates().rates);
+}
+
+#[test]
+fn test_import_rates_csv() {
+    let csv = "date,currency,buying,selling,source\n\
+               31/01/2025,USD,25.300,25.660,VCB\n\
+               31/01/2025,EUR,\"26.150,50\",,SBV\n";
+    let rates = import_rates_csv(csv.as_bytes(), b',').unwrap();
+    assert_eq!(rates[0].selling, Decimal::from(25_660));
+    assert_eq!(rates[0].source, "VCB");
+    assert_eq!(rates[1].buying, Decimal::new(2615050, 2));
+    assert_eq!(rates[1].selling, rates[1].buying);
+
+    let bad = "date,currency,buying\n31/01/2025,usd,25.300\n";
+    assert!(matches!(import_rates_csv(bad.as_bytes(), b','), Err(LedgerError::InvalidImport(_))));
+    assert!(import_rates_csv("date,currency\n".as_bytes(), b',').is_err());
+}
+
+// --- Revaluation Tests ---
+fn fx_ledger() -> Ledger {
+    let mut ledger = Ledger::new();
+    let deposit = JournalEntry::new(
+        date(6),
+        vec![line("1122", 254_000, 0).with_foreign(usd(10, 25_400)).unwrap(), line("411", 0, 254_000)],
+        "Nhận vốn góp".to_string(),
+    )
+    .unwrap();
+    ledger.post_entry(deposit).unwrap();
+    let purchase = JournalEntry::new(
+        date(10),
+        vec![
+            line("152", 101_400, 0),
+            line("331", 0, 101_400).with_party("NCC003").with_foreign(usd(4, 25_350)).unwrap(),
+        ],
+        "Nhập khẩu".to_string(),
+    )
+    .unwrap();
+    ledger.post_entry(purchase).unwrap();
+    ledger
+}
+
+fn january() -> PeriodManager {
+    let mut periods = PeriodManager::new();
+    periods.add_period(Period::new(date(1), date(31)));
+    periods
+}
+
+#[test]
+fn test_revaluation_uses_buying_rate_for_assets_and_selling_for_liabilities() {
+    let mut ledger = fx_ledger();
+    ledger.set_exchange_rate(rate(31, 25_500, 25_600)).unwrap();
+
+    let lines = revaluation(&ledger, date(31)).unwrap();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].account.as_str(), "1122");
+    assert_eq!(lines[0].rate, Decimal::from(25_500));
+    assert_eq!(lines[0].difference, Decimal::from(1_000));
+    assert_eq!(lines[1].party.as_deref(), Some("NCC003"));
+    assert_eq!(lines[1].rate, Decimal::from(25_600));
+    assert_eq!(lines[1].difference, Decimal::from(-1_000));
+
+    assert!(matches!(revaluation(&ledger, date(30)), Err(LedgerError::MissingExchangeRate(_, _))));
+}
+
+#[test]
+fn test_revaluation_posts_to_413_and_reverses_next_period() {
+    let mut ledger = fx_ledger();
+    ledger.set_exchange_rate(rate(31, 25_600, 25_600)).unwrap();
+
+    let id = ledger.revalue_currencies(&january(), date(31)).unwrap().unwrap();
+    let balance = |ledger: &Ledger, code: &str| {
+        ledger
+            .get_account_balance(&AccountCode::new(code).unwrap())
+            .map(|b| b.debit_balance - b.credit_balance)
+            .unwrap_or_default()
+    };
+    // 1122 gains 2.000, 331 loses 1.000: net gain to 515, 413 cleared
+    assert_eq!(balance(&ledger, "1122"), Decimal::from(256_000));
+    assert_eq!(balance(&ledger, "331"), Decimal::from(-102_400));
+    assert_eq!(balance(&ledger, "413"), Decimal::ZERO);
+    assert_eq!(balance(&ledger, "515"), Decimal::from(-1_000));
+    assert_eq!(ledger.get_entry(id).unwrap().auto_reverse_on, Some(ymd(2025, 2, 1)));
+
+    let balances = currency_balances(&ledger, date(31));
+    assert_eq!(balances[0].foreign_balance, Decimal::from(10));
+    assert_eq!(balances[0].balance, Decimal::from(256_000));
+    // Already at the closing rate
+    assert_eq!(ledger.revalue_currencies(&january(), date(31)).unwrap(), None);
+
+    let reversals = ledger.run_auto_reversals(ymd(2025, 2, 1)).unwrap();
+    assert_eq!(reversals.len(), 1);
+    assert_eq!(balance(&ledger, "1122"), Decimal::from(254_000));
+    assert_eq!(balance(&ledger, "515"), Decimal::ZERO);
+}
+
+#[test]
+fn test_revaluation_refuses_closed_period() {
+    let mut ledger = fx_ledger();
+    ledger.set_exchange_rate(rate(31, 25_600, 25_600)).unwrap();
+    let mut periods = PeriodManager::empty();
+    periods.add_period(Period::new(date(1), date(31)));
+    periods.add_period(Period::new(ymd(2025, 2, 1), ymd(2025, 2, 28)));
+    ledger.close_period(&mut periods, date(31)).unwrap();
+    let posted = ledger.entries().len();
+    assert!(matches!(ledger.revalue_currencies(&periods, date(31)), Err(LedgerError::DateNotInOpenPeriod(_))));
+    assert_eq!(ledger.entries().len(), posted);
+}
+
+#[test]
+fn test_revaluation_loss_goes_to_635() {
+    let mut ledger = fx_ledger();
+    ledger.set_exchange_rate(rate(31, 25_200, 25_700)).unwrap();
+    ledger.revalue_currencies(&january(), date(31)).unwrap().unwrap();
+    let loss = ledger.get_account_balance(&AccountCode::new("635").unwrap()).unwrap();
+    assert_eq!(loss.debit_balance, Decimal::from(2_000 + 1_400));
+    ledger.verify_projection().unwrap();
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use gl_core::{
+    amount_in_words, format_vnd, AccountCode, CashVoucher, CashVoucherKind, CompanyInfo, JournalEntry, LedgerError,
+    VoucherFormat, VoucherParty, VoucherType,
+};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+use common::{date, line};
+
+/// Any TrueType font with Vietnamese glyphs; PDF tests are skipped without it.
+const FONT: &str = "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf";
+
+fn entry(lines: &[(&str, i64, i64)]) -> JournalEntry {
+    JournalEntry::new(
+        date(5),
+        lines.iter().map(|(a, d, c)| line(a, *d, *c)).collect(),
+        "Thu tiền bán hàng".to_string(),
+    )
+    .unwrap()
+}
+
+fn company() -> CompanyInfo {
+    CompanyInfo { name: "Công ty TNHH ABC".to_string(), address: "12 Lê Lợi, Quận 1, TP.HCM".to_string() }
+}
+
+fn party() -> VoucherParty {
+    VoucherParty { name: "Nguyễn Văn A".to_string(), address: "Hà Nội".to_string() }
+}
+
+// --- Amount In Words Tests ---
+#[test]
+fn test_amount_in_words() {
+    let cases = [
+        (0_i64, "Không đồng chẵn"),
+        (15, "Mười lăm đồng chẵn"),
+        (21, "Hai mươi mốt đồng chẵn"),
+        (105, "Một trăm linh năm đồng chẵn"),
+        (100_000, "Một trăm nghìn đồng chẵn"),
+        (1_050_000, "Một triệu không trăm năm mươi nghìn đồng chẵn"),
+        (2_024_000, "Hai triệu không trăm hai mươi tư nghìn đồng chẵn"),
+        (1_000_000_000, "Một tỷ đồng chẵn"),
+        (2_000_500_000_000, "Hai nghìn tỷ năm trăm triệu đồng chẵn"),
+    ];
+    for (amount, words) in cases {
+        assert_eq!(amount_in_words(Decimal::from(amount)), words, "{}", amount);
+    }
+}
+
+#[test]
+fn test_amount_in_words_rounds_to_dong() {
+    let amount = Decimal::from_str("1000000.50").unwrap();
+    assert_eq!(amount_in_words(amount), "Một triệu không trăm linh một đồng chẵn");
+    assert_eq!(format_vnd(amount), "1.000.001");
+    assert_eq!(format_vnd(Decimal::from(250_000)), "250.000");
+}
+
+// --- Cash Voucher Tests ---
+#[test]
+fn test_receipt_from_entry() {
+    let format = VoucherFormat::default();
+    let sale = entry(&[("1111", 100_000, 0), ("5111", 0, 100_000)])
+        .with_voucher(format.voucher(VoucherType::PhieuThu, date(5), 1));
+    let voucher = CashVoucher::from_entry(&sale, company(), party()).unwrap();
+
+    assert_eq!(voucher.kind, CashVoucherKind::Receipt);
+    assert_eq!(voucher.number, "PT2501-0001");
+    assert_eq!(voucher.amount, Decimal::from(100_000));
+    assert_eq!(voucher.debit_accounts, vec![AccountCode::new("1111").unwrap()]);
+    assert_eq!(voucher.credit_accounts, vec![AccountCode::new("5111").unwrap()]);
+}
+
+#[test]
+fn test_payment_and_non_cash_entries() {
+    let payment = entry(&[("642", 250_000, 0), ("1111", 0, 250_000)]);
+    let voucher = CashVoucher::from_entry(&payment, company(), party()).unwrap();
+    assert_eq!(voucher.kind, CashVoucherKind::Payment);
+    assert_eq!(voucher.kind.form(), "02-TT");
+
+    let bank = entry(&[("1121", 100, 0), ("131", 0, 100)]);
+    assert!(matches!(
+        CashVoucher::from_entry(&bank, company(), party()),
+        Err(LedgerError::InvalidDocument(_))
+    ));
+
+    let mislabelled = payment.with_voucher(VoucherFormat::default().voucher(VoucherType::PhieuThu, date(5), 1));
+    assert!(matches!(
+        CashVoucher::from_entry(&mislabelled, company(), party()),
+        Err(LedgerError::InvalidDocument(_))
+    ));
+}
+
+#[test]
+fn test_render_html() {
+    let sale = entry(&[("1111", 100_000, 0), ("5111", 0, 100_000)]);
+    let mut party = party();
+    party.name = "Trần <B>".to_string();
+    let html = CashVoucher::from_entry(&sale, company(), party).unwrap().with_attachments(2).render_html();
+
+    assert!(html.contains("PHIẾU THU"));
+    assert!(html.contains("Mẫu số 01-TT"));
+    assert!(html.contains("Ngày 05 tháng 01 năm 2025"));
+    assert!(html.contains("Họ và tên người nộp tiền: Trần &lt;B&gt;"));
+    assert!(html.contains("Số tiền: 100.000 đồng"));
+    assert!(html.contains("Một trăm nghìn đồng chẵn"));
+    assert!(html.contains("Kèm theo: 2 chứng từ gốc"));
+    assert!(html.contains("Thủ quỹ"));
+}
+
+#[test]
+fn test_render_pdf() {
+    let Ok(font) = std::fs::read(FONT) else {
+        eprintln!("skipping: {} not found", FONT);
+        return;
+    };
+    let payment = entry(&[("642", 250_000, 0), ("1111", 0, 250_000)]);
+    let pdf = CashVoucher::from_entry(&payment, company(), party()).unwrap().render_pdf(&font).unwrap();
+    assert!(pdf.starts_with(b"%PDF"));
+
+    let invalid = CashVoucher::from_entry(&payment, company(), party()).unwrap().render_pdf(b"not a font");
+    assert!(matches!(invalid, Err(LedgerError::Pdf(_))));
+}
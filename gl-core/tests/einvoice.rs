@@ -0,0 +1,154 @@
+use chrono::Days;
+use gl_core::{
+    parse_einvoice, AccountCode, EInvoiceImporter, EInvoiceItemKind, EInvoiceMapping, Ledger,
+    LedgerError, PrecisionPolicy, RoundingMode, Vendor, VendorMaster,
+};
+use rust_decimal::Decimal;
+
+const SAMPLE: &str = include_str!("../../data/sample_einvoice.xml");
+const SELLER_MST: &str = "0101234567";
+const BUYER_MST: &str = "0309876543";
+
+fn line_amounts(entry: &gl_core::JournalEntry, code: &str) -> (Decimal, Decimal) {
+    let line = entry
+        .lines
+        .iter()
+        .find(|l| l.account.as_str() == code)
+        .unwrap_or_else(|| panic!("missing line for {}", code));
+    (line.debit, line.credit)
+}
+
+// --- Parsing Tests ---
+#[test]
+fn test_parse_einvoice_header_and_parties() {
+    let invoice = parse_einvoice(SAMPLE).unwrap();
+    assert_eq!(invoice.template, "1");
+    assert_eq!(invoice.series, "C25TAA");
+    assert_eq!(invoice.number, "123");
+    assert_eq!(invoice.seller.tax_code, SELLER_MST);
+    assert_eq!(invoice.buyer.tax_code, BUYER_MST);
+    assert_eq!(invoice.items.len(), 3);
+    assert_eq!(invoice.items[2].kind, EInvoiceItemKind::Discount);
+    assert_eq!(invoice.total_payment, Decimal::from(8_800_000));
+    assert!(invoice.seller_signed);
+}
+
+#[test]
+fn test_parse_einvoice_rejects_malformed_xml() {
+    let result = parse_einvoice("<HDon><DLHDon></DLHDon></HDon>");
+    assert!(matches!(result, Err(LedgerError::InvalidInvoice(_))));
+}
+
+// --- Mapping Tests ---
+#[test]
+fn test_purchase_invoice_maps_to_156_133_331() {
+    let importer = EInvoiceImporter::new(EInvoiceMapping::new(BUYER_MST));
+    let entry = importer.import_xml(&Ledger::new(), SAMPLE).unwrap();
+
+    assert!(entry.is_balanced());
+    assert_eq!(line_amounts(&entry, "156"), (Decimal::from(8_000_000), Decimal::ZERO));
+    assert_eq!(line_amounts(&entry, "133"), (Decimal::from(800_000), Decimal::ZERO));
+    assert_eq!(line_amounts(&entry, "331"), (Decimal::ZERO, Decimal::from(8_800_000)));
+}
+
+#[test]
+fn test_sale_invoice_maps_to_131_5111_3331() {
+    let importer = EInvoiceImporter::new(EInvoiceMapping::new(SELLER_MST));
+    let entry = importer.import_xml(&Ledger::new(), SAMPLE).unwrap();
+
+    assert!(entry.is_balanced());
+    assert_eq!(line_amounts(&entry, "131"), (Decimal::from(8_800_000), Decimal::ZERO));
+    assert_eq!(line_amounts(&entry, "5111"), (Decimal::ZERO, Decimal::from(8_000_000)));
+    assert_eq!(line_amounts(&entry, "3331"), (Decimal::ZERO, Decimal::from(800_000)));
+}
+
+#[test]
+fn test_item_account_override() {
+    let mut mapping = EInvoiceMapping::new(BUYER_MST);
+    mapping
+        .purchase
+        .item_accounts
+        .insert("VC".to_string(), AccountCode::new("642").unwrap());
+    let importer = EInvoiceImporter::new(mapping);
+    let entry = importer.import_xml(&Ledger::new(), SAMPLE).unwrap();
+
+    // Discount reduces the default line account, transport goes to 642
+    assert_eq!(line_amounts(&entry, "156"), (Decimal::from(7_500_000), Decimal::ZERO));
+    assert_eq!(line_amounts(&entry, "642"), (Decimal::from(500_000), Decimal::ZERO));
+    assert!(entry.is_balanced());
+}
+
+#[test]
+fn test_foreign_invoice_is_rounded_with_the_policy() {
+    // At this rate the net amount is 25 đồng and the 10% VAT 2,5 đồng
+    let usd = SAMPLE
+        .replace("<DVTTe>VND</DVTTe>", "<DVTTe>USD</DVTTe>")
+        .replace("<TGia>1</TGia>", "<TGia>0.000003125</TGia>");
+
+    let importer = EInvoiceImporter::new(EInvoiceMapping::new(BUYER_MST));
+    let entry = importer.import_xml(&Ledger::new(), &usd).unwrap();
+    assert_eq!(line_amounts(&entry, "156"), (Decimal::from(25), Decimal::ZERO));
+    assert_eq!(line_amounts(&entry, "133"), (Decimal::from(3), Decimal::ZERO));
+    assert_eq!(line_amounts(&entry, "331"), (Decimal::ZERO, Decimal::from(28)));
+
+    let policy = PrecisionPolicy { tax_rounding: RoundingMode::HalfEven, ..Default::default() };
+    let importer = EInvoiceImporter::new(EInvoiceMapping::new(BUYER_MST));
+    let entry = importer.import_xml(&Ledger::new().with_precision(policy), &usd).unwrap();
+    assert_eq!(line_amounts(&entry, "133"), (Decimal::from(2), Decimal::ZERO));
+    assert_eq!(line_amounts(&entry, "331"), (Decimal::ZERO, Decimal::from(27)));
+    assert!(entry.is_balanced());
+}
+
+#[test]
+fn test_unrelated_company_is_rejected() {
+    let importer = EInvoiceImporter::new(EInvoiceMapping::new("0000000000"));
+    assert!(importer.import_xml(&Ledger::new(), SAMPLE).is_err());
+}
+
+// --- Counterparty Tests ---
+#[test]
+fn test_counter_line_carries_partner_invoice_and_due_date() {
+    let importer = EInvoiceImporter::new(EInvoiceMapping::new(BUYER_MST));
+    let entry = importer.import_xml(&Ledger::new(), SAMPLE).unwrap();
+    let payable = entry.lines.iter().find(|l| l.account.as_str() == "331").unwrap();
+    assert_eq!(payable.party.as_deref(), Some(SELLER_MST));
+    assert_eq!(payable.reference.as_deref(), Some("C25TAA-123"));
+    assert_eq!(payable.due_date, Some(entry.date));
+
+    // A known vendor is referred to by its code, and its terms set the due date
+    let vendors = VendorMaster {
+        vendors: vec![Vendor {
+            code: "NCC01".to_string(),
+            name: "Công ty Cung Cấp".to_string(),
+            tax_code: SELLER_MST.to_string(),
+            address: String::new(),
+            payment_terms_days: 30,
+        }],
+    };
+    let ledger = Ledger::new().with_vendors(vendors);
+    let entry = importer.import_xml(&ledger, SAMPLE).unwrap();
+    let payable = entry.lines.iter().find(|l| l.account.as_str() == "331").unwrap();
+    assert_eq!(payable.party.as_deref(), Some("NCC01"));
+    assert_eq!(payable.due_date, entry.date.checked_add_days(Days::new(30)));
+}
+
+// --- Duplicate Detection Tests ---
+#[test]
+fn test_posted_invoice_is_a_duplicate() {
+    let importer = EInvoiceImporter::new(EInvoiceMapping::new(BUYER_MST));
+    let mut ledger = Ledger::new();
+    // Importing alone does not use the invoice up
+    importer.import_xml(&ledger, SAMPLE).unwrap();
+    let entry = importer.import_xml(&ledger, SAMPLE).unwrap();
+    let id = ledger.post_entry(entry).unwrap();
+
+    let result = importer.import_xml(&ledger, SAMPLE);
+    assert!(matches!(result, Err(LedgerError::DuplicateInvoice(s, n)) if s == "C25TAA" && n == "123"));
+    // The same invoice as a sale of the seller is not this purchase
+    let seller = EInvoiceImporter::new(EInvoiceMapping::new(SELLER_MST));
+    assert!(seller.import_xml(&ledger, SAMPLE).is_ok());
+
+    // Once reversed it can be imported again
+    ledger.reverse_entry(id, ledger.get_entry(id).unwrap().entry.date, "Sai").unwrap();
+    assert!(importer.import_xml(&ledger, SAMPLE).is_ok());
+}
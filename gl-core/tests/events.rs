@@ -0,0 +1,213 @@
+mod common;
+
+use chrono::{DateTime, TimeZone, Utc};
+use gl_core::{
+    JournalEntry, Ledger, LedgerError, LedgerEvent, Period, PeriodManager,
+    TrialBalance,
+};
+use rust_decimal::Decimal;
+
+use common::{balance, date, line, ymd};
+
+fn entry(day: u32, debit: &str, credit: &str, amount: i64) -> JournalEntry {
+    JournalEntry::new(
+        date(day),
+        vec![line(debit, amount, 0), line(credit, 0, amount)],
+        format!("Entry on day {}", day),
+    )
+    .unwrap()
+}
+
+fn cash(ledger: &Ledger) -> Decimal {
+    balance(ledger, "1111")
+}
+
+/// Events: 1 post 100, 2 post 50, 3 reverse #1, 4 open period, 5 post 30.
+fn sample_ledger() -> Ledger {
+    let mut ledger = Ledger::new();
+    let first = ledger.post_entry(entry(1, "1111", "5111", 100)).unwrap();
+    ledger.post_entry(entry(2, "1111", "131", 50)).unwrap();
+    ledger.reverse_entry(first, date(3), "Sai số tiền").unwrap();
+    let mut periods = PeriodManager::new();
+    ledger.open_period(&mut periods, Period::new(date(1), date(31))).unwrap();
+    ledger.post_entry(entry(4, "1111", "5111", 30)).unwrap();
+    ledger
+}
+
+// --- Event Stream Tests ---
+#[test]
+fn test_every_change_is_an_event() {
+    let ledger = sample_ledger();
+    let events = ledger.events();
+    assert_eq!(events.len(), 5);
+    assert_eq!(ledger.sequence(), 5);
+    assert!(events.iter().enumerate().all(|(i, e)| e.sequence == i as u64 + 1));
+    assert!(matches!(events[2].event, LedgerEvent::EntryReversed { original: 1, .. }));
+    assert!(matches!(events[3].event, LedgerEvent::PeriodOpened { .. }));
+    assert_eq!(ledger.audit_log().events().len(), 5);
+}
+
+#[test]
+fn test_replay_rebuilds_projection() {
+    let ledger = sample_ledger();
+    let replayed = Ledger::replay(ledger.events().to_vec()).unwrap();
+    assert_eq!(cash(&replayed), Decimal::from(80));
+    assert_eq!(replayed.entries().len(), 4);
+    assert_eq!(replayed.get_entry(1).unwrap().reversed_by, Some(3));
+}
+
+#[test]
+fn test_replay_rejects_gap_in_sequence() {
+    let mut events = sample_ledger().events().to_vec();
+    events.remove(1);
+    assert!(matches!(Ledger::replay(events), Err(LedgerError::InvalidSequence(3))));
+}
+
+// --- Replay As Of Tests ---
+#[test]
+fn test_state_at_each_sequence() {
+    let ledger = sample_ledger();
+    let expected = [0, 100, 150, 50, 50, 80];
+    for (sequence, amount) in expected.iter().enumerate() {
+        let state = ledger.state_at(sequence as u64).unwrap();
+        assert_eq!(cash(&state), Decimal::from(*amount), "at event {}", sequence);
+    }
+    assert!(ledger.state_at(2).unwrap().get_entry(1).unwrap().reversed_by.is_none());
+    assert!(matches!(ledger.state_at(6), Err(LedgerError::InvalidSequence(6))));
+}
+
+#[test]
+fn test_state_at_starts_from_snapshot() {
+    let mut ledger = Ledger::new();
+    ledger.post_entry(entry(1, "1111", "5111", 100)).unwrap();
+    ledger.post_entry(entry(2, "1111", "5111", 50)).unwrap();
+    assert_eq!(ledger.take_snapshot(), 2);
+    ledger.post_entry(entry(3, "1111", "5111", 25)).unwrap();
+
+    assert_eq!(ledger.snapshots().len(), 1);
+    assert_eq!(cash(&ledger.state_at(1).unwrap()), Decimal::from(100));
+    assert_eq!(cash(&ledger.state_at(2).unwrap()), Decimal::from(150));
+    assert_eq!(cash(&ledger.state_at(3).unwrap()), Decimal::from(175));
+}
+
+// --- Verification Tests ---
+#[test]
+fn test_verify_projection_of_consistent_ledger() {
+    let mut ledger = sample_ledger();
+    ledger.take_snapshot();
+    ledger.post_entry(entry(5, "1111", "5111", 10)).unwrap();
+    assert!(ledger.verify_projection().is_ok());
+}
+
+#[test]
+fn test_serialized_ledger_rebuilds_balances() {
+    let mut ledger = sample_ledger();
+    ledger.take_snapshot();
+    let json = serde_json::to_value(&ledger).unwrap();
+    assert!(json.get("balances").is_none());
+
+    let loaded: Ledger = serde_json::from_value(json).unwrap();
+    assert_eq!(cash(&loaded), Decimal::from(80));
+    assert_eq!(loaded.events().len(), 5);
+    assert!(loaded.verify_projection().is_ok());
+}
+
+#[test]
+fn test_load_refuses_tampered_snapshot() {
+    let mut ledger = sample_ledger();
+    ledger.take_snapshot();
+    let mut json = serde_json::to_value(&ledger).unwrap();
+    json["snapshots"][0]["balances"][0]["debit_balance"] = serde_json::json!("999");
+    let error = serde_json::from_value::<Ledger>(json.clone()).unwrap_err();
+    assert!(error.to_string().contains("snapshot at event 5"), "{}", error);
+
+    // Balances intact, but an entry's description changed
+    json["snapshots"][0]["balances"] = serde_json::to_value(&ledger.snapshots()[0].balances).unwrap();
+    json["snapshots"][0]["entries"][0]["entry"]["description"] = serde_json::json!("Khác");
+    assert!(serde_json::from_value::<Ledger>(json).is_err());
+}
+
+#[test]
+fn test_closing_a_period_takes_a_snapshot() {
+    let mut ledger = sample_ledger();
+    let mut periods = ledger.period_manager();
+    ledger.close_period(&mut periods, date(15)).unwrap();
+    assert_eq!(ledger.snapshots().len(), 1);
+    assert_eq!(ledger.snapshots()[0].sequence, 6);
+
+    let loaded: Ledger = serde_json::from_value(serde_json::to_value(&ledger).unwrap()).unwrap();
+    assert_eq!(loaded.snapshots().len(), 1);
+    assert_eq!(cash(&loaded.state_at(6).unwrap()), Decimal::from(80));
+}
+
+// --- Point-in-time Tests ---
+fn at(day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 2, day, 9, 0, 0).unwrap()
+}
+
+/// January entries posted on 1 Feb, a January adjustment posted late on 10 Feb.
+fn late_adjusted_ledger() -> Ledger {
+    let mut ledger = Ledger::new();
+    ledger.post_entry(entry(5, "1111", "5111", 100)).unwrap();
+    ledger.post_entry(entry(20, "1111", "5111", 40)).unwrap();
+    ledger.post_entry(entry(15, "1111", "5111", 7)).unwrap();
+    let mut events = ledger.events().to_vec();
+    events[0].recorded_at = at(1);
+    events[1].recorded_at = at(1);
+    events[2].recorded_at = at(10);
+    Ledger::replay(events).unwrap()
+}
+
+fn cash_in(tb: &TrialBalance) -> Decimal {
+    tb.balances
+        .iter()
+        .filter(|b| b.account.as_str() == "1111")
+        .map(|b| b.debit_balance - b.credit_balance)
+        .sum()
+}
+
+#[test]
+fn test_as_of_date_filters_by_transaction_date() {
+    let ledger = late_adjusted_ledger();
+    assert_eq!(cash_in(&ledger.as_of_date(date(4))), Decimal::ZERO);
+    assert_eq!(cash_in(&ledger.as_of_date(date(15))), Decimal::from(107));
+    assert_eq!(cash_in(&ledger.as_of_date(date(31))), Decimal::from(147));
+}
+
+#[test]
+fn test_as_known_at_excludes_later_postings() {
+    let ledger = late_adjusted_ledger();
+    let issued = ledger.as_known_at(at(5));
+    assert_eq!(issued.entries().len(), 2);
+    assert_eq!(cash(&issued), Decimal::from(140));
+    assert_eq!(cash(&ledger.as_known_at(at(10))), Decimal::from(147));
+    assert!(ledger.as_known_at(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()).entries().is_empty());
+}
+
+#[test]
+fn test_trial_balance_at_combines_both_axes() {
+    let ledger = late_adjusted_ledger();
+    assert_eq!(cash_in(&ledger.trial_balance_at(date(15), at(5))), Decimal::from(100));
+    assert_eq!(cash_in(&ledger.trial_balance_at(date(15), at(11))), Decimal::from(107));
+}
+
+#[test]
+fn test_periods_rebuilt_from_events() {
+    let mut ledger = Ledger::new();
+    let mut periods = PeriodManager::empty();
+    let quarter = Period::new(date(1), ymd(2025, 3, 31));
+    for month in quarter.months() {
+        ledger.open_period(&mut periods, month).unwrap();
+    }
+    assert_eq!(periods.periods().len(), 3);
+    let january = ledger.close_period(&mut periods, date(15)).unwrap();
+    assert_eq!(january.end_date, date(31));
+    assert!(matches!(ledger.close_period(&mut periods, date(20)), Err(LedgerError::DateNotInOpenPeriod(_))));
+
+    let json = serde_json::to_string(&ledger).unwrap();
+    let restored: Ledger = serde_json::from_str(&json).unwrap();
+    let rebuilt = restored.period_manager();
+    assert_eq!(rebuilt.periods(), periods.periods());
+    assert!(!rebuilt.is_date_in_open_period(date(15)));
+    assert!(rebuilt.is_date_in_open_period(ymd(2025, 2, 28)));
+}
@@ -0,0 +1,161 @@
+use chrono::NaiveDate;
+use gl_core::{
+    import_csv, parse_amount, parse_date, ColumnMapping, ImportMode, LedgerError, NumberFormat,
+    VoucherFormat, VoucherType,
+};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+const BALANCED: &str = "\
+voucher,date,account,debit,credit,description
+PT0001,02/01/2025,1111,\"1.000.000,50\",,Thu tiền bán hàng
+PT0001,02/01/2025,5111,,\"1.000.000,50\",Thu tiền bán hàng
+PC0001,03/01/2025,642,250.000,,Chi tiền văn phòng phẩm
+PC0001,03/01/2025,1111,,250.000,Chi tiền văn phòng phẩm
+";
+
+const WITH_ERRORS: &str = "\
+voucher,date,account,debit,credit,description
+PT0001,02/01/2025,1111,100.000,,Thu tiền
+PT0001,02/01/2025,5111,,100.000,Thu tiền
+PC0001,31/02/2025,642,50.000,,Ngày sai
+PC0001,03/01/2025,1111,,50.000,Ngày sai
+PC0002,04/01/2025,642,70.000,,Lệch
+PC0002,04/01/2025,1111,,60.000,Lệch
+";
+
+// --- Number and Date Format Tests ---
+#[test]
+fn test_parse_vietnamese_amount() {
+    let amount = parse_amount("1.000.000,50", &NumberFormat::Vietnamese).unwrap();
+    assert_eq!(amount, Decimal::from_str("1000000.50").unwrap());
+    assert_eq!(parse_amount("", &NumberFormat::Vietnamese).unwrap(), Decimal::ZERO);
+    assert!(parse_amount("abc", &NumberFormat::Vietnamese).is_err());
+}
+
+#[test]
+fn test_parse_plain_amount() {
+    let amount = parse_amount("1,000,000.50", &NumberFormat::Plain).unwrap();
+    assert_eq!(amount, Decimal::from_str("1000000.50").unwrap());
+}
+
+#[test]
+fn test_parse_dd_mm_yyyy_date() {
+    let date = parse_date("02/01/2025", "%d/%m/%Y").unwrap();
+    assert_eq!(date, NaiveDate::from_ymd_opt(2025, 1, 2).unwrap());
+    assert!(parse_date("2025-01-02", "%d/%m/%Y").is_err());
+}
+
+// --- CSV Import Tests ---
+#[test]
+fn test_import_csv_groups_rows_by_voucher() {
+    let result = import_csv(
+        BALANCED.as_bytes(),
+        b',',
+        &ColumnMapping::default(),
+        &ImportMode::BestEffort,
+    )
+    .unwrap();
+
+    assert!(result.is_ok());
+    assert_eq!(result.vouchers.len(), 2);
+    assert_eq!(result.vouchers[0].voucher, "PT0001");
+    assert_eq!(result.vouchers[0].line, 2);
+    assert_eq!(result.vouchers[0].entry.lines.len(), 2);
+    assert!(result.vouchers[1].entry.is_balanced());
+}
+
+#[test]
+fn test_import_csv_reports_errors_with_line_numbers() {
+    let result = import_csv(
+        WITH_ERRORS.as_bytes(),
+        b',',
+        &ColumnMapping::default(),
+        &ImportMode::BestEffort,
+    )
+    .unwrap();
+
+    assert_eq!(result.vouchers.len(), 1);
+    assert_eq!(result.errors.len(), 2);
+    assert_eq!(result.errors[0].line, 4);
+    assert_eq!(result.errors[0].voucher.as_deref(), Some("PC0001"));
+    assert_eq!(result.errors[1].line, 6);
+    assert!(result.errors[1].message.contains("unbalanced"));
+}
+
+#[test]
+fn test_import_csv_all_or_nothing_rejects_file() {
+    let result = import_csv(
+        WITH_ERRORS.as_bytes(),
+        b',',
+        &ColumnMapping::default(),
+        &ImportMode::AllOrNothing,
+    )
+    .unwrap();
+
+    assert!(result.vouchers.is_empty());
+    assert_eq!(result.errors.len(), 2);
+}
+
+#[test]
+fn test_import_csv_with_custom_mapping() {
+    let data = "\
+Số CT;Ngày;TK;Nợ;Có;Diễn giải
+PKT01;05/01/2025;621;10,5;0;Xuất kho
+PKT01;05/01/2025;152;0;10,5;Xuất kho
+";
+    let mapping = ColumnMapping {
+        voucher: "Số CT".to_string(),
+        date: "Ngày".to_string(),
+        account: "TK".to_string(),
+        debit: "Nợ".to_string(),
+        credit: "Có".to_string(),
+        description: "Diễn giải".to_string(),
+        ..ColumnMapping::default()
+    };
+    let result = import_csv(data.as_bytes(), b';', &mapping, &ImportMode::AllOrNothing).unwrap();
+
+    assert!(result.is_ok());
+    assert_eq!(result.vouchers[0].entry.lines[0].debit, Decimal::from_str("10.5").unwrap());
+}
+
+#[test]
+fn test_import_csv_missing_column_fails() {
+    let result = import_csv(
+        "voucher,date,account,debit\n".as_bytes(),
+        b',',
+        &ColumnMapping::default(),
+        &ImportMode::BestEffort,
+    );
+    assert!(matches!(result, Err(LedgerError::InvalidImport(_))));
+}
+
+// --- Voucher Number Validation Tests ---
+#[test]
+fn test_import_csv_validates_voucher_numbers() {
+    let data = "\
+voucher,date,account,debit,credit,description
+PT2501-0001,02/01/2025,1111,100.000,,Thu tiền
+PT2501-0001,02/01/2025,5111,,100.000,Thu tiền
+PT2501-0003,05/01/2025,1111,20.000,,Bỏ số
+PT2501-0003,05/01/2025,5111,,20.000,Bỏ số
+PC2502-0001,06/01/2025,642,30.000,,Sai kỳ
+PC2502-0001,06/01/2025,1111,,30.000,Sai kỳ
+PX0001,07/01/2025,642,10.000,,Sai định dạng
+PX0001,07/01/2025,1111,,10.000,Sai định dạng
+";
+    let mapping = ColumnMapping { voucher_format: Some(VoucherFormat::default()), ..ColumnMapping::default() };
+    let result = import_csv(data.as_bytes(), b',', &mapping, &ImportMode::BestEffort).unwrap();
+
+    assert_eq!(result.vouchers.len(), 1);
+    let voucher = result.vouchers[0].entry.voucher.as_ref().unwrap();
+    assert_eq!(voucher.voucher_type, VoucherType::PhieuThu);
+    assert_eq!(voucher.sequence, 1);
+
+    let messages: Vec<(usize, &str)> = result.errors.iter().map(|e| (e.line, e.message.as_str())).collect();
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[0].0, 4);
+    assert!(messages[0].1.contains("gap"));
+    assert!(messages[1].1.contains("does not belong"));
+    assert!(messages[2].1.contains("Invalid voucher number"));
+}
@@ -190,4 +190,188 @@ fn test_ledger_get_balance_for_non_existent_account() {
     // implies that the *implementation* returns a zero balance struct if None is found. 
     // However, if the API signature is Option<Balance>, a failure to find the 
     // account should generally return None. Let's assume the latter (is_none()) for clean API design.
-}
\ No newline at end of file
+}
+// --- Batch Posting Tests ---
+// Tests atomic posting of several entries against the CoA and open periods.
+fn test_coa() -> gl_core::CoaConfig {
+    let account = |code: &str| gl_core::Account {
+        code: code.to_string(),
+        name: String::new(),
+        classification: String::new(),
+        default_balance: String::new(),
+        description: String::new(),
+    };
+    gl_core::CoaConfig { accounts: vec![account("1111"), account("411"), account("642")] }
+}
+
+fn raw_entry(date: NaiveDate, lines: Vec<(&str, i64, i64)>) -> JournalEntry {
+    JournalEntry {
+        date,
+        lines: lines
+            .into_iter()
+            .map(|(a, d, c)| JournalLine {
+                account: AccountCode::new(a).unwrap(),
+                debit: Decimal::from(d),
+                credit: Decimal::from(c),
+                party: None,
+                due_date: None,
+                reference: None,
+                foreign: None,
+            })
+            .collect(),
+        description: "Batch".to_string(),
+        voucher: None,
+    }
+}
+
+#[test]
+fn test_post_batch_posts_all_entries() {
+    let mut ledger = Ledger::new();
+    let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    let entries = vec![
+        raw_entry(date, vec![("1111", 100, 0), ("411", 0, 100)]),
+        // Sub-account of 642 is accepted
+        raw_entry(date, vec![("6421", 40, 0), ("1111", 0, 40)]),
+    ];
+
+    let count = ledger.post_batch(entries, &test_coa(), &gl_core::PeriodManager::new()).unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(ledger.trial_balance().total_debit, Decimal::from(140));
+}
+
+#[test]
+fn test_post_batch_rejects_whole_batch_and_reports_every_failure() {
+    let mut ledger = Ledger::new();
+    let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    let entries = vec![
+        raw_entry(date, vec![("1111", 100, 0), ("411", 0, 100)]),
+        raw_entry(date, vec![("1111", 100, 0), ("411", 0, 90)]),
+        raw_entry(date, vec![("9999", 10, 0), ("1111", 0, 10)]),
+        raw_entry(NaiveDate::from_ymd_opt(2035, 1, 1).unwrap(), vec![("1111", 1, 0), ("411", 0, 1)]),
+    ];
+
+    let errors = ledger.post_batch(entries, &test_coa(), &gl_core::PeriodManager::new()).unwrap_err();
+    let indexes: Vec<usize> = errors.iter().map(|e| e.index).collect();
+    assert_eq!(indexes, vec![1, 2, 3]);
+    assert!(matches!(errors[0].error, gl_core::LedgerError::UnbalancedEntry(_, _)));
+    assert!(matches!(errors[1].error, gl_core::LedgerError::UnknownAccount(_)));
+    assert!(matches!(errors[2].error, gl_core::LedgerError::DateNotInOpenPeriod(_)));
+    assert!(ledger.entries().is_empty());
+    assert_eq!(ledger.trial_balance().total_debit, Decimal::ZERO);
+}
+
+#[test]
+fn test_validate_batch_returns_trial_balance_delta() {
+    let mut ledger = Ledger::new();
+    let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    ledger.post_entry(raw_entry(date, vec![("1111", 500, 0), ("411", 0, 500)])).unwrap();
+
+    let delta = ledger
+        .validate_batch(&[raw_entry(date, vec![("642", 40, 0), ("1111", 0, 40)])], &test_coa(), &gl_core::PeriodManager::new())
+        .unwrap();
+    assert_eq!(delta.total_debit, Decimal::from(40));
+    // Dry run leaves the ledger untouched
+    assert_eq!(ledger.trial_balance().total_debit, Decimal::from(500));
+}
+
+#[test]
+fn test_parse_entry_batch_json_array_and_jsonl() {
+    let line = r#"{"date":"2025-01-02","lines":[{"account":"1111","debit":1,"credit":0},{"account":"411","debit":0,"credit":1}],"description":"x"}"#;
+    let array = format!("[{},{}]", line, line);
+    let jsonl = format!("{}\n\n{}\n", line, line);
+
+    assert_eq!(gl_core::parse_entry_batch(&array).unwrap().len(), 2);
+    assert_eq!(gl_core::parse_entry_batch(&jsonl).unwrap().len(), 2);
+    assert!(gl_core::parse_entry_batch("{not json}").is_err());
+}
+
+// --- Reversal Tests ---
+// Tests mirror entries, reversal links and auto-reversing accruals.
+fn sale_entry(date: NaiveDate) -> JournalEntry {
+    JournalEntry::new(
+        date,
+        vec![
+            JournalLine::new(AccountCode::new("131").unwrap(), Decimal::from(100), Decimal::ZERO).unwrap(),
+            JournalLine::new(AccountCode::new("5111").unwrap(), Decimal::ZERO, Decimal::from(100)).unwrap(),
+        ],
+        "Sale".to_string(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_reverse_entry_swaps_lines_and_links_entries() {
+    let mut ledger = Ledger::new();
+    let date = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+    let id = ledger.post_entry(sale_entry(date)).unwrap();
+
+    let reversal_id = ledger.reverse_entry(id, date, "Sai khách hàng").unwrap();
+
+    let reversal = ledger.get_entry(reversal_id).unwrap();
+    assert_eq!(reversal.reverses, Some(id));
+    assert_eq!(reversal.entry.lines[0].credit, Decimal::from(100));
+    assert_eq!(reversal.entry.lines[1].debit, Decimal::from(100));
+    assert_eq!(ledger.get_entry(id).unwrap().reversed_by, Some(reversal_id));
+
+    let balance_131 = ledger.get_account_balance(&AccountCode::new("131").unwrap()).unwrap();
+    assert_eq!(balance_131.debit_balance, balance_131.credit_balance);
+}
+
+#[test]
+fn test_double_reversal_is_rejected() {
+    let mut ledger = Ledger::new();
+    let date = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+    let id = ledger.post_entry(sale_entry(date)).unwrap();
+    let reversal_id = ledger.reverse_entry(id, date, "Sai").unwrap();
+
+    assert!(matches!(ledger.reverse_entry(id, date, "Lần 2"), Err(gl_core::LedgerError::AlreadyReversed(_))));
+    assert!(matches!(
+        ledger.reverse_entry(reversal_id, date, "Đảo của đảo"),
+        Err(gl_core::LedgerError::ReversalOfReversal(_))
+    ));
+    assert!(matches!(ledger.reverse_entry(99, date, "?"), Err(gl_core::LedgerError::EntryNotFound(99))));
+}
+
+#[test]
+fn test_accrual_reverses_on_first_day_of_next_period() {
+    let mut periods = gl_core::PeriodManager::new();
+    periods.add_period(gl_core::Period::new(
+        NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+    ));
+    let mut ledger = Ledger::new();
+    let id = ledger
+        .post_accrual(sale_entry(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()), &periods)
+        .unwrap();
+
+    assert!(ledger.run_auto_reversals(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()).unwrap().is_empty());
+    let reversals = ledger.run_auto_reversals(NaiveDate::from_ymd_opt(2025, 2, 15).unwrap()).unwrap();
+
+    assert_eq!(reversals.len(), 1);
+    let reversal = ledger.get_entry(reversals[0]).unwrap();
+    assert_eq!(reversal.entry.date, NaiveDate::from_ymd_opt(2025, 2, 1).unwrap());
+    assert_eq!(reversal.reverses, Some(id));
+    // Running again does not reverse twice
+    assert!(ledger.run_auto_reversals(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()).unwrap().is_empty());
+}
+
+#[test]
+fn test_accrual_and_its_reversal_need_open_periods() {
+    let ymd = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+    let mut periods = gl_core::PeriodManager::empty();
+    periods.add_period(gl_core::Period::new(ymd(1, 1), ymd(1, 31)));
+    let mut ledger = Ledger::new();
+    // February, where the accrual would reverse, is not open yet
+    assert!(matches!(
+        ledger.post_accrual(sale_entry(ymd(1, 31)), &periods),
+        Err(gl_core::LedgerError::DateNotInOpenPeriod(d)) if d == ymd(2, 1)
+    ));
+
+    periods.add_period(gl_core::Period::new(ymd(2, 1), ymd(2, 28)));
+    ledger.close_period(&mut periods, ymd(1, 15)).unwrap();
+    assert!(matches!(
+        ledger.post_accrual(sale_entry(ymd(1, 31)), &periods),
+        Err(gl_core::LedgerError::DateNotInOpenPeriod(d)) if d == ymd(1, 31)
+    ));
+    assert!(ledger.entries().is_empty());
+}
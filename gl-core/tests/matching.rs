@@ -0,0 +1,181 @@
+mod common;
+
+use gl_core::{
+    receivables_aging, ItemKind, JournalEntry, Ledger, LedgerError, Vendor,
+};
+
+use common::{date, dec as d, line, ymd};
+
+fn invoice(day: u32, customer: &str, amount: i64, reference: &str) -> JournalEntry {
+    JournalEntry::new(
+        date(day),
+        vec![line("131", amount, 0).with_party(customer).with_reference(reference), line("5111", 0, amount)],
+        format!("Hóa đơn {}", reference),
+    )
+    .unwrap()
+}
+
+fn receipt(day: u32, customer: &str, amount: i64, reference: Option<&str>) -> JournalEntry {
+    let mut receivable = line("131", 0, amount).with_party(customer);
+    if let Some(reference) = reference {
+        receivable = receivable.with_reference(reference);
+    }
+    JournalEntry::new(date(day), vec![line("1111", amount, 0), receivable], "Thu tiền".to_string()).unwrap()
+}
+
+fn credit_note(day: u32, customer: &str, amount: i64, reference: &str) -> JournalEntry {
+    JournalEntry::new(
+        date(day),
+        vec![line("5211", amount, 0), line("131", 0, amount).with_party(customer).with_reference(reference)],
+        "Giảm giá hàng bán".to_string(),
+    )
+    .unwrap()
+}
+
+// --- Open Item Tests ---
+#[test]
+fn test_entries_become_open_items() {
+    let mut ledger = Ledger::new();
+    ledger.post_entry(invoice(5, "KH001", 1_000, "HD01")).unwrap();
+    ledger.post_entry(receipt(10, "KH001", 400, None)).unwrap();
+
+    let items = ledger.open_items("131").unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].kind, ItemKind::Charge);
+    assert_eq!(items[0].reference.as_deref(), Some("HD01"));
+    assert_eq!(items[1].kind, ItemKind::Settlement);
+    assert_eq!(items[1].amount, d(400));
+
+    assert!(matches!(ledger.open_items("1111"), Err(LedgerError::NotAControlAccount(_))));
+}
+
+#[test]
+fn test_manual_partial_matching_and_unmatch() {
+    let mut ledger = Ledger::new();
+    let charge = ledger.post_entry(invoice(5, "KH001", 1_000, "HD01")).unwrap();
+    let payment = ledger.post_entry(receipt(10, "KH001", 700, None)).unwrap();
+
+    let first = ledger.match_items("131", "KH001", charge, payment, Some(d(300))).unwrap();
+    let second = ledger.match_items("131", "KH001", charge, payment, None).unwrap();
+    let items = ledger.open_items("131").unwrap();
+    assert_eq!(items[0].matched, d(700));
+    assert_eq!(items[0].open_amount(), d(300));
+    assert!(!items[1].is_open());
+    assert_eq!(ledger.unmatched_items("131").unwrap().len(), 1);
+
+    assert!(matches!(
+        ledger.match_items("131", "KH001", charge, payment, None),
+        Err(LedgerError::InvalidMatch(_))
+    ));
+    assert!(matches!(
+        ledger.match_items("131", "KH002", charge, payment, None),
+        Err(LedgerError::UnknownOpenItem(_, _))
+    ));
+    // A charge cannot stand in for a settlement
+    assert!(matches!(
+        ledger.match_items("131", "KH001", payment, charge, None),
+        Err(LedgerError::UnknownOpenItem(_, _))
+    ));
+
+    ledger.unmatch_items(first).unwrap();
+    assert_eq!(ledger.open_items("131").unwrap()[0].open_amount(), d(600));
+    assert!(matches!(ledger.unmatch_items(first), Err(LedgerError::UnknownMatch(_))));
+    assert!(matches!(
+        ledger.match_items("131", "KH001", charge, payment, Some(d(500))),
+        Err(LedgerError::MatchExceedsOpenAmount(_, _))
+    ));
+    assert!(ledger.match_items("131", "KH001", charge, payment, None).unwrap() > second);
+}
+
+#[test]
+fn test_auto_match_by_reference_then_amount() {
+    let mut ledger = Ledger::new();
+    ledger.post_entry(invoice(2, "KH001", 1_000, "HD01")).unwrap();
+    ledger.post_entry(invoice(3, "KH001", 500, "HD02")).unwrap();
+    ledger.post_entry(invoice(4, "KH002", 800, "HD03")).unwrap();
+    ledger.post_entry(receipt(10, "KH001", 600, Some("HD02"))).unwrap();
+    ledger.post_entry(credit_note(11, "KH002", 800, "GG01")).unwrap();
+    ledger.post_entry(receipt(12, "KH002", 50, None)).unwrap();
+
+    let made = ledger.auto_match("131").unwrap();
+    assert_eq!(made.len(), 2);
+    // The reference settles HD02 and the rest of that payment matches no
+    // charge by amount; the credit note settles HD03 by amount.
+    let items = ledger.open_items("131").unwrap();
+    let open = |id: u64| items.iter().find(|i| i.entry_id == id).unwrap().open_amount();
+    assert_eq!(open(1), d(1_000));
+    assert_eq!(open(2), d(0));
+    assert_eq!(open(3), d(0));
+    assert_eq!(open(4), d(100));
+    assert_eq!(open(5), d(0));
+    assert_eq!(open(6), d(50));
+}
+
+#[test]
+fn test_matches_survive_replay_and_drive_aging() {
+    let mut ledger = Ledger::new();
+    let old = ledger.post_entry(invoice(2, "KH001", 1_000, "HD01")).unwrap();
+    let new = ledger.post_entry(invoice(20, "KH001", 1_000, "HD02")).unwrap();
+    let payment = ledger.post_entry(receipt(25, "KH001", 1_000, None)).unwrap();
+    ledger.match_items("131", "KH001", new, payment, None).unwrap();
+
+    // FIFO would settle HD01; the explicit match settles HD02 instead
+    let aging = receivables_aging(&ledger, ymd(2025, 2, 10));
+    assert_eq!(aging.lines[0].items.len(), 1);
+    assert_eq!(aging.lines[0].items[0].entry_id, old);
+
+    let json = serde_json::to_string(&ledger).unwrap();
+    let restored: Ledger = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.item_matches(), ledger.item_matches());
+    assert_eq!(ledger.state_at(3).unwrap().item_matches().len(), 0);
+}
+
+#[test]
+fn test_reversal_and_matches() {
+    let mut ledger = Ledger::new();
+    let charge = ledger.post_entry(invoice(5, "KH001", 1_000, "HD01")).unwrap();
+    let payment = ledger.post_entry(receipt(10, "KH001", 1_000, Some("HD01"))).unwrap();
+    ledger.auto_match("131").unwrap();
+
+    assert!(matches!(ledger.reverse_entry(charge, date(15), "Sai"), Err(LedgerError::ItemHasMatches(_))));
+    ledger.reverse_entry(payment, date(15), "Sai").unwrap();
+    assert!(ledger.item_matches().is_empty());
+    assert_eq!(ledger.unmatched_items("131").unwrap()[0].open_amount(), d(1_000));
+    ledger.reverse_entry(charge, date(15), "Sai").unwrap();
+    assert!(ledger.open_items("131").unwrap().is_empty());
+}
+
+#[test]
+fn test_vendor_payments_are_matches() {
+    let mut ledger = Ledger::new();
+    ledger
+        .add_vendor(Vendor {
+            code: "NCC001".to_string(),
+            name: "Phú Thành".to_string(),
+            tax_code: String::new(),
+            address: String::new(),
+            payment_terms_days: 30,
+        })
+        .unwrap();
+    let purchase = JournalEntry::new(date(2), vec![line("152", 900, 0), line("331", 0, 900)], "Mua".to_string()).unwrap();
+    let invoice = ledger.record_vendor_invoice("NCC001", "HD9", purchase, None).unwrap();
+    let pay = |amount: i64| {
+        JournalEntry::new(date(9), vec![line("331", amount, 0), line("1121", 0, amount)], "Trả tiền".to_string()).unwrap()
+    };
+    let paid = ledger.pay_vendor("NCC001", pay(400), vec![]).unwrap();
+    assert_eq!(ledger.item_matches()[0].charge, invoice);
+    assert_eq!(ledger.item_matches()[0].settlement, paid);
+
+    // A manual match on 331 pays the vendor invoice too
+    let other = JournalEntry::new(
+        date(10),
+        vec![line("331", 200, 0).with_party("NCC001"), line("1121", 0, 200)],
+        "Trả tiền".to_string(),
+    )
+    .unwrap();
+    let other = ledger.post_entry(other).unwrap();
+    let id = ledger.match_items("331", "NCC001", invoice, other, None).unwrap();
+    assert_eq!(ledger.vendor_invoice("NCC001", "HD9").unwrap().outstanding(), d(300));
+    ledger.unmatch_items(id).unwrap();
+    assert_eq!(ledger.vendor_invoice("NCC001", "HD9").unwrap().outstanding(), d(500));
+}
@@ -0,0 +1,195 @@
+mod common;
+
+use gl_core::{
+    payments_due, reconcile_payables, vendor_balances, vendor_statement, InvoiceAllocation, JournalEntry, Ledger,
+    LedgerError, Vendor, VendorMaster,
+};
+use rust_decimal::Decimal;
+
+use common::{date, line, ymd};
+
+fn purchase(day: u32, amount: i64) -> JournalEntry {
+    JournalEntry::new(
+        date(day),
+        vec![line("152", amount, 0), line("331", 0, amount)],
+        format!("Mua hàng ngày {}", day),
+    )
+    .unwrap()
+}
+
+fn payment(day: u32, amount: i64) -> JournalEntry {
+    JournalEntry::new(
+        date(day),
+        vec![line("331", amount, 0), line("1121", 0, amount)],
+        format!("Thanh toán ngày {}", day),
+    )
+    .unwrap()
+}
+
+fn vendor(code: &str, payment_terms_days: u32) -> Vendor {
+    Vendor {
+        code: code.to_string(),
+        name: format!("Nhà cung cấp {}", code),
+        tax_code: "0312345678".to_string(),
+        address: "Hà Nội".to_string(),
+        payment_terms_days,
+    }
+}
+
+fn ap_ledger() -> Ledger {
+    let mut ledger = Ledger::new();
+    ledger.add_vendor(vendor("NCC001", 10)).unwrap();
+    ledger.add_vendor(vendor("NCC002", 30)).unwrap();
+    ledger
+}
+
+fn allocation(invoice: &str, amount: i64) -> InvoiceAllocation {
+    InvoiceAllocation { invoice: invoice.to_string(), amount: Decimal::from(amount) }
+}
+
+// --- Vendor Master Tests ---
+#[test]
+fn test_vendor_master_from_yaml_and_payable_lines() {
+    let master = VendorMaster::from_yaml("vendors:\n  - code: NCC001\n    name: Phú Thành\n").unwrap();
+    assert_eq!(master.get("NCC001").unwrap().payment_terms_days, 30);
+
+    let mut ledger = Ledger::new().with_vendors(master);
+    assert!(matches!(ledger.add_vendor(vendor("NCC001", 5)), Err(LedgerError::DuplicateVendor(_))));
+    assert!(matches!(ledger.post_entry(purchase(2, 100)), Err(LedgerError::MissingParty(_))));
+    let unknown = JournalEntry::new(
+        date(2),
+        vec![line("152", 100, 0), line("331", 0, 100).with_party("NCC999")],
+        "Mua hàng".to_string(),
+    )
+    .unwrap();
+    assert!(matches!(ledger.post_entry(unknown), Err(LedgerError::UnknownVendor(_))));
+}
+
+// --- Vendor Invoice Tests ---
+#[test]
+fn test_invoice_due_date_from_payment_terms() {
+    let mut ledger = ap_ledger();
+    ledger.record_vendor_invoice("NCC001", "HD01", purchase(5, 1_000), None).unwrap();
+    ledger.record_vendor_invoice("NCC002", "HD02", purchase(6, 500), Some(date(20))).unwrap();
+
+    let invoice = ledger.vendor_invoice("NCC001", "HD01").unwrap();
+    assert_eq!(invoice.due_date, date(15));
+    assert_eq!(invoice.amount, Decimal::from(1_000));
+    assert_eq!(ledger.entries()[0].entry.lines[1].party.as_deref(), Some("NCC001"));
+    assert_eq!(ledger.vendor_invoice("NCC002", "HD02").unwrap().due_date, date(20));
+
+    assert!(matches!(
+        ledger.record_vendor_invoice("NCC001", "HD01", purchase(7, 10), None),
+        Err(LedgerError::DuplicateVendorInvoice(_, _))
+    ));
+    assert!(matches!(
+        ledger.record_vendor_invoice("NCC999", "HD03", purchase(7, 10), None),
+        Err(LedgerError::UnknownVendor(_))
+    ));
+    assert!(matches!(
+        ledger.record_vendor_invoice("NCC001", "HD04", payment(7, 10), None),
+        Err(LedgerError::InvalidVendorInvoice(_))
+    ));
+}
+
+#[test]
+fn test_partial_payments_matched_to_invoices() {
+    let mut ledger = ap_ledger();
+    ledger.record_vendor_invoice("NCC001", "HD01", purchase(5, 1_000), None).unwrap();
+    ledger.record_vendor_invoice("NCC001", "HD02", purchase(8, 600), None).unwrap();
+
+    ledger.pay_vendor("NCC001", payment(10, 300), vec![allocation("HD02", 300)]).unwrap();
+    assert_eq!(ledger.vendor_invoice("NCC001", "HD02").unwrap().outstanding(), Decimal::from(300));
+    assert_eq!(ledger.vendor_invoice("NCC001", "HD01").unwrap().outstanding(), Decimal::from(1_000));
+
+    // Without allocations the oldest due invoice is paid first
+    ledger.pay_vendor("NCC001", payment(12, 1_100), vec![]).unwrap();
+    assert!(ledger.vendor_invoice("NCC001", "HD01").unwrap().is_paid());
+    assert_eq!(ledger.vendor_invoice("NCC001", "HD02").unwrap().outstanding(), Decimal::from(200));
+
+    assert!(matches!(
+        ledger.pay_vendor("NCC001", payment(13, 500), vec![allocation("HD02", 500)]),
+        Err(LedgerError::PaymentExceedsInvoice(_, _, _))
+    ));
+    assert!(matches!(
+        ledger.pay_vendor("NCC001", payment(13, 100), vec![allocation("HD02", 150)]),
+        Err(LedgerError::AllocationExceedsPayment(_, _))
+    ));
+    assert!(matches!(
+        ledger.pay_vendor("NCC001", payment(13, 100), vec![allocation("HD09", 100)]),
+        Err(LedgerError::UnknownVendorInvoice(_, _))
+    ));
+    assert_eq!(ledger.entries().len(), 4);
+}
+
+#[test]
+fn test_payments_due_report() {
+    let mut ledger = ap_ledger();
+    ledger.record_vendor_invoice("NCC001", "HD01", purchase(5, 1_000), None).unwrap();
+    ledger.record_vendor_invoice("NCC002", "HD02", purchase(2, 700), None).unwrap();
+    ledger.record_vendor_invoice("NCC001", "HD03", purchase(6, 400), Some(date(9))).unwrap();
+    ledger.pay_vendor("NCC001", payment(7, 400), vec![allocation("HD03", 400)]).unwrap();
+    ledger.pay_vendor("NCC001", payment(8, 250), vec![allocation("HD01", 250)]).unwrap();
+
+    let schedule = payments_due(&ledger, date(20));
+    assert_eq!(schedule.lines.len(), 1);
+    assert_eq!(schedule.lines[0].invoice, "HD01");
+    assert_eq!(schedule.lines[0].outstanding, Decimal::from(750));
+    assert_eq!(schedule.lines[0].days_overdue, 5);
+
+    let schedule = payments_due(&ledger, ymd(2025, 2, 1));
+    let invoices: Vec<&str> = schedule.lines.iter().map(|l| l.invoice.as_str()).collect();
+    assert_eq!(invoices, vec!["HD01", "HD02"]);
+    assert_eq!(schedule.total, Decimal::from(1_450));
+}
+
+// --- AP Subledger Tests ---
+#[test]
+fn test_vendor_balances_statement_and_reconciliation() {
+    let mut ledger = ap_ledger();
+    ledger.record_vendor_invoice("NCC001", "HD01", purchase(5, 1_000), None).unwrap();
+    ledger.record_vendor_invoice("NCC002", "HD02", purchase(6, 500), None).unwrap();
+    ledger.pay_vendor("NCC001", payment(10, 400), vec![]).unwrap();
+
+    let balances = vendor_balances(&ledger, date(31));
+    assert_eq!(balances[0].balance, Decimal::from(600));
+    assert_eq!(balances[1].balance, Decimal::from(500));
+
+    let reconciliation = reconcile_payables(&ledger, date(31)).unwrap();
+    assert_eq!(reconciliation.ledger_balance, Decimal::from(1_100));
+
+    let statement = vendor_statement(&ledger, "NCC001", date(6), date(31)).unwrap();
+    assert_eq!(statement.opening_balance, Decimal::from(1_000));
+    assert_eq!(statement.lines.len(), 1);
+    assert_eq!(statement.closing_balance, Decimal::from(600));
+
+    // Without a vendor master, 331 lines may lack a vendor and the subledger falls short
+    let mut unchecked = Ledger::new();
+    unchecked.post_entry(purchase(12, 50)).unwrap();
+    assert!(matches!(reconcile_payables(&unchecked, date(31)), Err(LedgerError::SubledgerMismatch(_, _, _))));
+}
+
+#[test]
+fn test_invoices_survive_replay_and_reversal() {
+    let mut ledger = ap_ledger();
+    let invoice = ledger.record_vendor_invoice("NCC001", "HD01", purchase(5, 1_000), None).unwrap();
+    let paid = ledger.pay_vendor("NCC001", payment(10, 400), vec![]).unwrap();
+    ledger.take_snapshot();
+    ledger.record_vendor_invoice("NCC002", "HD02", purchase(11, 300), None).unwrap();
+
+    let json = serde_json::to_string(&ledger).unwrap();
+    let restored: Ledger = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.vendor_invoices(), ledger.vendor_invoices());
+    assert_eq!(ledger.state_at(1).unwrap().vendor_invoices()[0].paid(), Decimal::ZERO);
+    let state = ledger.state_at(3).unwrap();
+    assert_eq!(state.vendor_invoices().len(), 2);
+    assert_eq!(state.vendor_invoices()[0].paid(), Decimal::from(400));
+
+    // An invoice with payments cannot be reversed before its payments are
+    assert!(matches!(ledger.reverse_entry(invoice, date(15), "Sai"), Err(LedgerError::InvalidVendorInvoice(_))));
+    ledger.reverse_entry(paid, date(15), "Sai").unwrap();
+    assert_eq!(ledger.vendor_invoice("NCC001", "HD01").unwrap().outstanding(), Decimal::from(1_000));
+    ledger.reverse_entry(invoice, date(15), "Sai").unwrap();
+    assert!(ledger.vendor_invoice("NCC001", "HD01").is_none());
+    assert!(reconcile_payables(&ledger, date(31)).is_ok());
+}
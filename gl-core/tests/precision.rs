@@ -0,0 +1,142 @@
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use gl_core::{
+    AccountCode, ForeignAmount, JournalEntry, JournalLine, Ledger, LedgerError, PrecisionPolicy, RoundingMode,
+};
+use rust_decimal::Decimal;
+
+/// Amounts as decimal strings, to test fractions of a đồng.
+fn line(account: &str, debit: &str, credit: &str) -> JournalLine {
+    JournalLine::new(
+        AccountCode::new(account).unwrap(),
+        Decimal::from_str(debit).unwrap(),
+        Decimal::from_str(credit).unwrap(),
+    )
+    .unwrap()
+}
+
+fn entry(lines: Vec<JournalLine>) -> JournalEntry {
+    JournalEntry::new(NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(), lines, "Thu tiền".to_string()).unwrap()
+}
+
+fn d(value: &str) -> Decimal {
+    Decimal::from_str(value).unwrap()
+}
+
+// --- Rounding Tests ---
+#[test]
+fn test_rounding_modes() {
+    assert_eq!(RoundingMode::HalfUp.round(d("2.5"), 0), d("3"));
+    assert_eq!(RoundingMode::HalfUp.round(d("-2.5"), 0), d("-3"));
+    assert_eq!(RoundingMode::HalfEven.round(d("2.5"), 0), d("2"));
+    assert_eq!(RoundingMode::Down.round(d("2.99"), 0), d("2"));
+    assert_eq!(RoundingMode::Up.round(d("2.01"), 1), d("2.1"));
+}
+
+#[test]
+fn test_tax_rounding_mode() {
+    let mut policy = PrecisionPolicy::default();
+    // 10% of 123.445 is 12.344,5
+    assert_eq!(policy.tax(d("123445"), d("10")), d("12345"));
+    policy.tax_rounding = RoundingMode::HalfEven;
+    assert_eq!(policy.tax(d("123445"), d("10")), d("12344"));
+    policy.tax_rounding = RoundingMode::Down;
+    assert_eq!(policy.tax(d("123449"), d("8")), d("9875"));
+}
+
+// --- Posting Tests ---
+#[test]
+fn test_fractional_dong_is_rejected() {
+    let mut ledger = Ledger::new();
+    let fractional = entry(vec![line("1111", "1000.5", "0"), line("5111", "0", "1000.5")]);
+    assert!(matches!(
+        ledger.post_entry(fractional.clone()),
+        Err(LedgerError::PrecisionExceeded(currency, _, 0)) if currency == "VND"
+    ));
+    // Trailing zeros are not extra precision
+    ledger.post_entry(entry(vec![line("1111", "1000.00", "0"), line("5111", "0", "1000")])).unwrap();
+
+    let mut policy = PrecisionPolicy::default();
+    policy.decimals.insert("VND".to_string(), 2);
+    let mut lenient = Ledger::new().with_precision(policy);
+    lenient.post_entry(fractional).unwrap();
+}
+
+#[test]
+fn test_foreign_amounts_follow_their_currency() {
+    let mut ledger = Ledger::new();
+    let cents = ForeignAmount::new("USD", d("10.505"), d("25000")).unwrap();
+    let usd = entry(vec![
+        line("1122", "262625", "0").with_foreign(cents).unwrap(),
+        line("411", "0", "262625"),
+    ]);
+    assert!(matches!(
+        ledger.post_entry(usd),
+        Err(LedgerError::PrecisionExceeded(currency, _, 2)) if currency == "USD"
+    ));
+}
+
+#[test]
+fn test_conversion_follows_rounding_mode() {
+    // 10,30 USD at 25.455 is 262.186,5 đồng
+    let foreign = ForeignAmount::new("USD", d("10.30"), d("25455")).unwrap();
+    let usd = |vnd: &str| {
+        entry(vec![line("1122", vnd, "0").with_foreign(foreign.clone()).unwrap(), line("411", "0", vnd)])
+    };
+    assert_eq!(foreign.functional_amount(&PrecisionPolicy::default()), d("262187"));
+    Ledger::new().post_entry(usd("262187")).unwrap();
+
+    let policy = PrecisionPolicy { rounding: RoundingMode::HalfEven, ..Default::default() };
+    assert_eq!(foreign.functional_amount(&policy), d("262186"));
+    let mut ledger = Ledger::new().with_precision(policy);
+    assert!(matches!(
+        ledger.post_entry(usd("262187")),
+        Err(LedgerError::CurrencyAmountMismatch(_, _, converted)) if converted == d("262186")
+    ));
+    ledger.post_entry(usd("262186")).unwrap();
+}
+
+// --- Allocation Tests ---
+#[test]
+fn test_allocation_books_rounding_difference() {
+    let policy = PrecisionPolicy::default();
+    let thirds = policy.allocate("VND", d("100"), &[Decimal::ONE, Decimal::ONE, Decimal::ONE]).unwrap();
+    assert_eq!(thirds.parts, vec![d("33"), d("33"), d("33")]);
+    assert_eq!(thirds.rounding_difference, d("1"));
+
+    let targets: Vec<(AccountCode, Decimal)> = ["6421", "6422", "6423"]
+        .iter()
+        .map(|a| (AccountCode::new(a).unwrap(), Decimal::ONE))
+        .collect();
+    let mut lines = policy.allocation_lines(d("100"), &targets, true).unwrap();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[3].account.as_str(), "811");
+    assert_eq!(lines[3].debit, d("1"));
+    lines.push(line("1111", "0", "100"));
+    assert!(entry(lines).is_balanced());
+
+    // Parts rounded up exceed the total: the excess is credited to 711
+    let lines = policy.allocation_lines(d("200"), &targets, true).unwrap();
+    assert_eq!(lines[3].account.as_str(), "711");
+    assert_eq!(lines[3].credit, d("1"));
+
+    assert!(matches!(policy.allocate("VND", d("100"), &[]), Err(LedgerError::InvalidAllocation(_))));
+    assert!(matches!(
+        policy.allocate("VND", d("100.5"), &[Decimal::ONE]),
+        Err(LedgerError::PrecisionExceeded(_, _, _))
+    ));
+}
+
+#[test]
+fn test_policy_from_yaml_keeps_defaults() {
+    let policy = PrecisionPolicy::from_yaml("tax_rounding: Down\n").unwrap();
+    assert_eq!(policy.tax_rounding, RoundingMode::Down);
+    assert_eq!(policy.decimals_for("VND"), 0);
+    assert_eq!(policy.decimals_for("CHF"), 2);
+
+    let ledger = Ledger::new().with_precision(policy.clone());
+    let json = serde_json::to_string(&ledger).unwrap();
+    let restored: Ledger = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.precision(), &policy);
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use chrono::{Datelike, NaiveDate};
+use gl_core::{
+    AuditAction, CoaConfig, JournalEntry, Ledger, LedgerError, LedgerEvent, Period, PeriodManager, RecurringTemplates,
+    Schedule, SkipReason,
+};
+use rust_decimal::Decimal;
+
+use common::{line, ymd};
+
+fn coa() -> CoaConfig {
+    common::coa(&["1121", "211", "214", "331", "642"])
+}
+
+/// Months January to `last` of 2025 open.
+fn periods(last: u32) -> PeriodManager {
+    let mut periods = PeriodManager::empty();
+    for month in 1..=last {
+        periods.add_period(Period::month(ymd(2025, month, 1)).unwrap());
+    }
+    periods
+}
+
+const TEMPLATES: &str = r#"
+templates:
+  - code: RENT
+    description: Tiền thuê văn phòng tháng {period}
+    schedule: { every: monthly, day: 5 }
+    start: 2025-01-01
+    lines:
+      - { account: "642", debit: 15000000 }
+      - { account: "331", credit: 15000000 }
+  - code: DEPR
+    description: Khấu hao TSCĐ tháng {period}
+    schedule: { every: end_of_month }
+    start: 2025-01-01
+    end: 2025-12-31
+    lines:
+      - { account: "642", debit: "balance(211) / 36" }
+      - { account: "214" }
+"#;
+
+fn ledger() -> Ledger {
+    let templates = RecurringTemplates::from_yaml(TEMPLATES).unwrap();
+    let mut ledger = Ledger::new().with_recurring(templates);
+    let purchase = JournalEntry::new(
+        ymd(2025, 1, 2),
+        vec![line("211", 100_000_000, 0), line("1121", 0, 100_000_000)],
+        "Mua máy tính".to_string(),
+    )
+    .unwrap();
+    ledger.post_entry(purchase).unwrap();
+    ledger
+}
+
+// --- Recurring Entry Tests ---
+#[test]
+fn test_schedule_dates() {
+    let monthly = Schedule::Monthly { day: 31 };
+    assert_eq!(
+        monthly.dates(ymd(2025, 1, 15), ymd(2025, 4, 30)),
+        vec![ymd(2025, 1, 31), ymd(2025, 2, 28), ymd(2025, 3, 31), ymd(2025, 4, 30)]
+    );
+    let quarterly = Schedule::Quarterly { day: 10 };
+    assert_eq!(
+        quarterly.dates(ymd(2025, 2, 1), ymd(2025, 12, 31)),
+        vec![ymd(2025, 2, 10), ymd(2025, 5, 10), ymd(2025, 8, 10), ymd(2025, 11, 10)]
+    );
+    assert_eq!(Schedule::EndOfMonth.dates(ymd(2024, 2, 1), ymd(2024, 2, 29)), vec![ymd(2024, 2, 29)]);
+}
+
+#[test]
+fn test_run_posts_due_occurrences_once() {
+    let mut ledger = ledger();
+    let run = ledger.run_recurring(&coa(), &periods(3), ymd(2025, 3, 31)).unwrap();
+    assert_eq!(run.posted.len(), 6);
+    assert!(run.failed.is_empty() && run.skipped.is_empty());
+    // Oldest first, across templates
+    let dates: Vec<NaiveDate> = run.posted.iter().map(|g| g.date).collect();
+    assert!(dates.windows(2).all(|w| w[0] <= w[1]));
+
+    let rent = ledger.get_entry(run.posted[0].entry_id).unwrap();
+    assert_eq!(rent.entry.description, "Tiền thuê văn phòng tháng 01/2025");
+    assert_eq!(rent.entry.lines[0].debit, Decimal::from(15_000_000));
+    // 100.000.000 / 36 rounded to whole đồng, booked to 214 by the balancing line
+    let depreciation = ledger.get_entry(run.posted[1].entry_id).unwrap();
+    assert_eq!(depreciation.entry.date, ymd(2025, 1, 31));
+    assert_eq!(depreciation.entry.lines[1].account.as_str(), "214");
+    assert_eq!(depreciation.entry.lines[1].credit, Decimal::from(2_777_778));
+
+    let again = ledger.run_recurring(&coa(), &periods(3), ymd(2025, 3, 31)).unwrap();
+    assert!(again.posted.is_empty());
+    assert_eq!(ledger.generated_entries().len(), 6);
+    assert!(matches!(ledger.events().last().unwrap().event, LedgerEvent::RecurringEntryPosted { .. }));
+    assert_eq!(ledger.audit_log().events().last().unwrap().action, AuditAction::RecurringEntryPosted);
+}
+
+#[test]
+fn test_occurrences_wait_for_their_period() {
+    let mut ledger = ledger();
+    let run = ledger.run_recurring(&coa(), &periods(1), ymd(2025, 2, 28)).unwrap();
+    assert_eq!(run.posted.len(), 2);
+    assert_eq!(run.skipped.len(), 2);
+    assert!(run.skipped.iter().all(|(o, reason)| o.date.month0() == 1 && *reason == SkipReason::PeriodNotOpen));
+
+    let run = ledger.run_recurring(&coa(), &periods(2), ymd(2025, 2, 28)).unwrap();
+    assert_eq!(run.posted.len(), 2);
+    assert!(run.skipped.is_empty());
+}
+
+#[test]
+fn test_generated_entries_survive_reload() {
+    let mut ledger = ledger();
+    ledger.run_recurring(&coa(), &periods(2), ymd(2025, 2, 28)).unwrap();
+    let json = serde_json::to_string(&ledger).unwrap();
+    let mut reloaded: Ledger = serde_json::from_str(&json).unwrap();
+    assert_eq!(reloaded.recurring().templates.len(), 2);
+    assert_eq!(reloaded.generated_entries(), ledger.generated_entries());
+
+    let run = reloaded.run_recurring(&coa(), &periods(3), ymd(2025, 3, 31)).unwrap();
+    assert_eq!(run.posted.len(), 2);
+    assert!(run.posted.iter().all(|g| g.date.month() == 3));
+}
+
+#[test]
+fn test_variable_amounts_and_failures() {
+    let templates = RecurringTemplates::from_yaml(
+        r#"
+templates:
+  - code: POWER
+    description: Tiền điện tháng {period}
+    schedule: { every: monthly, day: 20 }
+    start: 2025-01-01
+    lines:
+      - { account: "642", debit: { "2025-01": 2100000, "2025-02": 1850000 } }
+      - { account: "331" }
+  - code: BAD
+    description: Tài khoản không có trong hệ thống
+    schedule: { every: monthly, day: 20 }
+    start: 2025-01-01
+    lines:
+      - { account: "6428", debit: 1000 }
+      - { account: "999", credit: 1000 }
+"#,
+    )
+    .unwrap();
+    let mut ledger = Ledger::new().with_recurring(templates);
+    let run = ledger.run_recurring(&coa(), &periods(3), ymd(2025, 3, 31)).unwrap();
+    let amounts: Vec<Decimal> =
+        run.posted.iter().map(|g| ledger.get_entry(g.entry_id).unwrap().entry.lines[0].debit).collect();
+    assert_eq!(amounts, vec![Decimal::from(2_100_000), Decimal::from(1_850_000)]);
+    assert_eq!(run.failed.len(), 4);
+    assert!(matches!(&run.failed[0].1, LedgerError::UnknownAccount(code) if code == "999"));
+    let (march, error) = run.failed.iter().find(|(o, _)| o.template == "POWER").unwrap();
+    assert_eq!(march.date, ymd(2025, 3, 20));
+    assert!(matches!(error, LedgerError::InvalidTemplate(reason) if reason.contains("2025-03")));
+}
+
+#[test]
+fn test_invalid_templates_are_rejected() {
+    let template = |schedule: &str, lines: &str| {
+        format!(
+            "templates:\n  - code: T\n    description: x\n    schedule: {}\n    start: 2025-01-01\n    lines: {}\n",
+            schedule, lines
+        )
+    };
+    let lines = r#"[{ account: "642", debit: 1 }, { account: "331" }]"#;
+    assert!(RecurringTemplates::from_yaml(&template("{ every: monthly, day: 5 }", lines)).is_ok());
+    assert!(RecurringTemplates::from_yaml(&template("{ every: monthly, day: 32 }", lines)).is_err());
+    let formula = r#"[{ account: "642", debit: "balance(211) /" }, { account: "331" }]"#;
+    assert!(RecurringTemplates::from_yaml(&template("{ every: end_of_month }", formula)).is_err());
+    let two_balancing = r#"[{ account: "642" }, { account: "331" }]"#;
+    assert!(RecurringTemplates::from_yaml(&template("{ every: end_of_month }", two_balancing)).is_err());
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use gl_core::{
+    balance_sheet, import_xlsx, income_statement, ledger_book, AccountCode, ColumnMapping, ImportMode, JournalEntry,
+    Ledger, ReportWorkbook,
+};
+use rust_decimal::Decimal;
+use rust_xlsxwriter::Workbook;
+use std::io::Cursor;
+
+use common::{date, line};
+
+fn entry(day: u32, lines: &[(&str, i64, i64)], description: &str) -> JournalEntry {
+    JournalEntry::new(
+        date(day),
+        lines.iter().map(|(a, d, c)| line(a, *d, *c)).collect(),
+        description.to_string(),
+    )
+    .unwrap()
+}
+
+fn sample_ledger() -> Ledger {
+    let mut ledger = Ledger::new();
+    ledger
+        .post_entry(entry(1, &[("1111", 500_000, 0), ("411", 0, 500_000)], "Góp vốn"))
+        .unwrap();
+    ledger
+        .post_entry(entry(5, &[("131", 220_000, 0), ("5111", 0, 200_000), ("3331", 0, 20_000)], "Bán hàng"))
+        .unwrap();
+    ledger
+        .post_entry(entry(10, &[("642", 50_000, 0), ("1111", 0, 50_000)], "Chi phí"))
+        .unwrap();
+    ledger
+}
+
+fn amount(lines: &[gl_core::ReportLine], code: &str) -> Decimal {
+    lines.iter().find(|l| l.code == code).unwrap().amount
+}
+
+// --- Sổ cái Tests ---
+#[test]
+fn test_ledger_book_running_balance() {
+    let ledger = sample_ledger();
+    let book = ledger_book(&ledger, &AccountCode::new("1111").unwrap(), date(2), date(31));
+
+    assert_eq!(book.opening_balance, Decimal::from(500_000));
+    assert_eq!(book.lines.len(), 1);
+    assert_eq!(book.lines[0].counter_accounts, vec![AccountCode::new("642").unwrap()]);
+    assert_eq!(book.lines[0].balance, Decimal::from(450_000));
+    assert_eq!(book.closing_balance, Decimal::from(450_000));
+}
+
+// --- B01-DNN / B02-DNN Tests ---
+#[test]
+fn test_balance_sheet_balances() {
+    let bs = balance_sheet(&sample_ledger(), date(31));
+
+    assert_eq!(bs.total_assets, Decimal::from(670_000));
+    assert_eq!(bs.total_assets, bs.total_sources);
+    assert_eq!(amount(&bs.lines, "110"), Decimal::from(450_000));
+    assert_eq!(amount(&bs.lines, "313"), Decimal::from(20_000));
+    assert_eq!(amount(&bs.lines, "417"), Decimal::from(150_000));
+}
+
+#[test]
+fn test_income_statement() {
+    let is = income_statement(&sample_ledger(), date(1), date(31));
+
+    assert_eq!(amount(&is.lines, "01"), Decimal::from(200_000));
+    assert_eq!(amount(&is.lines, "24"), Decimal::from(50_000));
+    assert_eq!(is.profit_after_tax, Decimal::from(150_000));
+}
+
+// --- XLSX Tests ---
+#[test]
+fn test_export_reports_to_xlsx() {
+    let ledger = sample_ledger();
+    let mut workbook = ReportWorkbook::new();
+    workbook.add_trial_balance(&ledger.trial_balance()).unwrap();
+    workbook
+        .add_ledger_book(&ledger_book(&ledger, &AccountCode::new("1111").unwrap(), date(1), date(31)))
+        .unwrap();
+    workbook.add_balance_sheet(&balance_sheet(&ledger, date(31))).unwrap();
+    workbook.add_income_statement(&income_statement(&ledger, date(1), date(31))).unwrap();
+
+    let bytes = workbook.to_bytes().unwrap();
+    assert!(bytes.starts_with(b"PK"));
+}
+
+#[test]
+fn test_import_xlsx_uses_csv_column_mapping() {
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    for (col, h) in ["voucher", "date", "account", "debit", "credit", "description"].iter().enumerate() {
+        sheet.write_string(0, col as u16, *h).unwrap();
+    }
+    let rows = [
+        ("PT0001", "1111", 1_000_000.5, 0.0),
+        ("PT0001", "5111", 0.0, 1_000_000.5),
+        ("PC0001", "642", 70_000.0, 0.0),
+        ("PC0001", "1111", 0.0, 60_000.0),
+    ];
+    for (i, (voucher, account, debit, credit)) in rows.iter().enumerate() {
+        let row = i as u32 + 1;
+        sheet.write_string(row, 0, *voucher).unwrap();
+        sheet.write_string(row, 1, "02/01/2025").unwrap();
+        sheet.write_string(row, 2, *account).unwrap();
+        sheet.write_number(row, 3, *debit).unwrap();
+        sheet.write_number(row, 4, *credit).unwrap();
+        sheet.write_string(row, 5, "Nhập từ Excel").unwrap();
+    }
+    let bytes = workbook.save_to_buffer().unwrap();
+
+    let result = import_xlsx(
+        Cursor::new(bytes),
+        None,
+        &ColumnMapping::default(),
+        &ImportMode::BestEffort,
+    )
+    .unwrap();
+
+    assert_eq!(result.vouchers.len(), 1);
+    assert_eq!(result.vouchers[0].entry.lines[0].debit, Decimal::new(10_000_005, 1));
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].line, 4);
+}
+
+#[test]
+fn test_import_xlsx_rejects_rows_with_numbers_out_of_range() {
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    for (col, h) in ["voucher", "date", "account", "debit", "credit"].iter().enumerate() {
+        sheet.write_string(0, col as u16, *h).unwrap();
+    }
+    let rows = [("PT0001", "1111", 500.0, 0.0), ("PT0001", "5111", 0.0, 500.0), ("PC0001", "642", 1e30, 0.0), ("PC0001", "1111", 0.0, 1e30)];
+    for (i, (voucher, account, debit, credit)) in rows.iter().enumerate() {
+        let row = i as u32 + 1;
+        sheet.write_string(row, 0, *voucher).unwrap();
+        sheet.write_string(row, 1, "02/01/2025").unwrap();
+        sheet.write_string(row, 2, *account).unwrap();
+        sheet.write_number(row, 3, *debit).unwrap();
+        sheet.write_number(row, 4, *credit).unwrap();
+    }
+    let bytes = workbook.save_to_buffer().unwrap();
+
+    let mapping = ColumnMapping::default();
+    let result = import_xlsx(Cursor::new(bytes), None, &mapping, &ImportMode::BestEffort).unwrap();
+    // Not posted as zero amounts: the rows are rejected with their numbers
+    assert_eq!(result.vouchers.len(), 1);
+    let lines: Vec<usize> = result.errors.iter().map(|e| e.line).collect();
+    assert_eq!(lines, vec![4, 5]);
+    assert!(result.errors[0].message.contains("invalid amount"), "{}", result.errors[0]);
+}
+
+#[test]
+fn test_ledger_book_shows_reversal_link() {
+    let mut ledger = sample_ledger();
+    let reversal = ledger.reverse_entry(3, date(11), "Ghi nhầm").unwrap();
+    let book = ledger_book(&ledger, &AccountCode::new("642").unwrap(), date(1), date(31));
+
+    assert_eq!(book.lines.len(), 2);
+    assert_eq!(book.lines[0].reversed_by, Some(reversal));
+    assert_eq!(book.lines[1].reverses, Some(3));
+    assert_eq!(book.closing_balance, Decimal::ZERO);
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use gl_core::{
+    customer_balances, customer_statement, reconcile_receivables, CoaConfig, Customer, CustomerMaster, JournalEntry,
+    Ledger, LedgerError, PeriodManager,
+};
+use rust_decimal::Decimal;
+
+use common::{date, line};
+
+/// A sale on credit, to `customer` when given.
+fn credit_sale(day: u32, customer: Option<&str>, amount: i64) -> JournalEntry {
+    let mut receivable = line("131", amount, 0);
+    if let Some(customer) = customer {
+        receivable = receivable.with_party(customer);
+    }
+    JournalEntry::new(
+        date(day),
+        vec![receivable, line("5111", 0, amount)],
+        format!("Bán hàng ngày {}", day),
+    )
+    .unwrap()
+}
+
+fn receipt(day: u32, customer: &str, amount: i64) -> JournalEntry {
+    JournalEntry::new(
+        date(day),
+        vec![line("1111", amount, 0), line("131", 0, amount).with_party(customer)],
+        format!("Thu tiền ngày {}", day),
+    )
+    .unwrap()
+}
+
+fn customer(code: &str, credit_limit: Option<i64>) -> Customer {
+    Customer {
+        code: code.to_string(),
+        name: format!("Khách hàng {}", code),
+        tax_code: "0309876543".to_string(),
+        address: "TP.HCM".to_string(),
+        credit_limit: credit_limit.map(Decimal::from),
+    }
+}
+
+fn ar_ledger() -> Ledger {
+    let mut ledger = Ledger::new();
+    ledger.add_customer(customer("KH001", Some(1_000))).unwrap();
+    ledger.add_customer(customer("KH002", None)).unwrap();
+    ledger
+}
+
+// --- Customer Master Tests ---
+#[test]
+fn test_customer_master_from_yaml_and_duplicates() {
+    let master = CustomerMaster::from_yaml(
+        "customers:\n  - code: KH001\n    name: Minh An\n    tax_code: '0309876543'\n    credit_limit: 5000\n",
+    )
+    .unwrap();
+    assert_eq!(master.get("KH001").unwrap().credit_limit, Some(Decimal::from(5_000)));
+
+    let mut ledger = Ledger::new().with_customers(master);
+    assert!(matches!(ledger.add_customer(customer("KH001", None)), Err(LedgerError::DuplicateCustomer(_))));
+    ledger.update_customer(customer("KH001", Some(9_000))).unwrap();
+    assert_eq!(ledger.customers().get("KH001").unwrap().credit_limit, Some(Decimal::from(9_000)));
+    assert_eq!(ledger.audit_log().events().len(), 1);
+}
+
+#[test]
+fn test_receivable_lines_need_known_customer() {
+    let mut ledger = ar_ledger();
+    assert!(matches!(ledger.post_entry(credit_sale(2, None, 100)), Err(LedgerError::MissingParty(_))));
+    assert!(matches!(ledger.post_entry(credit_sale(2, Some("KH999"), 100)), Err(LedgerError::UnknownCustomer(_))));
+    assert!(ledger.post_entry(credit_sale(2, Some("KH002"), 100)).is_ok());
+
+    // Without a customer master, 131 lines are not checked
+    assert!(Ledger::new().post_entry(credit_sale(2, None, 100)).is_ok());
+}
+
+#[test]
+fn test_credit_limit() {
+    let mut ledger = ar_ledger();
+    ledger.post_entry(credit_sale(2, Some("KH001"), 800)).unwrap();
+    assert!(matches!(
+        ledger.post_entry(credit_sale(3, Some("KH001"), 300)),
+        Err(LedgerError::CreditLimitExceeded(_, _, _))
+    ));
+    ledger.post_entry(receipt(4, "KH001", 500)).unwrap();
+    assert!(ledger.post_entry(credit_sale(5, Some("KH001"), 300)).is_ok());
+}
+
+#[test]
+fn test_batch_credit_limit_counts_earlier_entries() {
+    let coa = CoaConfig {
+        accounts: ["131", "5111"]
+            .iter()
+            .map(|c| gl_core::Account {
+                code: c.to_string(),
+                name: String::new(),
+                classification: String::new(),
+                default_balance: String::new(),
+                description: String::new(),
+            })
+            .collect(),
+    };
+    let mut ledger = ar_ledger();
+    let batch = vec![credit_sale(2, Some("KH001"), 600), credit_sale(3, Some("KH001"), 600)];
+    let errors = ledger.post_batch(batch, &coa, &PeriodManager::new()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].index, 1);
+    assert!(ledger.entries().is_empty());
+}
+
+// --- Subledger Tests ---
+#[test]
+fn test_balances_statement_and_reconciliation() {
+    let mut ledger = ar_ledger();
+    ledger.post_entry(credit_sale(2, Some("KH001"), 700)).unwrap();
+    ledger.post_entry(credit_sale(3, Some("KH002"), 2_000)).unwrap();
+    ledger.post_entry(receipt(10, "KH001", 300)).unwrap();
+    ledger.post_entry(credit_sale(20, Some("KH001"), 100)).unwrap();
+
+    let balances = customer_balances(&ledger, date(31));
+    assert_eq!(balances.len(), 2);
+    assert_eq!(balances[0].balance, Decimal::from(500));
+    assert_eq!(balances[1].name, "Khách hàng KH002");
+
+    let statement = customer_statement(&ledger, "KH001", date(5), date(31)).unwrap();
+    assert_eq!(statement.opening_balance, Decimal::from(700));
+    assert_eq!(statement.lines.len(), 2);
+    assert_eq!(statement.lines[0].credit, Decimal::from(300));
+    assert_eq!(statement.closing_balance, Decimal::from(500));
+
+    let reconciliation = reconcile_receivables(&ledger, date(31)).unwrap();
+    assert_eq!(reconciliation.ledger_balance, Decimal::from(2_500));
+    assert!(matches!(customer_statement(&ledger, "KH999", date(1), date(31)), Err(LedgerError::UnknownCustomer(_))));
+}
+
+#[test]
+fn test_reconciliation_fails_when_subledger_differs() {
+    // Posted before the customer master existed, so the 131 line has no customer
+    let mut ledger = Ledger::new();
+    ledger.post_entry(credit_sale(2, None, 400)).unwrap();
+    ledger.post_entry(credit_sale(3, Some("KH001"), 100)).unwrap();
+
+    match reconcile_receivables(&ledger, date(31)) {
+        Err(LedgerError::SubledgerMismatch(account, subledger, gl)) => {
+            assert_eq!(account, "131");
+            assert_eq!(subledger, Decimal::from(100));
+            assert_eq!(gl, Decimal::from(500));
+        }
+        other => panic!("expected mismatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_reversal_keeps_customer_reference() {
+    let mut ledger = ar_ledger();
+    let id = ledger.post_entry(credit_sale(2, Some("KH001"), 900)).unwrap();
+    ledger.reverse_entry(id, date(3), "Sai khách hàng").unwrap();
+    assert_eq!(customer_balances(&ledger, date(31))[0].balance, Decimal::ZERO);
+    assert!(reconcile_receivables(&ledger, date(31)).is_ok());
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use chrono::NaiveDate;
+use gl_core::{CoaConfig, JournalEntry, Ledger, LedgerError, PeriodManager, Voucher, VoucherFormat, VoucherType};
+
+use common::{line, ymd};
+
+fn receipt(date: NaiveDate) -> JournalEntry {
+    JournalEntry::new(
+        date,
+        vec![line("1111", 100, 0), line("5111", 0, 100)],
+        "Thu tiền bán hàng".to_string(),
+    )
+    .unwrap()
+}
+
+fn number(ledger: &Ledger, id: u64) -> String {
+    ledger.get_entry(id).unwrap().entry.voucher.as_ref().unwrap().number.clone()
+}
+
+// --- Format Tests ---
+#[test]
+fn test_default_format() {
+    let format = VoucherFormat::default();
+    assert_eq!(format.voucher(VoucherType::PhieuThu, ymd(2025, 1, 5), 1).number, "PT2501-0001");
+    assert_eq!(format.voucher(VoucherType::PhieuKeToan, ymd(2025, 12, 5), 42).number, "PKT2512-0042");
+}
+
+#[test]
+fn test_custom_prefix_from_yaml() {
+    let format = VoucherFormat::from_yaml("prefixes:\n  BaoCo: GBC\ndigits: 3\n").unwrap();
+    assert_eq!(format.voucher(VoucherType::BaoCo, ymd(2025, 3, 1), 7).number, "GBC2503-007");
+    assert_eq!(format.voucher(VoucherType::PhieuChi, ymd(2025, 3, 1), 7).number, "PC2503-007");
+}
+
+#[test]
+fn test_parse_voucher_number() {
+    let format = VoucherFormat::default();
+    let voucher = format.parse("PKT2502-0010", ymd(2025, 2, 1)).unwrap();
+    assert_eq!(voucher.voucher_type, VoucherType::PhieuKeToan);
+    assert_eq!((voucher.year, voucher.month, voucher.sequence), (2025, 2, 10));
+    for bad in ["PT2501-01", "PT2513-0001", "XX2501-0001", "PT2501-0000", "PT25010001"] {
+        assert!(
+            matches!(format.parse(bad, ymd(2025, 1, 1)), Err(LedgerError::InvalidVoucherNumber(_))),
+            "{}",
+            bad
+        );
+    }
+}
+
+// --- Numbering Tests ---
+#[test]
+fn test_numbers_are_sequential_per_type_and_month() {
+    let mut ledger = Ledger::new();
+    let a = ledger.post_voucher(receipt(ymd(2025, 1, 2)), VoucherType::PhieuThu).unwrap();
+    let b = ledger.post_voucher(receipt(ymd(2025, 1, 3)), VoucherType::PhieuThu).unwrap();
+    let c = ledger.post_voucher(receipt(ymd(2025, 1, 3)), VoucherType::PhieuChi).unwrap();
+    let d = ledger.post_voucher(receipt(ymd(2025, 2, 1)), VoucherType::PhieuThu).unwrap();
+
+    assert_eq!(number(&ledger, a), "PT2501-0001");
+    assert_eq!(number(&ledger, b), "PT2501-0002");
+    assert_eq!(number(&ledger, c), "PC2501-0001");
+    assert_eq!(number(&ledger, d), "PT2502-0001");
+}
+
+#[test]
+fn test_rejects_duplicates_gaps_and_wrong_period() {
+    let format = VoucherFormat::default();
+    let mut ledger = Ledger::new();
+    ledger.post_voucher(receipt(ymd(2025, 1, 2)), VoucherType::PhieuThu).unwrap();
+
+    let with = |number: &str, d: NaiveDate| -> JournalEntry {
+        let voucher: Voucher = format.parse(number, d).unwrap();
+        receipt(d).with_voucher(voucher)
+    };
+    assert!(matches!(
+        ledger.post_entry(with("PT2501-0001", ymd(2025, 1, 5))),
+        Err(LedgerError::DuplicateVoucher(_))
+    ));
+    assert!(matches!(
+        ledger.post_entry(with("PT2501-0003", ymd(2025, 1, 5))),
+        Err(LedgerError::VoucherOutOfSequence(_, expected)) if expected == "PT2501-0002"
+    ));
+    assert!(matches!(
+        ledger.post_entry(with("PT2501-0002", ymd(2025, 2, 5))),
+        Err(LedgerError::VoucherPeriodMismatch(_, _))
+    ));
+    assert!(ledger.post_entry(with("PT2501-0002", ymd(2025, 1, 5))).is_ok());
+    assert_eq!(ledger.entries().len(), 2);
+}
+
+#[test]
+fn test_rejects_number_not_printed_from_its_parts() {
+    let format = VoucherFormat::default();
+    let mut ledger = Ledger::new();
+    let date = ymd(2025, 1, 5);
+    // Right series and sequence, but another number printed on it
+    let mut voucher = format.voucher(VoucherType::PhieuThu, date, 1);
+    voucher.number = "PT2501-0099".to_string();
+    assert!(matches!(
+        ledger.post_entry(receipt(date).with_voucher(voucher.clone())),
+        Err(LedgerError::InvalidVoucherNumber(number)) if number == "PT2501-0099"
+    ));
+    voucher.number = "PC2501-0001".to_string();
+    assert!(ledger.post_entry(receipt(date).with_voucher(voucher)).is_err());
+    assert!(ledger.entries().is_empty());
+}
+
+#[test]
+fn test_batch_vouchers_follow_each_other() {
+    let format = VoucherFormat::default();
+    let coa = CoaConfig { accounts: ["1111", "5111"].iter().map(|c| account(c)).collect() };
+    let periods = PeriodManager::new();
+    let mut ledger = Ledger::new();
+    let batch: Vec<JournalEntry> = ["PT2501-0001", "PT2501-0002"]
+        .iter()
+        .map(|n| receipt(ymd(2025, 1, 2)).with_voucher(format.parse(n, ymd(2025, 1, 2)).unwrap()))
+        .collect();
+    assert_eq!(ledger.post_batch(batch, &coa, &periods).unwrap(), 2);
+
+    let gap = vec![receipt(ymd(2025, 1, 3)).with_voucher(format.parse("PT2501-0004", ymd(2025, 1, 3)).unwrap())];
+    let errors = ledger.post_batch(gap, &coa, &periods).unwrap_err();
+    assert!(matches!(errors[0].error, LedgerError::VoucherOutOfSequence(_, _)));
+}
+
+#[test]
+fn test_numbering_survives_replay() {
+    let mut ledger = Ledger::new();
+    ledger.post_voucher(receipt(ymd(2025, 1, 2)), VoucherType::PhieuThu).unwrap();
+    let json = serde_json::to_string(&ledger).unwrap();
+    let mut loaded: Ledger = serde_json::from_str(&json).unwrap();
+    let id = loaded.post_voucher(receipt(ymd(2025, 1, 4)), VoucherType::PhieuThu).unwrap();
+    assert_eq!(number(&loaded, id), "PT2501-0002");
+}
+
+fn account(code: &str) -> gl_core::Account {
+    gl_core::Account {
+        code: code.to_string(),
+        name: String::new(),
+        classification: String::new(),
+        default_balance: String::new(),
+        description: String::new(),
+    }
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use gl_core::{AuditAction, CoaConfig, DraftStatus, JournalEntry, Ledger, LedgerError, Period, PeriodManager, Permission};
+
+use common::{date, dec, ledger_as, line};
+
+/// Built field by field: a draft may be unbalanced, which `JournalEntry::new` refuses.
+fn entry(debit: i64, credit: i64) -> JournalEntry {
+    JournalEntry {
+        date: date(10),
+        lines: vec![line("1111", debit, 0), line("5111", 0, credit)],
+        description: "Bán hàng".to_string(),
+        voucher: None,
+    }
+}
+
+fn ledger() -> Ledger {
+    ledger_as("ketoanvien")
+}
+
+fn coa() -> CoaConfig {
+    common::coa(&["1111", "5111"])
+}
+
+// --- Workflow Tests ---
+#[test]
+fn test_draft_to_posted() {
+    let mut ledger = ledger();
+    let id = ledger.save_draft(entry(1_000, 900)).unwrap();
+    assert!(ledger.entries().is_empty());
+    assert!(matches!(ledger.submit_draft(id), Err(LedgerError::UnbalancedEntry(..))));
+
+    ledger.update_draft(id, entry(1_000, 1_000)).unwrap();
+    ledger.submit_draft(id).unwrap();
+    assert!(ledger.trial_balance().balances.is_empty());
+
+    ledger.set_actor("ketoantruong");
+    ledger.approve_draft(id, Some("Đúng chứng từ")).unwrap();
+    assert_eq!(ledger.draft(id).unwrap().status, DraftStatus::Approved);
+    assert!(ledger.entries().is_empty());
+
+    let entry_id = ledger.post_draft(id, &coa(), &PeriodManager::new()).unwrap();
+    let draft = ledger.draft(id).unwrap();
+    assert_eq!(draft.status, DraftStatus::Posted);
+    assert_eq!(draft.entry_id, Some(entry_id));
+    assert_eq!(draft.approver.as_deref(), Some("ketoantruong"));
+    assert_eq!(ledger.trial_balance().total_debit, dec(1_000));
+}
+
+#[test]
+fn test_steps_out_of_order_are_refused() {
+    let mut ledger = ledger();
+    let id = ledger.save_draft(entry(500, 500)).unwrap();
+    assert!(matches!(ledger.post_draft(id, &coa(), &PeriodManager::new()), Err(LedgerError::InvalidDraftStatus(_, DraftStatus::Draft, _))));
+    ledger.submit_draft(id).unwrap();
+    assert!(matches!(ledger.update_draft(id, entry(600, 600)), Err(LedgerError::InvalidDraftStatus(..))));
+    assert!(matches!(ledger.draft(99), Err(LedgerError::DraftNotFound(99))));
+}
+
+#[test]
+fn test_approver_must_differ_from_preparer() {
+    let mut ledger = ledger();
+    ledger.set_actor("ketoantruong");
+    let id = ledger.save_draft(entry(500, 500)).unwrap();
+    ledger.submit_draft(id).unwrap();
+    assert!(matches!(ledger.approve_draft(id, None), Err(LedgerError::SelfApproval(_, _))));
+
+    ledger.set_actor("ketoanvien");
+    assert!(matches!(
+        ledger.approve_draft(id, None),
+        Err(LedgerError::PermissionDenied(_, Permission::ApproveEntry))
+    ));
+
+    ledger.set_actor("giamdoc");
+    ledger.approve_draft(id, None).unwrap();
+    // The director approves but does not post; the clerk posts the approved draft
+    assert!(matches!(ledger.post_draft(id, &coa(), &PeriodManager::new()), Err(LedgerError::PermissionDenied(_, Permission::PrepareEntry))));
+    ledger.set_actor("ketoanvien");
+    ledger.post_draft(id, &coa(), &PeriodManager::new()).unwrap();
+    assert_eq!(ledger.entries().len(), 1);
+}
+
+#[test]
+fn test_posting_checks_the_draft_against_coa_and_periods() {
+    let mut ledger = ledger();
+    let id = ledger.save_draft(entry(500, 500)).unwrap();
+    ledger.submit_draft(id).unwrap();
+    ledger.set_actor("ketoantruong");
+    ledger.approve_draft(id, None).unwrap();
+
+    let mut closed = PeriodManager::empty();
+    closed.add_period(Period::new(date(1), date(31)));
+    closed.close_current_period();
+    assert!(matches!(ledger.post_draft(id, &coa(), &closed), Err(LedgerError::DateNotInOpenPeriod(_))));
+    let without_sales = common::coa(&["1111"]);
+    let result = ledger.post_draft(id, &without_sales, &PeriodManager::new());
+    assert!(matches!(result, Err(LedgerError::UnknownAccount(code)) if code == "5111"));
+    assert_eq!(ledger.draft(id).unwrap().status, DraftStatus::Approved);
+    assert!(ledger.entries().is_empty());
+}
+
+#[test]
+fn test_rejection_returns_to_draft_with_comment() {
+    let mut ledger = ledger();
+    let id = ledger.save_draft(entry(500, 500)).unwrap();
+    ledger.submit_draft(id).unwrap();
+
+    ledger.set_actor("ketoantruong");
+    assert!(matches!(ledger.reject_draft(id, " "), Err(LedgerError::MissingRejectionComment(_))));
+    ledger.reject_draft(id, "Thiếu hóa đơn đầu vào").unwrap();
+    let draft = ledger.draft(id).unwrap();
+    assert_eq!(draft.status, DraftStatus::Draft);
+    assert_eq!(draft.rejection_comments().collect::<Vec<_>>(), vec!["Thiếu hóa đơn đầu vào"]);
+
+    ledger.set_actor("ketoanvien");
+    ledger.update_draft(id, entry(550, 550)).unwrap();
+    ledger.submit_draft(id).unwrap();
+    let actions: Vec<AuditAction> = ledger.audit_log().events().iter().map(|e| e.action.clone()).collect();
+    assert_eq!(
+        actions,
+        vec![
+            AuditAction::DraftSaved,
+            AuditAction::DraftSubmitted,
+            AuditAction::DraftRejected,
+            AuditAction::DraftSaved,
+            AuditAction::DraftSubmitted,
+        ]
+    );
+}
+
+#[test]
+fn test_drafts_survive_replay() {
+    let mut ledger = ledger();
+    let id = ledger.save_draft(entry(500, 400)).unwrap();
+    ledger.take_snapshot();
+    ledger.update_draft(id, entry(500, 500)).unwrap();
+
+    let json = serde_json::to_string(&ledger).unwrap();
+    let restored: Ledger = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.drafts().len(), 1);
+    assert_eq!(restored.draft(id).unwrap().history.len(), 2);
+    assert_eq!(ledger.state_at(1).unwrap().draft(id).unwrap().entry.lines[1].credit, dec(400));
+}